@@ -1,11 +1,14 @@
 //! Локальный AI через Ollama
 
 use super::tools::ToolRegistry;
+use crate::app::commands::registry;
 use crate::app::constants::{
     errors, messages, OLLAMA_CUSTOM_MODEL, OLLAMA_INSTALL_SCRIPT, OLLAMA_MODEL,
     OLLAMA_TIMEOUT_SECS, OLLAMA_URL,
 };
 use crate::app::desktop::DesktopEnvironment;
+use crate::app::exec::has_program;
+use crate::app::i18n::{tr, trf};
 use regex::Regex;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -65,11 +68,21 @@ impl LocalAi {
 
     /// Генерирует ответ на запрос пользователя
     pub async fn generate(&self, input: &str) -> Result<String, String> {
+        let response = self
+            .ask(&self.tools.generate_system_prompt(), input)
+            .await?;
+
+        // Обрабатываем инструменты и команды в ответе
+        Ok(self.process_response(&response))
+    }
+
+    /// Запрос со специальным системным промптом (без обработки инструментов)
+    pub async fn ask(&self, system: &str, input: &str) -> Result<String, String> {
         let payload = OllamaRequest {
             model: self.get_model(),
             prompt: input.to_string(),
             stream: false,
-            system: self.tools.generate_system_prompt(),
+            system: system.to_string(),
         };
 
         let response = self
@@ -78,15 +91,14 @@ impl LocalAi {
             .json(&payload)
             .send()
             .await
-            .map_err(|e| format!("{}: {}", errors::OLLAMA_CONNECTION, e))?;
+            .map_err(|e| format!("{}: {}", tr(errors::OLLAMA_CONNECTION), e))?;
 
         let data: OllamaResponse = response
             .json()
             .await
-            .map_err(|e| format!("{}: {}", errors::OLLAMA_PARSE, e))?;
+            .map_err(|e| format!("{}: {}", tr(errors::OLLAMA_PARSE), e))?;
 
-        // Обрабатываем инструменты и команды в ответе
-        Ok(self.process_response(&data.response))
+        Ok(data.response)
     }
 
     /// Обрабатывает маркеры [TOOL:...] и [CMD:...] в ответе
@@ -149,12 +161,12 @@ pub fn is_base_model_exists() -> bool {
 pub fn create_custom_model() -> String {
     // Проверяем, что базовая модель существует
     if !is_base_model_exists() {
-        return errors::MODEL_BASE_NOT_FOUND.to_string();
+        return tr(errors::MODEL_BASE_NOT_FOUND).to_string();
     }
 
     // Проверяем, не существует ли уже модель
     if is_custom_model_exists() {
-        return messages::MODEL_EXISTS.to_string();
+        return tr(messages::MODEL_EXISTS).to_string();
     }
 
     // Находим путь к Modelfile (рядом с исполняемым файлом или в текущей директории)
@@ -182,10 +194,10 @@ pub fn create_custom_model() -> String {
                 if std::fs::write(&modelfile_path, generate_modelfile_content()).is_ok() {
                     modelfile_path
                 } else {
-                    return errors::MODEL_CREATE_FAILED.to_string();
+                    return tr(errors::MODEL_CREATE_FAILED).to_string();
                 }
             } else {
-                return errors::MODEL_CREATE_FAILED.to_string();
+                return tr(errors::MODEL_CREATE_FAILED).to_string();
             }
         }
     };
@@ -196,20 +208,19 @@ pub fn create_custom_model() -> String {
         .arg(&modelfile)
         .output()
     {
-        Ok(output) if output.status.success() => messages::MODEL_CREATED.to_string(),
+        Ok(output) if output.status.success() => tr(messages::MODEL_CREATED).to_string(),
         Ok(output) => {
             let stderr = String::from_utf8_lossy(&output.stderr);
-            format!("{} ({})", errors::MODEL_CREATE_FAILED, stderr.trim())
+            format!("{} ({})", tr(errors::MODEL_CREATE_FAILED), stderr.trim())
         }
         Err(e) => {
-            format!("{} ({})", errors::MODEL_CREATE_FAILED, e)
+            format!("{} ({})", tr(errors::MODEL_CREATE_FAILED), e)
         }
     }
 }
 
-/// Генерирует содержимое Modelfile
-fn generate_modelfile_content() -> &'static str {
-    r#"FROM llama3
+/// Шаблон Modelfile; перевод на английский — в каталоге `i18n`
+pub const MODELFILE: &str = r#"FROM llama3
 
 SYSTEM """
 Ты Альфонс — умный помощник для Arch Linux. Отвечай кратко и по делу на русском языке.
@@ -222,31 +233,31 @@ SYSTEM """
 - [TOOL:cpu] - информация о процессоре
 
 ДОСТУПНЫЕ КОМАНДЫ (формат: [CMD:команда]):
-- [CMD:очистить] - очистить чат
-- [CMD:поиск <запрос>] - найти пакеты
-- [CMD:установить <пакет>] - установить (откроется диалог!)
-- [CMD:удалить <пакет>] - удалить (откроется диалог!)
-- [CMD:обновить систему] - обновить (откроется диалог!)
-- [CMD:гайд <тема>] - показать гайд (pacman, aur, wifi, systemd, gpu, audio)
-
+{}
 ПРАВИЛА:
 1. Команды установки/удаления ТОЛЬКО открывают диалог - НЕ говори "установлено"!
-2. Опасные команды (выключить, перезагрузить) - ТОЛЬКО по явному запросу!
+2. Опасные команды ({}) - ТОЛЬКО по явному запросу!
 """
 
 PARAMETER temperature 0.7
 PARAMETER top_p 0.9
 PARAMETER num_ctx 4096
-"#
+"#;
+
+/// Генерирует содержимое Modelfile; список команд берётся из реестра
+fn generate_modelfile_content() -> String {
+    trf(
+        MODELFILE,
+        &[
+            &registry::ai_command_list(),
+            &registry::critical_phrases().join(", "),
+        ],
+    )
 }
 
 /// Проверяет, установлена ли Ollama
 pub fn is_ollama_installed() -> bool {
-    Command::new("which")
-        .arg("ollama")
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
+    has_program("ollama")
 }
 
 /// Устанавливает Ollama через официальный скрипт в терминале
@@ -254,11 +265,11 @@ pub fn is_ollama_installed() -> bool {
 pub fn install_ollama() -> String {
     // Проверяем, не установлена ли уже
     if is_ollama_installed() {
-        return messages::OLLAMA_ALREADY.to_string();
+        return tr(messages::OLLAMA_ALREADY).to_string();
     }
 
     let cmd = format!("curl -fsSL {} | sh", OLLAMA_INSTALL_SCRIPT);
-    run_in_terminal(&cmd, "Установка Ollama")
+    run_in_terminal(&cmd, tr("Установка Ollama"))
 }
 
 /// Запускает команду в терминале (с учётом текущего DE)
@@ -268,12 +279,7 @@ fn run_in_terminal(cmd: &str, action: &str) -> String {
 
     for term in terminals {
         // Проверяем, установлен ли терминал
-        if !Command::new("which")
-            .arg(term)
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
-        {
+        if !has_program(term) {
             continue;
         }
 
@@ -285,15 +291,14 @@ fn run_in_terminal(cmd: &str, action: &str) -> String {
 
         // Запускаем
         match Command::new(term).args(&args).spawn() {
-            Ok(_) => return format!("[OK] {} запущено в {}", action, term),
+            Ok(_) => return trf("[OK] {} запущено в {}", &[&action, &term]),
             Err(_) => continue,
         }
     }
 
-    format!(
+    trf(
         "[X] Не найден терминал для {}. Установите {} или другой терминал.",
-        de.name(),
-        de.preferred_terminal()
+        &[&de.name(), &de.preferred_terminal()],
     )
 }
 
@@ -311,23 +316,23 @@ fn get_terminal_args(term: &str, cmd: &str) -> Option<Vec<String>> {
             "-e".to_string(),
             "sh".to_string(),
             "-c".to_string(),
-            format!("{}; echo 'Нажмите Enter...'; read", cmd),
+            trf("{}; echo 'Нажмите Enter...'; read", &[&cmd]),
         ],
         "gnome-terminal" | "kgx" => vec![
             "--".to_string(),
             "sh".to_string(),
             "-c".to_string(),
-            format!("{}; echo 'Нажмите Enter...'; read", cmd),
+            trf("{}; echo 'Нажмите Enter...'; read", &[&cmd]),
         ],
         "konsole" => vec![
             "-e".to_string(),
             "sh".to_string(),
             "-c".to_string(),
-            format!("{}; echo 'Нажмите Enter...'; read", cmd),
+            trf("{}; echo 'Нажмите Enter...'; read", &[&cmd]),
         ],
         "xfce4-terminal" => vec![
             "-e".to_string(),
-            format!("sh -c '{}; echo Нажмите Enter...; read'", cmd),
+            trf("sh -c '{}; echo Нажмите Enter...; read'", &[&cmd]),
         ],
         "xterm" => vec![
             "-hold".to_string(),
@@ -345,7 +350,7 @@ fn get_terminal_args(term: &str, cmd: &str) -> Option<Vec<String>> {
 pub fn start_ollama_service() -> String {
     // Проверяем, установлена ли Ollama
     if !is_ollama_installed() {
-        return errors::OLLAMA_INSTALL_FAILED.to_string();
+        return tr(errors::OLLAMA_INSTALL_FAILED).to_string();
     }
 
     // Запускаем ollama serve в фоне
@@ -355,10 +360,10 @@ pub fn start_ollama_service() -> String {
         Ok(_) => {
             // Даём серверу время запуститься
             std::thread::sleep(std::time::Duration::from_secs(2));
-            messages::OLLAMA_STARTED.to_string()
+            tr(messages::OLLAMA_STARTED).to_string()
         }
         Err(e) => {
-            format!("{} ({})", errors::OLLAMA_START_FAILED, e)
+            format!("{} ({})", tr(errors::OLLAMA_START_FAILED), e)
         }
     }
 }
@@ -1,15 +1,82 @@
+use super::super::commands::registry;
+use super::super::i18n::{tr, trf};
+use super::super::monitor::{
+    battery_summary, cpu_summary, disk_summary, memory_summary, network_rate_summary,
+    temperature_summary,
+};
 use chrono::Local;
 use std::collections::HashMap;
-use std::process::Command;
+
+/// Системный промпт; названия инструментов не переводятся — это идентификаторы.
+/// Английская версия лежит в каталоге `i18n` и велит отвечать по-английски
+pub const SYSTEM_PROMPT: &str = r#"Ты помощник Альфонс для Arch Linux. Отвечай кратко и по делу на русском языке.
+
+ДОСТУПНЫЕ ИНСТРУМЕНТЫ:
+{}
+Формат использования: [TOOL:название] или [TOOL:название:аргумент]
+
+ДОСТУПНЫЕ КОМАНДЫ (ты можешь выполнять их за пользователя):
+Формат: [CMD:команда]; <...> — обязательный аргумент, [...] — необязательный
+{}
+  Темы гайдов: pacman, aur, mirrors, wifi, systemd, gpu, audio, locale, backup
+  Перед обновлением снимок создаётся автоматически
+
+ВАЖНЫЕ ПРАВИЛА:
+1. Используй [TOOL:...] для получения информации (время, дата)
+2. Используй [CMD:...] для выполнения команд за пользователя
+3. КОМАНДЫ установки/удаления/обновления ТОЛЬКО открывают диалог! НЕ говори "установлено" или "обновлено" сразу!
+4. После команды установки скажи "откроется диалог подтверждения" или просто используй команду
+5. Если спрашивают "как установить" - объясни или предложи [CMD:гайд pacman]
+6. ОПАСНЫЕ КОМАНДЫ ({}) выполняй ТОЛЬКО если пользователь ЯВНО попросил это сделать!
+7. На вопросы "что ты умеешь?" или "какие команды есть?" - ОТВЕЧАЙ ТЕКСТОМ, НЕ выполняй команды!
+8. Команды терминала пиши в блоках ```bash ... ``` - пользователь сможет скопировать или выполнить их. Можно использовать **жирный**, `код`, списки и заголовки
+
+ПРИМЕРЫ:
+- "Который час?" -> "Сейчас [TOOL:время]"
+- "Установи firefox" -> "[CMD:установить firefox]" (НЕ говори "установлен"!)
+- "Найди пакет vim" -> "[CMD:поиск vim]"
+- "Как настроить wifi?" -> "[CMD:гайд wifi]"
+- "Подключи wifi" -> "[CMD:wifi]"
+- "Какой у меня IP?" -> "[TOOL:сеть]"
+- "Не перегревается ли ноутбук?" -> "[TOOL:температура]"
+- "Переключи звук на наушники" -> "[CMD:переключи звук на наушники]"
+- "Сделай погромче" -> "[CMD:громкость +10]"
+- "Что грузит процессор?" -> "[TOOL:топ_процессы]"
+- "Firefox завис" -> "[CMD:завершить процесс firefox]"
+- "Медленно качаются пакеты" -> "[CMD:зеркала ранжировать]"
+- "Есть ли уязвимости?" -> "[CMD:безопасность]"
+- "После обновления всё сломалось" -> "[CMD:откат]"
+- "Не работает видеокарта после обновления" -> "[TOOL:ядро]"
+- "Какие драйверы мне нужны?" -> "[TOOL:видеокарта]"
+- "Что с сервисами?" -> "[TOOL:сервисы_с_ошибками]"
+- "Почему не работает звук?" -> "[TOOL:ошибки_сервиса:pipewire]"
+- "Запусти bluetooth" -> "[CMD:запустить сервис bluetooth]"
+- "Покажи гайды" -> "[CMD:гайды]"
+- "Обнови систему" -> "[CMD:обновить систему]" (откроется диалог)
+- "Что ты умеешь?" -> Перечисли возможности ТЕКСТОМ, НЕ выполняй команды!
+- "Выключи компьютер" -> "[CMD:выключить пк]" (только по явному запросу!)
+- "Выключи комп через час" -> "[CMD:выключить через 1 час]"
+
+Отвечай кратко. НЕ пиши текст после команд установки/удаления/обновления."#;
 
 /// Тип функции-обработчика инструмента
 pub type ToolHandler = fn() -> String;
 
+/// Обработчик инструмента с аргументом (`[TOOL:название:аргумент]`)
+pub type ToolArgHandler = fn(&str) -> String;
+
+/// Способ вызова инструмента
+pub enum ToolKind {
+    Plain(ToolHandler),
+    /// Название аргумента (для промпта) и обработчик
+    WithArg(&'static str, ToolArgHandler),
+}
+
 /// Описание одного инструмента
 pub struct Tool {
     pub name: String,
     pub description: String,
-    pub handler: ToolHandler,
+    pub handler: ToolKind,
 }
 
 /// Контейнер для всех инструментов
@@ -46,25 +113,98 @@ impl ToolRegistry {
         registry.register(
             "список_гайдов",
             "показать доступные обучающие гайды",
-            || "pacman, aur, wifi, systemd, gpu, audio, locale, backup".to_string(),
+            || "pacman, aur, mirrors, wifi, systemd, gpu, audio, locale, backup".to_string(),
         );
 
         // Системная информация
-        registry.register("память", "показать использование RAM", get_memory_info);
+        registry.register(
+            "память",
+            "показать использование RAM и swap",
+            memory_summary,
+        );
+
+        registry.register("диск", "показать заполненность разделов", disk_summary);
+
+        registry.register("cpu", "показать процессор и его загрузку", cpu_summary);
+
+        registry.register(
+            "температура",
+            "температура датчиков (CPU, GPU, диски)",
+            temperature_summary,
+        );
+
+        registry.register("батарея", "заряд и состояние батареи", battery_summary);
+
+        registry.register("трафик", "текущая скорость сети", network_rate_summary);
+
+        registry.register(
+            "уязвимости",
+            "уязвимые установленные пакеты (CVE, важность, исправленная версия)",
+            super::super::commands::security::audit_summary,
+        );
+
+        registry.register(
+            "сервисы_с_ошибками",
+            "упавшие сервисы systemd (systemctl --failed)",
+            super::super::commands::services::failed_units,
+        );
+
+        registry.register(
+            "сеть",
+            "текущее сетевое подключение, IP и DNS",
+            super::super::commands::network::status_summary,
+        );
+
+        registry.register(
+            "звук",
+            "устройство вывода, громкость и что сейчас играет",
+            super::super::commands::audio::audio_status,
+        );
 
-        registry.register("диск", "показать использование дисков", get_disk_info);
+        registry.register(
+            "аудиоустройства",
+            "все устройства вывода и ввода звука",
+            super::super::commands::audio::audio_devices,
+        );
 
-        registry.register("cpu", "показать информацию о процессоре", get_cpu_info);
+        registry.register(
+            "видеокарта",
+            "видеокарты, загруженный драйвер и рекомендуемые пакеты",
+            super::super::commands::gpu::gpu_info,
+        );
+
+        registry.register(
+            "ядро",
+            "работающее и установленные ядра, нужна ли перезагрузка, записи загрузчика",
+            super::super::commands::kernels::kernel_info,
+        );
+
+        registry.register(
+            "снимки",
+            "последние снимки системы (snapper, timeshift или btrfs)",
+            super::super::commands::snapshots::snapshots_summary,
+        );
+
+        registry.register(
+            "топ_процессы",
+            "самые загруженные процессы (CPU и память)",
+            super::super::commands::processes::top_processes,
+        );
+
+        registry.register_with_arg(
+            "ошибки_сервиса",
+            "сервис",
+            "последние ошибки сервиса из журнала за текущую загрузку",
+            super::super::commands::journal::recent_errors,
+        );
 
         registry.register(
             "система",
             "показать общую информацию о системе",
             || {
-                format!(
+                trf(
                     "Память: {}\nCPU: {}\nДиск: {}",
-                    get_memory_info(),
-                    get_cpu_info(),
-                    get_disk_info()
+                    &[&memory_summary(), &cpu_summary(), &disk_summary()],
                 )
             },
         );
@@ -74,6 +214,21 @@ impl ToolRegistry {
 
     /// Регистрирует новый инструмент
     pub fn register(&mut self, name: &str, description: &str, handler: ToolHandler) {
+        self.insert(name, description, ToolKind::Plain(handler));
+    }
+
+    /// Регистрирует инструмент с аргументом
+    pub fn register_with_arg(
+        &mut self,
+        name: &str,
+        arg: &'static str,
+        description: &str,
+        handler: ToolArgHandler,
+    ) {
+        self.insert(name, description, ToolKind::WithArg(arg, handler));
+    }
+
+    fn insert(&mut self, name: &str, description: &str, handler: ToolKind) {
         self.tools.insert(
             name.to_string(),
             Tool {
@@ -84,68 +239,33 @@ impl ToolRegistry {
         );
     }
 
-    /// Выполняет инструмент по имени
-    pub fn execute(&self, name: &str) -> Option<String> {
-        self.tools.get(name).map(|tool| (tool.handler)())
+    /// Выполняет инструмент: "название" или "название:аргумент"
+    pub fn execute(&self, spec: &str) -> Option<String> {
+        let (name, arg) = spec.split_once(':').unwrap_or((spec, ""));
+        self.tools.get(name).map(|tool| match tool.handler {
+            ToolKind::Plain(handler) => handler(),
+            ToolKind::WithArg(_, handler) => handler(arg.trim()),
+        })
     }
 
     /// Генерирует системный промпт с описанием всех инструментов
     pub fn generate_system_prompt(&self) -> String {
         let mut tools_list = String::new();
         for tool in self.tools.values() {
-            tools_list.push_str(&format!("- [TOOL:{}] - {}\n", tool.name, tool.description));
+            let usage = match tool.handler {
+                ToolKind::Plain(_) => tool.name.clone(),
+                ToolKind::WithArg(arg, _) => format!("{}:<{}>", tool.name, tr(arg)),
+            };
+            tools_list.push_str(&format!("- [TOOL:{}] - {}\n", usage, tr(&tool.description)));
         }
 
-        format!(
-            r#"Ты помощник Альфонс для Arch Linux. Отвечай кратко и по делу на русском языке.
-
-ДОСТУПНЫЕ ИНСТРУМЕНТЫ:
-{}
-Формат использования: [TOOL:название]
-
-ДОСТУПНЫЕ КОМАНДЫ (ты можешь выполнять их за пользователя):
-Формат: [CMD:команда]
-
-▸ Базовые:
-  [CMD:очистить] - очистить чат
-  [CMD:помощь] - показать справку
-
-▸ Пакеты (yay/pacman):
-  [CMD:поиск <запрос>] - найти пакеты
-  [CMD:установить <пакет>] - запросить установку (откроется диалог подтверждения!)
-  [CMD:удалить <пакет>] - запросить удаление (откроется диалог подтверждения!)
-  [CMD:обновить систему] - запросить обновление (откроется диалог подтверждения!)
-
-▸ Система:
-  [CMD:выключить пк] - выключить компьютер
-  [CMD:перезагрузить] - перезагрузить компьютер
-
-▸ Гайды:
-  [CMD:гайды] - показать список всех гайдов
-  [CMD:гайд <тема>] - показать конкретный гайд
-  Доступные темы: pacman, aur, wifi, systemd, gpu, audio, locale, backup
-
-ВАЖНЫЕ ПРАВИЛА:
-1. Используй [TOOL:...] для получения информации (время, дата)
-2. Используй [CMD:...] для выполнения команд за пользователя
-3. КОМАНДЫ установки/удаления/обновления ТОЛЬКО открывают диалог! НЕ говори "установлено" или "обновлено" сразу!
-4. После команды установки скажи "откроется диалог подтверждения" или просто используй команду
-5. Если спрашивают "как установить" - объясни или предложи [CMD:гайд pacman]
-6. ОПАСНЫЕ КОМАНДЫ (выключить пк, перезагрузить) выполняй ТОЛЬКО если пользователь ЯВНО попросил это сделать!
-7. На вопросы "что ты умеешь?" или "какие команды есть?" - ОТВЕЧАЙ ТЕКСТОМ, НЕ выполняй команды!
-
-ПРИМЕРЫ:
-- "Который час?" -> "Сейчас [TOOL:время]"
-- "Установи firefox" -> "[CMD:установить firefox]" (НЕ говори "установлен"!)
-- "Найди пакет vim" -> "[CMD:поиск vim]"
-- "Как настроить wifi?" -> "[CMD:гайд wifi]"
-- "Покажи гайды" -> "[CMD:гайды]"
-- "Обнови систему" -> "[CMD:обновить систему]" (откроется диалог)
-- "Что ты умеешь?" -> Перечисли возможности ТЕКСТОМ, НЕ выполняй команды!
-- "Выключи компьютер" -> "[CMD:выключить пк]" (только по явному запросу!)
-
-Отвечай кратко. НЕ пиши текст после команд установки/удаления/обновления."#,
-            tools_list
+        trf(
+            SYSTEM_PROMPT,
+            &[
+                &tools_list,
+                &registry::ai_command_list(),
+                &registry::critical_phrases().join(", "),
+            ],
         )
     }
 }
@@ -155,68 +275,3 @@ impl Default for ToolRegistry {
         Self::new()
     }
 }
-
-// ============================================================================
-// Функции получения системной информации
-// ============================================================================
-
-/// Получает информацию об использовании памяти
-fn get_memory_info() -> String {
-    let output = Command::new("free").args(["-h", "--si"]).output();
-
-    match output {
-        Ok(out) => {
-            let text = String::from_utf8_lossy(&out.stdout);
-            // Парсим вторую строку (Mem:)
-            if let Some(line) = text.lines().nth(1) {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 3 {
-                    return format!("{} / {} (использовано)", parts[2], parts[1]);
-                }
-            }
-            "Не удалось получить".into()
-        }
-        Err(_) => "Ошибка выполнения free".into(),
-    }
-}
-
-/// Получает информацию об использовании дисков
-fn get_disk_info() -> String {
-    let output = Command::new("df").args(["-h", "/"]).output();
-
-    match output {
-        Ok(out) => {
-            let text = String::from_utf8_lossy(&out.stdout);
-            if let Some(line) = text.lines().nth(1) {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 5 {
-                    return format!("{} / {} ({})", parts[2], parts[1], parts[4]);
-                }
-            }
-            "Не удалось получить".into()
-        }
-        Err(_) => "Ошибка выполнения df".into(),
-    }
-}
-
-/// Получает информацию о процессоре
-fn get_cpu_info() -> String {
-    // Имя процессора
-    let name = std::fs::read_to_string("/proc/cpuinfo")
-        .ok()
-        .and_then(|s| {
-            s.lines()
-                .find(|l| l.starts_with("model name"))
-                .and_then(|l| l.split(':').nth(1))
-                .map(|s| s.trim().to_string())
-        })
-        .unwrap_or_else(|| "Неизвестно".into());
-
-    // Загрузка (из /proc/loadavg)
-    let load = std::fs::read_to_string("/proc/loadavg")
-        .ok()
-        .and_then(|s| s.split_whitespace().next().map(|s| s.to_string()))
-        .unwrap_or_else(|| "?".into());
-
-    format!("{} (загрузка: {})", name, load)
-}
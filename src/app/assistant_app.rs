@@ -1,13 +1,27 @@
 //! Главная структура приложения
 
 use super::ai::local_provider::LocalAi;
-use super::chat::{ChatHistory, DialogState, InputHistory, TaskManager};
-use super::commands::{self, base::CMD_CLEAR_CHAT};
+use super::chat::{BackgroundTask, ChatHistory, DialogState, InputHistory, TaskManager};
+use super::cli::Status;
+use super::commands::{
+    self,
+    base::{
+        CMD_CLEAR_CHAT, CMD_EXPORT_CHAT_PREFIX, CMD_NEW_CHAT, CMD_OPEN_PANEL_PREFIX,
+        CMD_SEARCH_HISTORY_PREFIX, CMD_SHOW_SESSIONS,
+    },
+};
 use super::config::Config;
-use super::constants::messages;
+use super::constants::{messages, MONITOR_INTERVAL_MS, UPDATES_CHECK_INTERVAL_SECS, USER_SENDER};
 use super::desktop::{DeStyles, DesktopEnvironment};
+use super::export::{self, ExportFormat};
 use super::guides::GuideRegistry;
+use super::i18n::{self, tr, trf};
+use super::ipc::{AppState, Incoming, IpcServer, Request, Response};
+use super::macros::{self, Macro, MacroRun, StepOutcome};
+use super::monitor::Monitor;
+use super::sessions;
 use super::ui;
+use super::ui::panels::{Panel, PanelState};
 use eframe::egui;
 use regex::Regex;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -18,11 +32,21 @@ use std::time::{Duration, Instant};
 const OLLAMA_CHECK_INTERVAL: u64 = 30;
 
 /// Статический Regex для парсинга [CMD:...] маркеров
-fn cmd_regex() -> &'static Regex {
+pub fn cmd_regex() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
     RE.get_or_init(|| Regex::new(r"\[CMD:([^\]]+)\]").expect("Invalid CMD regex"))
 }
 
+/// Ответ ИИ из фоновой задачи
+struct AiReply {
+    /// Перегенерируемое сообщение и его вариант, созданный под этот ответ;
+    /// None — новый ответ в конец чата
+    target: Option<(u64, usize)>,
+    result: Result<String, String>,
+    /// Запрос Ask по IPC, ждущий этот ответ
+    reply_to: Option<mpsc::Sender<Response>>,
+}
+
 /// Центральное хранилище состояния приложения
 pub struct AssistantApp {
     // Данные
@@ -34,8 +58,25 @@ pub struct AssistantApp {
     // UI состояние
     pub input_text: String,
     pub show_settings: bool,
+    pub show_sessions: bool,
+    /// Переименование сессии: идентификатор и новое название
+    pub session_rename: Option<(String, String)>,
     pub dialog: DialogState,
     pub input_history: InputHistory,
+    /// Изменяемое сообщение пользователя (его текст в поле ввода)
+    pub editing: Option<u64>,
+    pub mirror_countries_input: String,
+    /// Пользовательские макросы и поля их редактора в настройках
+    pub macros: Vec<Macro>,
+    pub macro_name_input: String,
+    pub macro_steps_input: String,
+    /// Выполняемый макрос
+    macro_run: Option<MacroRun>,
+    pub panels: PanelState,
+    pub search: ui::search::SearchState,
+    pub export: ui::export::ExportState,
+    pub completion: ui::completion::CompletionState,
+    pub monitor: Monitor,
     pub ollama_online: Arc<AtomicBool>,
     pub ollama_installed: Arc<AtomicBool>,
     pub yay_installed: Arc<AtomicBool>,
@@ -50,19 +91,32 @@ pub struct AssistantApp {
     // Фоновые задачи
     pub tasks: TaskManager,
     task_receiver: mpsc::Receiver<String>,
+    /// Сколько результатов задач получено
+    tasks_finished: usize,
+    ai_sender: mpsc::Sender<AiReply>,
+    ai_receiver: mpsc::Receiver<AiReply>,
+
+    /// Сокет для других программ; None — уже открыт другим экземпляром
+    ipc: Option<IpcServer>,
 }
 
 impl AssistantApp {
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    /// `input` — текст для поля ввода (`alfons show "..."`)
+    pub fn new(cc: &eframe::CreationContext<'_>, input: Option<String>) -> Self {
         let (tasks, task_receiver) = TaskManager::new();
+        let (ai_sender, ai_receiver) = mpsc::channel();
         let config = Config::load();
+        i18n::set_language(config.language);
 
         // Определяем окружение рабочего стола
         let desktop_env = DesktopEnvironment::detect();
         let de_styles = DeStyles::for_de(desktop_env);
 
-        let mut chat = ChatHistory::default();
-        chat.add_message(&config.assistant_name, messages::WELCOME);
+        // Последняя сессия чата восстанавливается с диска
+        let mut chat = ChatHistory::restore(config.session_limit);
+        if chat.messages().next().is_none() {
+            chat.add_message(&config.assistant_name, tr(messages::WELCOME));
+        }
 
         let ai = Arc::new(LocalAi::new());
         ai.set_model(&config.ollama_model);
@@ -99,6 +153,28 @@ impl AssistantApp {
             custom_model_clone.store(exists, Ordering::SeqCst);
         });
 
+        let mirror_countries_input = config.mirror_countries.join(", ");
+
+        // Проверка уязвимостей в фоне (результат показывается в шапке)
+        let security_feed = config.security_feed.clone();
+        std::thread::spawn(move || {
+            let _ = super::commands::security::run_audit(&security_feed);
+        });
+
+        // Определение видеокарты в фоне (результат показывается в настройках)
+        std::thread::spawn(|| {
+            let _ = super::commands::gpu::detect();
+        });
+
+        // Доступные обновления для статуса по IPC
+        std::thread::spawn(|| loop {
+            let _ = super::commands::package::check_updates();
+            std::thread::sleep(Duration::from_secs(UPDATES_CHECK_INTERVAL_SECS));
+        });
+
+        // Пакеты и сервисы для автодополнения
+        super::completion::preload();
+
         // Проверяем, установлено ли приложение в систему
         let app_installed = Arc::new(AtomicBool::new(super::installer::is_installed()));
 
@@ -107,10 +183,23 @@ impl AssistantApp {
             chat,
             guides: GuideRegistry::new(),
             ai,
-            input_text: String::new(),
+            input_text: input.unwrap_or_default(),
             show_settings: false,
+            show_sessions: false,
+            session_rename: None,
             dialog: DialogState::new(),
             input_history: InputHistory::new(),
+            editing: None,
+            mirror_countries_input,
+            macros: macros::load(),
+            macro_name_input: String::new(),
+            macro_steps_input: String::new(),
+            macro_run: None,
+            panels: PanelState::default(),
+            search: ui::search::SearchState::default(),
+            export: ui::export::ExportState::default(),
+            completion: ui::completion::CompletionState::default(),
+            monitor: Monitor::default(),
             ollama_online,
             ollama_installed,
             yay_installed,
@@ -121,6 +210,10 @@ impl AssistantApp {
             de_styles,
             tasks,
             task_receiver,
+            tasks_finished: 0,
+            ai_sender,
+            ai_receiver,
+            ipc: IpcServer::start(cc.egui_ctx.clone()),
         }
     }
 
@@ -145,66 +238,275 @@ impl AssistantApp {
 
         let input = input.to_string();
         self.input_history.push(&input);
-        self.chat.add_message("Вы", &input);
+
+        // Правка сообщения становится его новым вариантом
+        let edited = self
+            .editing
+            .take()
+            .and_then(|id| self.chat.add_variant(id, &input))
+            .is_some();
+        if !edited {
+            self.chat.add_message(USER_SENDER, &input);
+        }
+
+        if self.execute_input(&input).is_none() {
+            // Отправляем в AI
+            self.send_to_ai(&input, None, None);
+        }
+
+        self.input_text.clear();
+    }
+
+    /// Выполняет макрос или команду и показывает ответ в чате.
+    /// Возвращает показанный текст (пусто — действие интерфейса),
+    /// None — команда не распознана
+    fn execute_input(&mut self, input: &str) -> Option<String> {
+        // Макрос пользователя выполняется по шагам в advance_macro
+        if let Some(m) = macros::find(&self.macros, input) {
+            let run = MacroRun::new(m);
+            let text = trf(
+                "Запускаю макрос «{}», шагов: {}",
+                &[&run.name(), &run.total()],
+            );
+            self.chat.add_message("Система", &text);
+            self.macro_run = Some(run);
+            return Some(text);
+        }
 
         // Пробуем обработать как команду
-        let response = commands::process_command(
-            &input,
-            &self.config.assistant_name,
+        let text = commands::process_command(
+            input,
+            &self.config,
             &mut self.dialog,
             &self.tasks,
             &self.guides,
-        );
+        )?;
 
-        if let Some(text) = response {
-            // Проверяем специальные команды
-            if text == CMD_CLEAR_CHAT {
-                self.clear_chat();
-            } else {
-                self.chat.add_message(&self.config.assistant_name, text);
-            }
-        } else {
-            // Отправляем в AI
-            self.send_to_ai(&input);
+        // Проверяем специальные команды
+        if self.handle_ui_action(&text) {
+            return Some(String::new());
         }
-
-        self.input_text.clear();
+        self.chat.add_message(&self.config.assistant_name, &text);
+        Some(text)
     }
 
-    /// Отправка запроса в AI
-    fn send_to_ai(&self, input: &str) {
+    /// Отправка запроса в AI. `target` — сообщение и номер варианта,
+    /// вместо текста которого встанет ответ (перегенерация)
+    /// `reply_to` — запрос Ask по IPC, которому отправляется ответ
+    fn send_to_ai(
+        &self,
+        input: &str,
+        target: Option<(u64, usize)>,
+        reply_to: Option<mpsc::Sender<Response>>,
+    ) {
         let ai = Arc::clone(&self.ai);
-        let tx = self.tasks.result_sender.clone();
-        let name = self.config.assistant_name.clone();
+        let tx = self.ai_sender.clone();
         let input = input.to_string();
 
         tokio::spawn(async move {
-            let response = match ai.generate(&input).await {
-                Ok(text) => format!("{}: {}", name, text),
-                Err(e) => format!("Ошибка ИИ: {}", e),
-            };
-            let _ = tx.send(response);
+            let result = ai.generate(&input).await;
+            let _ = tx.send(AiReply {
+                target,
+                result,
+                reply_to,
+            });
         });
     }
 
-    /// Проверка завершённых фоновых задач
+    /// Выполняет следующий шаг макроса, когда закрыт диалог и свободен
+    /// менеджер задач. Шаг с диалогом засчитывается после ответа пользователя,
+    /// шаг с фоновой задачей — по её результату в check_tasks
+    fn advance_macro(&mut self) {
+        if self.dialog.visible || self.tasks.is_busy() {
+            return;
+        }
+        let Some(mut run) = self.macro_run.take() else {
+            return;
+        };
+        if run.running.is_some() {
+            self.macro_run = Some(run);
+            return;
+        }
+
+        if let Some((step, started)) = run.awaiting.take() {
+            if !self.dialog.confirmed {
+                run.record(step, StepOutcome::Cancelled);
+            } else if self.tasks.started() > started {
+                run.running = Some((step, started + 1));
+            } else {
+                run.record(step, StepOutcome::Done);
+            }
+            self.macro_run = Some(run);
+            return;
+        }
+
+        let Some((index, step)) = run.next_step() else {
+            self.chat.add_message("Система", run.summary());
+            return;
+        };
+
+        self.chat.add_message(
+            "Система",
+            trf("Шаг {}/{}: {}", &[&index, &run.total(), &step]),
+        );
+        let started = self.tasks.started();
+        let response = commands::process_command(
+            &step,
+            &self.config,
+            &mut self.dialog,
+            &self.tasks,
+            &self.guides,
+        );
+        match response {
+            Some(text) => {
+                let outcome = StepOutcome::from_output(&text);
+                if !self.handle_ui_action(&text) {
+                    self.chat.add_message(&self.config.assistant_name, text);
+                }
+                if self.dialog.visible {
+                    run.awaiting = Some((step, started));
+                } else if self.tasks.started() > started {
+                    run.running = Some((step, started + 1));
+                } else {
+                    run.record(step, outcome);
+                }
+            }
+            None => run.record(step, StepOutcome::NotRecognized),
+        }
+        self.macro_run = Some(run);
+    }
+
+    /// Новый вариант ответа ИИ на тот же вопрос
+    pub fn regenerate(&mut self, id: u64) {
+        let Some(prompt) = self.chat.prompt_for(id) else {
+            return;
+        };
+        if let Some(variant) = self.chat.add_variant(id, tr(messages::REGENERATING)) {
+            self.send_to_ai(&prompt, Some((id, variant)), None);
+        }
+    }
+
+    /// Начинает правку сообщения пользователя в поле ввода
+    pub fn start_edit(&mut self, id: u64) {
+        if let Some(msg) = self.chat.messages().find(|m| m.id == id) {
+            self.input_text = msg.text.clone();
+            self.editing = Some(id);
+        }
+    }
+
+    /// Отмена правки сообщения
+    pub fn cancel_edit(&mut self) {
+        self.editing = None;
+        self.input_text.clear();
+    }
+
+    /// Проверка завершённых фоновых задач и ответов ИИ
     pub fn check_tasks(&mut self) {
         while let Ok(result) = self.task_receiver.try_recv() {
-            // AI ответы содержат имя ассистента
-            if result.starts_with(&self.config.assistant_name) {
-                if let Some((name, text)) = result.split_once(": ") {
+            self.tasks_finished += 1;
+            // Итог шага макроса — результат запущенной им задачи
+            let finished = self.tasks_finished;
+            if let Some(run) = self.macro_run.as_mut() {
+                if let Some((step, _)) = run.running.take_if(|(_, task)| *task == finished) {
+                    run.record(step, StepOutcome::from_output(&result));
+                }
+            }
+            if let Some(ipc) = &self.ipc {
+                ipc.broadcast("TaskCompleted", &result);
+            }
+            self.chat.add_message("Система", &result);
+        }
+
+        while let Ok(reply) = self.ai_receiver.try_recv() {
+            if let Some(tx) = &reply.reply_to {
+                let _ = tx.send(match &reply.result {
+                    Ok(text) => Response::new(cmd_regex().replace_all(text, "")),
+                    Err(e) => Response::with_status(trf("Ошибка ИИ: {}", &[e]), Status::Failed),
+                });
+            }
+            match (reply.result, reply.target) {
+                (Ok(text), target) => {
                     // Обрабатываем команды от AI
-                    let processed_text = self.process_ai_commands(text);
-                    self.chat.add_message(name, &processed_text);
-                } else {
-                    self.chat.add_message("Система", &result);
+                    let processed_text = self.process_ai_commands(&text);
+                    match target {
+                        Some((id, variant)) => self.chat.set_text(id, variant, processed_text),
+                        None => self
+                            .chat
+                            .add_reply(&self.config.assistant_name, processed_text),
+                    }
                 }
-            } else {
-                self.chat.add_message("Система", &result);
+                (Err(e), Some((id, variant))) => {
+                    self.chat.set_text(id, variant, trf("Ошибка ИИ: {}", &[&e]))
+                }
+                (Err(e), None) => self
+                    .chat
+                    .add_message("Система", trf("Ошибка ИИ: {}", &[&e])),
             }
         }
     }
 
+    /// Запросы других программ через IPC
+    fn check_ipc(&mut self, ctx: &egui::Context) {
+        while let Some(Incoming { request, reply }) =
+            self.ipc.as_ref().and_then(IpcServer::try_recv)
+        {
+            let response = match request {
+                Request::Ask { text } => {
+                    // Ответ уйдёт из check_tasks, когда ИИ договорит
+                    self.chat.add_message(USER_SENDER, &text);
+                    self.send_to_ai(&text, None, Some(reply));
+                    continue;
+                }
+                Request::RunCommand { text } => {
+                    self.chat.add_message(USER_SENDER, &text);
+                    match self.execute_input(&text) {
+                        Some(output) => {
+                            // Подтверждение, панель или макрос — нужно окно
+                            let needs_window = self.dialog.visible
+                                || self.macro_run.is_some()
+                                || output.is_empty();
+                            if needs_window {
+                                show_window(ctx);
+                            }
+                            if self.dialog.visible {
+                                Response::with_status(
+                                    format!("{}\n{}", output, tr("Ожидает подтверждения в окне")),
+                                    Status::Pending,
+                                )
+                            } else {
+                                Response::new(output)
+                            }
+                        }
+                        None => {
+                            let text = trf("[!] команда '{}' не распознана", &[&text]);
+                            self.chat.add_message("Система", &text);
+                            Response::with_status(text, Status::Unknown)
+                        }
+                    }
+                }
+                Request::ShowWindow { text } => {
+                    if let Some(text) = text {
+                        self.input_text = text;
+                    }
+                    show_window(ctx);
+                    Response::new(String::new())
+                }
+                Request::GetStatus => Response {
+                    state: Some(AppState {
+                        ollama_online: self.ollama_online.load(Ordering::SeqCst),
+                        busy: self.tasks.is_busy() || self.macro_run.is_some(),
+                        updates_pending: commands::package::last_updates_count(),
+                        vulnerable: commands::security::last_audit_count(),
+                    }),
+                    ..Response::new(String::new())
+                },
+                // Подписку обслуживает поток соединения
+                Request::Subscribe => continue,
+            };
+            let _ = reply.send(response);
+        }
+    }
+
     /// Обрабатывает маркеры [CMD:...] в ответе AI и выполняет команды
     fn process_ai_commands(&mut self, text: &str) -> String {
         let cmd_re = cmd_regex();
@@ -222,44 +524,152 @@ impl AssistantApp {
 
             let cmd_response = commands::process_command(
                 &cmd,
-                &self.config.assistant_name,
+                &self.config,
                 &mut self.dialog,
                 &self.tasks,
                 &self.guides,
             );
 
             if let Some(response) = cmd_response {
-                // Проверяем специальные команды
-                if response == commands::base::CMD_CLEAR_CHAT {
-                    self.clear_chat();
-                    result = result.replace(&marker, "");
-                } else {
-                    // Убираем маркер, оставляем только текст AI
-                    // Результат команды будет показан через диалог или системное сообщение
-                    result = result.replace(&marker, "");
-                }
+                // Специальные команды выполняются сразу, остальные показываются
+                // через диалог или системное сообщение — маркер просто убираем
+                self.handle_ui_action(&response);
+                result = result.replace(&marker, "");
             } else {
                 // Команда не распознана - показываем ошибку
-                result = result.replace(&marker, &format!("[!] команда '{}' не распознана", cmd));
+                result = result.replace(&marker, &trf("[!] команда '{}' не распознана", &[&cmd]));
             }
         }
 
         result
     }
 
+    /// Выполняет служебные действия интерфейса (очистка чата, открытие панели).
+    /// Возвращает true, если ответ команды был таким действием
+    fn handle_ui_action(&mut self, response: &str) -> bool {
+        if response == CMD_CLEAR_CHAT {
+            self.clear_chat();
+            return true;
+        }
+
+        if response == CMD_NEW_CHAT {
+            self.new_session();
+            return true;
+        }
+
+        if response == CMD_SHOW_SESSIONS {
+            self.show_sessions = true;
+            return true;
+        }
+
+        if let Some(format) = response.strip_prefix(CMD_EXPORT_CHAT_PREFIX) {
+            match ExportFormat::from_id(format) {
+                // Формат указан — сразу сохраняем весь чат
+                Some(format) => {
+                    let messages: Vec<_> = self.chat.messages().cloned().collect();
+                    let result = export::save(&messages, &self.chat.title(), format);
+                    self.chat.add_message("Система", result);
+                }
+                None => self.export.show(ExportFormat::default()),
+            }
+            return true;
+        }
+
+        if let Some(query) = response.strip_prefix(CMD_SEARCH_HISTORY_PREFIX) {
+            self.search.query = query.to_string();
+            self.search.show();
+            return true;
+        }
+
+        if let Some(spec) = response.strip_prefix(CMD_OPEN_PANEL_PREFIX) {
+            let (id, arg) = spec.split_once(':').unwrap_or((spec, ""));
+            if let Some(panel) = Panel::from_id(id) {
+                self.panels.open(panel, arg);
+            }
+            return true;
+        }
+
+        false
+    }
+
     /// Очистка чата
     pub fn clear_chat(&mut self) {
         self.chat.clear();
         self.chat
-            .add_message(&self.config.assistant_name, messages::CHAT_CLEARED);
+            .add_message(&self.config.assistant_name, tr(messages::CHAT_CLEARED));
+    }
+
+    /// Новая сессия. Пустая текущая сессия переиспользуется
+    pub fn new_session(&mut self) {
+        if !self.chat.has_user_messages() {
+            return;
+        }
+        self.chat.new_session();
+        sessions::prune(self.config.session_limit);
+        self.chat
+            .add_message(&self.config.assistant_name, tr(messages::WELCOME));
+    }
+
+    /// Переключение на сохранённую сессию. Пустая текущая сессия удаляется
+    pub fn switch_session(&mut self, id: &str) {
+        let Some(current) = self.chat.session_id().map(str::to_string) else {
+            self.chat.open_session(id);
+            return;
+        };
+        if current != id {
+            if !self.chat.has_user_messages() {
+                sessions::delete(&current);
+            }
+            self.chat.open_session(id);
+        }
+    }
+
+    /// Удаление сессии; вместо открытой открывается последняя оставшаяся
+    pub fn delete_session(&mut self, id: &str) {
+        sessions::delete(id);
+        if self.chat.session_id() == Some(id) {
+            match sessions::list().first() {
+                Some(next) => self.chat.open_session(&next.id),
+                None => {
+                    self.chat.new_session();
+                    self.chat
+                        .add_message(&self.config.assistant_name, tr(messages::WELCOME));
+                }
+            }
+        }
     }
 }
 
+/// Показывает окно поверх остальных
+fn show_window(ctx: &egui::Context) {
+    ctx.send_viewport_cmd(egui::ViewportCommand::Visible(true));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+    ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+}
+
 impl eframe::App for AssistantApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.check_tasks();
+        self.check_ipc(ctx);
         self.check_ollama_periodic();
 
+        // Макрос продолжается без действий пользователя
+        if self.macro_run.is_some() {
+            self.advance_macro();
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
+
+        // Таймер питания: сон, блокировку и выход выполняет само приложение
+        if let Some(action) = commands::system::take_due_schedule() {
+            self.tasks.execute(BackgroundTask::Power(action));
+        }
+
+        // Мониторинг обновляется, только пока открыты настройки
+        self.monitor.set_active(self.show_settings);
+        if self.show_settings {
+            ctx.request_repaint_after(Duration::from_millis(MONITOR_INTERVAL_MS));
+        }
+
         // Стили адаптированные под DE
         let mut style = (*ctx.style()).clone();
         style.spacing.item_spacing = egui::vec2(self.de_styles.spacing, self.de_styles.spacing);
@@ -1,9 +1,17 @@
 //! Модуль чата и фоновых задач
 
-use super::constants::MAX_CHAT_MESSAGES;
+use super::commands::audio::VolumeChange;
+use super::commands::aur::SharedReview;
+use super::commands::mirrors::MirrorFilter;
+use super::commands::network;
+use super::commands::processes::ProcessAction;
+use super::commands::services::ServiceAction;
+use super::commands::system::PowerAction;
+use super::constants::{MAX_CHAT_MESSAGES, USER_SENDER};
+use super::sessions;
 use chrono::{DateTime, Local};
 use std::collections::VecDeque;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::Arc;
 use std::thread;
@@ -30,6 +38,14 @@ pub struct DialogState {
     pub message: String,
     pub input: String,
     pub package: String,
+    /// Задача, которая будет запущена после подтверждения
+    pub task: Option<BackgroundTask>,
+    /// Проверки пакетов AUR для диалога установки, по одной на пакет
+    pub aur_reviews: Vec<SharedReview>,
+    /// Диалог закрыт основной кнопкой, а не отменой
+    pub confirmed: bool,
+    /// Диалог открыт критической командой (выключение, откат)
+    pub critical: bool,
 }
 
 impl DialogState {
@@ -40,19 +56,57 @@ impl DialogState {
     /// Показать диалог поиска пакетов
     pub fn show_search(&mut self) {
         self.visible = true;
+        self.confirmed = false;
         self.dialog_type = DialogType::PackageSearch;
         self.title = "Поиск пакетов".to_string();
         self.message = "Введите название пакета:".to_string();
         self.input.clear();
     }
 
-    /// Показать диалог подтверждения
+    /// Показать диалог подтверждения. Заменяет открытый диалог целиком:
+    /// его задача, проверка AUR и признак риска не переходят в новый
     pub fn show_confirm(&mut self, title: &str, message: &str, package: &str) {
         self.visible = true;
+        self.confirmed = false;
         self.dialog_type = DialogType::Confirmation;
         self.title = title.to_string();
         self.message = message.to_string();
         self.package = package.to_string();
+        self.task = None;
+        self.aur_reviews.clear();
+        self.critical = false;
+    }
+
+    /// Показать диалог подтверждения, после которого запускается задача
+    pub fn show_confirm_task(&mut self, title: &str, message: &str, task: BackgroundTask) {
+        self.show_confirm(title, message, "");
+        self.task = Some(task);
+    }
+
+    /// Задача, которую запускает основная кнопка диалога.
+    /// `snapshot` — делать ли снимок перед установкой и обновлением
+    pub fn take_action(&mut self, snapshot: bool) -> Option<BackgroundTask> {
+        match self.dialog_type {
+            DialogType::PackageSearch if !self.input.is_empty() => {
+                Some(BackgroundTask::SearchPackages(self.input.clone()))
+            }
+            DialogType::Confirmation if self.task.is_some() => self.task.take(),
+            DialogType::Confirmation => {
+                let title = &self.title;
+                let package = &self.package;
+
+                if title.contains("Установка") && !package.is_empty() {
+                    Some(BackgroundTask::InstallPackage(package.clone(), snapshot))
+                } else if title.contains("Удаление") && !package.is_empty() {
+                    Some(BackgroundTask::RemovePackage(package.clone()))
+                } else if title.contains("Обновление") {
+                    Some(BackgroundTask::UpdateSystem(snapshot))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
     }
 
     /// Скрыть диалог
@@ -60,6 +114,9 @@ impl DialogState {
         self.visible = false;
         self.input.clear();
         self.package.clear();
+        self.task = None;
+        self.aur_reviews.clear();
+        self.critical = false;
     }
 }
 
@@ -68,20 +125,52 @@ impl DialogState {
 // ============================================================================
 
 /// Типы фоновых задач
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum BackgroundTask {
     SearchPackages(String),
-    InstallPackage(String),
+    /// Пакеты и снимок перед пакетной установкой
+    InstallPackage(String, bool),
     RemovePackage(String),
-    UpdateSystem,
+    /// Снимок перед обновлением
+    UpdateSystem(bool),
     InstallYay,
-    ShutdownSystem,
-    RebootSystem,
+    /// Блок команд из сообщения чата
+    RunSnippet(String),
+    Power(PowerAction),
+    /// Действие и время срабатывания (unix, секунды)
+    SchedulePower(PowerAction, i64),
+    CancelPowerSchedule,
+    ListSnapshots,
+    KernelInfo,
+    GpuInfo,
+    /// Снимок перед установкой
+    InstallLtsKernel(bool),
+    CreateSnapshot(String),
+    RollbackSnapshot(String),
     CreateCustomModel,
     InstallToSystem,
     UninstallFromSystem,
     InstallOllama,
     StartOllama,
+    RankMirrors(MirrorFilter),
+    WriteMirrorlist(String),
+    SecurityAudit(String),
+    ServiceStatus(String),
+    ServiceAction(ServiceAction, String),
+    FailedServices,
+    JournalErrors(String),
+    NetworkStatus,
+    WifiScan,
+    WifiDisconnect,
+    AirplaneMode(bool),
+    AudioStatus,
+    AudioDevices,
+    AudioPlayback,
+    AudioSwitch(String),
+    AudioVolume(VolumeChange),
+    AudioMute(bool),
+    TopProcesses,
+    ProcessAction(ProcessAction, Vec<u32>),
 }
 
 // ============================================================================
@@ -91,15 +180,72 @@ pub enum BackgroundTask {
 /// Сообщение в чате
 #[derive(Clone)]
 pub struct ChatMessage {
+    /// Номер сообщения в памяти (не сохраняется, нужен для действий над ним)
+    pub id: u64,
     pub sender: String,
     pub text: String,
     pub timestamp: DateTime<Local>,
+    /// Ответ ИИ — его можно перегенерировать
+    pub generated: bool,
+    /// Варианты сообщения после перегенерации или правки; пусто — вариант один
+    pub variants: Vec<Variant>,
+    /// Показанный вариант
+    pub variant: usize,
+}
+
+/// Вариант сообщения со своим продолжением переписки
+#[derive(Clone)]
+pub struct Variant {
+    pub text: String,
+    pub timestamp: DateTime<Local>,
+    /// Сообщения после этого варианта; у показанного пусто — они в истории
+    pub following: Vec<ChatMessage>,
 }
 
-/// Управление историей чата
+impl ChatMessage {
+    pub fn new(sender: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            id: next_message_id(),
+            sender: sender.into(),
+            text: text.into(),
+            timestamp: Local::now(),
+            generated: false,
+            variants: Vec::new(),
+            variant: 0,
+        }
+    }
+
+    /// Сохраняет показанный вариант вместе с продолжением
+    fn stash_variant(&mut self, following: Vec<ChatMessage>) {
+        let current = Variant {
+            text: self.text.clone(),
+            timestamp: self.timestamp,
+            following,
+        };
+        match self.variants.get_mut(self.variant) {
+            Some(slot) => *slot = current,
+            None => {
+                self.variants = vec![current];
+                self.variant = 0;
+            }
+        }
+    }
+}
+
+/// Уникальный номер сообщения в пределах запуска
+pub fn next_message_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Управление историей чата.
+/// Если открыта сессия, каждое сообщение сразу дописывается на диск
 pub struct ChatHistory {
     messages: VecDeque<ChatMessage>,
     max_messages: usize,
+    session: Option<String>,
+    /// Ранние сообщения сессии, которые не показываются, но остаются в файле
+    hidden: usize,
 }
 
 impl ChatHistory {
@@ -107,26 +253,166 @@ impl ChatHistory {
         Self {
             messages: VecDeque::with_capacity(max_messages),
             max_messages,
+            session: None,
+            hidden: 0,
+        }
+    }
+
+    /// Открывает сессию, активную при прошлом выходе, или создаёт новую.
+    /// Старые сессии сверх `limit` удаляются
+    pub fn restore(limit: usize) -> Self {
+        let mut chat = Self::default();
+        match sessions::last_active() {
+            Some(id) => chat.open_session(&id),
+            None => chat.new_session(),
         }
+        sessions::prune(limit);
+        chat
+    }
+
+    /// Идентификатор открытой сессии
+    pub fn session_id(&self) -> Option<&str> {
+        self.session.as_deref()
+    }
+
+    /// Название открытой сессии
+    pub fn title(&self) -> String {
+        self.session
+            .as_deref()
+            .map_or_else(|| sessions::UNTITLED.to_string(), sessions::title)
+    }
+
+    /// Загружает сообщения сессии вместо текущих
+    pub fn open_session(&mut self, id: &str) {
+        let (hidden, messages) = sessions::load(id, self.max_messages);
+        self.hidden = hidden;
+        self.messages = messages.into();
+        self.session = Some(id.to_string());
+        sessions::set_active(id);
+    }
+
+    /// Начинает новую пустую сессию
+    pub fn new_session(&mut self) {
+        self.messages.clear();
+        self.hidden = 0;
+        self.session = Some(sessions::create());
+    }
+
+    /// Есть ли в истории сообщения пользователя
+    pub fn has_user_messages(&self) -> bool {
+        self.messages.iter().any(|m| m.sender == USER_SENDER)
     }
 
     /// Добавляет сообщение в историю
     pub fn add_message(&mut self, sender: impl Into<String>, text: impl Into<String>) {
-        self.messages.push_back(ChatMessage {
-            sender: sender.into(),
-            text: text.into(),
-            timestamp: Local::now(),
+        self.push(ChatMessage::new(sender, text));
+    }
+
+    /// Добавляет ответ ИИ (его можно перегенерировать)
+    pub fn add_reply(&mut self, sender: impl Into<String>, text: impl Into<String>) {
+        self.push(ChatMessage {
+            generated: true,
+            ..ChatMessage::new(sender, text)
         });
+    }
+
+    fn push(&mut self, msg: ChatMessage) {
+        if let Some(id) = &self.session {
+            sessions::append(id, &msg);
+        }
+        self.messages.push_back(msg);
 
-        // Удаляем старые сообщения при превышении лимита (O(1) для VecDeque)
+        // Убираем старые сообщения из памяти при превышении лимита
+        // (O(1) для VecDeque); в файле сессии они остаются
         if self.messages.len() > self.max_messages {
             self.messages.pop_front();
+            self.hidden += 1;
+        }
+    }
+
+    fn position(&self, id: u64) -> Option<usize> {
+        self.messages.iter().position(|m| m.id == id)
+    }
+
+    /// Вопрос пользователя, на который отвечает сообщение
+    pub fn prompt_for(&self, id: u64) -> Option<String> {
+        let i = self.position(id)?;
+        self.messages
+            .range(..i)
+            .rev()
+            .find(|m| m.sender == USER_SENDER)
+            .map(|m| m.text.clone())
+    }
+
+    /// Новый вариант сообщения: следующие за ним сообщения уходят
+    /// в прежний вариант. Возвращает номер варианта (None — сообщения уже нет)
+    pub fn add_variant(&mut self, id: u64, text: impl Into<String>) -> Option<usize> {
+        let i = self.position(id)?;
+        let following: Vec<ChatMessage> = self.messages.drain(i + 1..).collect();
+        let msg = &mut self.messages[i];
+        msg.stash_variant(following);
+        msg.text = text.into();
+        msg.timestamp = Local::now();
+        msg.variants.push(Variant {
+            text: msg.text.clone(),
+            timestamp: msg.timestamp,
+            following: Vec::new(),
+        });
+        msg.variant = msg.variants.len() - 1;
+        let variant = msg.variant;
+        self.save();
+        Some(variant)
+    }
+
+    /// Показывает другой вариант сообщения вместе с его продолжением
+    pub fn switch_variant(&mut self, id: u64, index: usize) {
+        let Some(i) = self.position(id) else {
+            return;
+        };
+        if index == self.messages[i].variant || index >= self.messages[i].variants.len() {
+            return;
+        }
+        let following: Vec<ChatMessage> = self.messages.drain(i + 1..).collect();
+        let msg = &mut self.messages[i];
+        msg.stash_variant(following);
+        let chosen = &mut msg.variants[index];
+        let restored = std::mem::take(&mut chosen.following);
+        msg.text = chosen.text.clone();
+        msg.timestamp = chosen.timestamp;
+        msg.variant = index;
+        self.messages.extend(restored);
+        self.save();
+    }
+
+    /// Заменяет текст варианта сообщения (ответ на перегенерацию).
+    /// Пока ИИ отвечал, на экране мог оказаться другой вариант — он не меняется
+    pub fn set_text(&mut self, id: u64, variant: usize, text: impl Into<String>) {
+        let Some(i) = self.position(id) else {
+            return;
+        };
+        let msg = &mut self.messages[i];
+        let Some(slot) = msg.variants.get_mut(variant) else {
+            return;
+        };
+        slot.text = text.into();
+        if msg.variant == variant {
+            msg.text = slot.text.clone();
+        }
+        self.save();
+    }
+
+    /// Перезаписывает файл сессии после изменения истории
+    fn save(&self) {
+        if let Some(id) = &self.session {
+            sessions::rewrite(id, self.hidden, &self.messages);
         }
     }
 
-    /// Очищает историю
+    /// Очищает историю (и файл открытой сессии)
     pub fn clear(&mut self) {
         self.messages.clear();
+        self.hidden = 0;
+        self.save();
     }
 
     /// Возвращает итератор по сообщениям
@@ -148,8 +434,10 @@ impl Default for ChatHistory {
 /// Менеджер фоновых задач
 pub struct TaskManager {
     task_sender: Sender<BackgroundTask>,
-    pub result_sender: Sender<String>,
     is_processing: Arc<AtomicBool>,
+    /// Сколько задач запущено; задачи выполняются по очереди,
+    /// поэтому N-й результат принадлежит N-й задаче
+    started: AtomicUsize,
 }
 
 impl TaskManager {
@@ -158,7 +446,6 @@ impl TaskManager {
         let (task_sender, task_receiver) = mpsc::channel::<BackgroundTask>();
         let (result_sender, result_receiver) = mpsc::channel::<String>();
 
-        let result_sender_clone = result_sender.clone();
         let is_processing = Arc::new(AtomicBool::new(false));
         let is_processing_clone = is_processing.clone();
 
@@ -169,16 +456,38 @@ impl TaskManager {
                     BackgroundTask::SearchPackages(query) => {
                         super::commands::package::search_packages(&query)
                     }
-                    BackgroundTask::InstallPackage(package) => {
-                        super::commands::package::install_package(&package)
+                    BackgroundTask::InstallPackage(package, snapshot) => {
+                        super::commands::package::install_package(&package, snapshot)
                     }
                     BackgroundTask::RemovePackage(package) => {
                         super::commands::package::remove_package(&package)
                     }
-                    BackgroundTask::UpdateSystem => super::commands::package::update_system(),
+                    BackgroundTask::UpdateSystem(snapshot) => {
+                        super::commands::package::update_system(snapshot)
+                    }
                     BackgroundTask::InstallYay => super::commands::package::install_yay(),
-                    BackgroundTask::ShutdownSystem => super::commands::system::execute_shutdown(),
-                    BackgroundTask::RebootSystem => super::commands::system::execute_reboot(),
+                    BackgroundTask::RunSnippet(code) => {
+                        super::commands::package::run_snippet(&code)
+                    }
+                    BackgroundTask::Power(action) => super::commands::system::execute_power(action),
+                    BackgroundTask::SchedulePower(action, at) => {
+                        super::commands::system::schedule_power(action, at)
+                    }
+                    BackgroundTask::CancelPowerSchedule => {
+                        super::commands::system::cancel_schedule()
+                    }
+                    BackgroundTask::KernelInfo => super::commands::kernels::kernel_info(),
+                    BackgroundTask::GpuInfo => super::commands::gpu::gpu_info(),
+                    BackgroundTask::InstallLtsKernel(snapshot) => {
+                        super::commands::kernels::install_lts_kernel(snapshot)
+                    }
+                    BackgroundTask::ListSnapshots => super::commands::snapshots::list_snapshots(),
+                    BackgroundTask::CreateSnapshot(label) => {
+                        super::commands::snapshots::create_snapshot(&label)
+                    }
+                    BackgroundTask::RollbackSnapshot(id) => {
+                        super::commands::snapshots::rollback_snapshot(&id)
+                    }
                     BackgroundTask::CreateCustomModel => {
                         super::ai::local_provider::create_custom_model()
                     }
@@ -194,9 +503,50 @@ impl TaskManager {
                     BackgroundTask::StartOllama => {
                         super::ai::local_provider::start_ollama_service()
                     }
+                    BackgroundTask::RankMirrors(filter) => {
+                        super::commands::mirrors::rank_and_preview(&filter)
+                    }
+                    BackgroundTask::WriteMirrorlist(content) => {
+                        super::commands::mirrors::write_mirrorlist(&content)
+                    }
+                    BackgroundTask::SecurityAudit(source) => {
+                        super::commands::security::security_audit(&source)
+                    }
+                    BackgroundTask::ServiceStatus(unit) => {
+                        super::commands::services::service_status(&unit)
+                    }
+                    BackgroundTask::ServiceAction(action, unit) => {
+                        super::commands::services::execute_action(action, &unit)
+                    }
+                    BackgroundTask::FailedServices => super::commands::services::failed_units(),
+                    BackgroundTask::JournalErrors(unit) => {
+                        super::commands::journal::recent_errors(&unit)
+                    }
+                    BackgroundTask::NetworkStatus => network::with_backend(network::network_status),
+                    BackgroundTask::WifiScan => network::with_backend(network::wifi_scan),
+                    BackgroundTask::WifiDisconnect => {
+                        network::with_backend(network::wifi_disconnect)
+                    }
+                    BackgroundTask::AirplaneMode(enabled) => {
+                        network::with_backend(|b| network::airplane_mode(b, enabled))
+                    }
+                    BackgroundTask::AudioStatus => super::commands::audio::audio_status(),
+                    BackgroundTask::AudioDevices => super::commands::audio::audio_devices(),
+                    BackgroundTask::AudioPlayback => super::commands::audio::audio_playback(),
+                    BackgroundTask::AudioSwitch(device) => {
+                        super::commands::audio::switch_output(&device)
+                    }
+                    BackgroundTask::AudioVolume(change) => {
+                        super::commands::audio::set_volume(change)
+                    }
+                    BackgroundTask::AudioMute(mute) => super::commands::audio::set_mute(mute),
+                    BackgroundTask::TopProcesses => super::commands::processes::top_processes(),
+                    BackgroundTask::ProcessAction(action, pids) => {
+                        super::commands::processes::execute_action(action, &pids)
+                    }
                 };
 
-                let _ = result_sender_clone.send(result);
+                let _ = result_sender.send(result);
                 is_processing_clone.store(false, Ordering::SeqCst);
             }
         });
@@ -204,8 +554,8 @@ impl TaskManager {
         (
             Self {
                 task_sender,
-                result_sender,
                 is_processing,
+                started: AtomicUsize::new(0),
             },
             result_receiver,
         )
@@ -218,9 +568,16 @@ impl TaskManager {
         if self.task_sender.send(task).is_err() {
             // Если отправка не удалась, сбрасываем флаг
             self.is_processing.store(false, Ordering::SeqCst);
+        } else {
+            self.started.fetch_add(1, Ordering::SeqCst);
         }
     }
 
+    /// Сколько задач запущено с начала работы
+    pub fn started(&self) -> usize {
+        self.started.load(Ordering::SeqCst)
+    }
+
     /// Проверяет, выполняется ли задача
     pub fn is_busy(&self) -> bool {
         self.is_processing.load(Ordering::SeqCst)
@@ -312,3 +669,27 @@ impl Default for InputHistory {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn show_confirm_replaces_previous_task() {
+        let mut dialog = DialogState::new();
+        dialog.show_confirm_task(
+            "Перезагрузка",
+            "Перезагрузить компьютер?",
+            BackgroundTask::Power(PowerAction::Reboot),
+        );
+        dialog.critical = true;
+        dialog.show_confirm("Установка пакета", "Установить 'firefox'?", "firefox");
+
+        assert!(!dialog.critical);
+        assert!(dialog.aur_reviews.is_empty());
+        assert!(matches!(
+            dialog.take_action(false),
+            Some(BackgroundTask::InstallPackage(package, false)) if package == "firefox"
+        ));
+    }
+}
@@ -0,0 +1,621 @@
+//! Режим командной строки: ответы ИИ, команды и гайды без окна
+//!
+//! `alfons ask "..."`, `alfons run "..."`, `alfons guide <тема>` и `alfons repl`.
+//! Команды проходят через тот же `process_command`, что и в окне, опасные
+//! подтверждаются в терминале. Если окно уже открыто, `ask` и `run` передаются
+//! ему через `ipc`. Код выхода: 0 — успех, 1 — ошибка или отмена,
+//! 2 — команда не распознана или неверные аргументы.
+
+use super::ai::local_provider::LocalAi;
+use super::assistant_app::cmd_regex;
+use super::chat::{ChatHistory, DialogState, DialogType, TaskManager};
+use super::commands::aur::{self, AurReview};
+use super::commands::{
+    self,
+    base::{
+        CMD_CLEAR_CHAT, CMD_EXPORT_CHAT_PREFIX, CMD_NEW_CHAT, CMD_OPEN_PANEL_PREFIX,
+        CMD_SEARCH_HISTORY_PREFIX, CMD_SHOW_SESSIONS,
+    },
+};
+use super::commands::{package, security};
+use super::config::Config;
+use super::constants::{messages, APP_NAME, MAX_CHAT_MESSAGES, USER_SENDER};
+use super::export::{self, ExportFormat};
+use super::guides::GuideRegistry;
+use super::i18n::{self, tr, trf};
+use super::ipc::{self, AppState, Request, Response};
+use super::macros::{self, Macro, MacroRun, StepOutcome};
+use super::sessions;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// Справка по режиму командной строки
+pub const USAGE: &str = "Использование: alfons [команда] [--json] [--yes]
+
+Без аргументов открывается окно приложения.
+
+Команды:
+  ask \"вопрос\"     ответ ИИ
+  run \"команда\"    команда ассистента, например \"поиск vim\"
+  guide [тема]     гайд или список гайдов
+  repl             диалог в терминале: команды и вопросы ИИ
+  show [текст]     показать окно, текст — в поле ввода
+  status           Ollama, обновления и уязвимости
+  watch            сигналы о завершении задач в открытом окне
+  help             эта справка
+
+Параметры:
+  --json           вывод в JSON: {\"input\", \"status\", \"output\"}
+  --yes            подтверждать опасные команды без вопроса
+                   (кроме пакетов AUR и критических команд из ответа ИИ)
+
+Коды выхода: 0 — успех, 1 — ошибка или отмена, 2 — команда не распознана или неверные аргументы,
+3 — команда ждёт подтверждения в окне";
+
+/// Код выхода при неверных аргументах
+const EXIT_USAGE: i32 = 2;
+
+/// Код выхода, когда команда ждёт подтверждения в окне
+const EXIT_PENDING: i32 = 3;
+
+/// Итог команды (поле `status` в JSON); порядок — от лучшего к худшему
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    /// Команда ещё не выполнена: ждёт подтверждения в окне
+    Pending,
+    Cancelled,
+    Failed,
+    Unknown,
+}
+
+impl Status {
+    /// Итог по тексту ответа: строка с `[X]` означает ошибку
+    pub fn from_output(output: &str) -> Self {
+        if output.lines().any(|l| l.trim_start().starts_with("[X]")) {
+            Status::Failed
+        } else {
+            Status::Ok
+        }
+    }
+
+    fn exit_code(self) -> i32 {
+        match self {
+            Status::Ok => 0,
+            Status::Pending => EXIT_PENDING,
+            Status::Cancelled | Status::Failed => 1,
+            Status::Unknown => EXIT_USAGE,
+        }
+    }
+}
+
+/// Текст ответа и его итог
+struct Reply {
+    output: String,
+    status: Status,
+}
+
+impl Reply {
+    /// Ответ команды; строка с `[X]` означает ошибку
+    fn new(output: impl Into<String>) -> Self {
+        let output = output.into();
+        let status = Status::from_output(&output);
+        Self { output, status }
+    }
+
+    fn with_status(output: impl Into<String>, status: Status) -> Self {
+        Self {
+            output: output.into(),
+            status,
+        }
+    }
+
+    /// Дописывает другой ответ, сохраняя худший итог
+    fn append(&mut self, other: Reply) {
+        if !other.output.is_empty() {
+            if !self.output.is_empty() {
+                self.output.push('\n');
+            }
+            self.output.push_str(&other.output);
+        }
+        self.status = self.status.max(other.status);
+    }
+}
+
+impl From<Response> for Reply {
+    fn from(response: Response) -> Self {
+        Self::with_status(response.output, response.status)
+    }
+}
+
+/// Что делать после разбора аргументов
+pub enum Launch {
+    /// Открыть окно; текст — в поле ввода
+    Window(Option<String>),
+    /// Завершиться с кодом выхода
+    Exit(i32),
+}
+
+/// Точка входа: без аргументов и по `show` открывается окно, если оно ещё
+/// не открыто; иначе выполняется команда и возвращается код выхода
+pub async fn run(args: &[String]) -> Launch {
+    let (flags, words): (Vec<&String>, Vec<&String>) =
+        args.iter().partition(|a| a.starts_with("--"));
+
+    let mut json = false;
+    let mut yes = false;
+    for flag in flags {
+        match flag.as_str() {
+            "--json" => json = true,
+            "--yes" => yes = true,
+            "--help" => return Launch::Exit(usage(0)),
+            _ => return Launch::Exit(usage(EXIT_USAGE)),
+        }
+    }
+
+    let (command, rest) = words
+        .split_first()
+        .map_or(("show", &[][..]), |(c, r)| (c.as_str(), r));
+    let text = rest
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    // Единственный экземпляр: окно уже открыто — поднимаем его
+    if command == "show" {
+        let text = (!text.is_empty()).then_some(text);
+        return match ipc::request(&Request::ShowWindow { text: text.clone() }) {
+            Some(_) => Launch::Exit(0),
+            None => Launch::Window(text),
+        };
+    }
+
+    let mut cli = Cli::new(json, yes);
+    let reply = match command {
+        "ask" if !text.is_empty() => match ipc::request(&Request::Ask { text: text.clone() }) {
+            Some(response) => response.into(),
+            None => cli.ask(&text).await,
+        },
+        "run" if !text.is_empty() => {
+            match ipc::request(&Request::RunCommand { text: text.clone() }) {
+                Some(response) => response.into(),
+                None => cli.run_command(&text),
+            }
+        }
+        "guide" => cli.guide(&text),
+        "repl" if text.is_empty() => return Launch::Exit(cli.repl().await),
+        "status" if text.is_empty() => return Launch::Exit(cli.status().await),
+        "watch" if text.is_empty() => return Launch::Exit(cli.watch()),
+        "help" => return Launch::Exit(usage(0)),
+        _ => return Launch::Exit(usage(EXIT_USAGE)),
+    };
+    cli.print(&text, &reply);
+    Launch::Exit(reply.status.exit_code())
+}
+
+/// Печатает справку и возвращает код выхода
+fn usage(code: i32) -> i32 {
+    i18n::set_language(Config::load().language);
+    if code == 0 {
+        out(tr(USAGE));
+    } else {
+        eprintln!("{}", tr(USAGE));
+    }
+    code
+}
+
+/// Строка в stdout. Закрытый канал (`| head`) не считается ошибкой
+fn out(text: &str) {
+    let _ = writeln!(io::stdout().lock(), "{}", text);
+}
+
+/// Строка из stdin без перевода строки; None — конец ввода
+fn read_line() -> Option<String> {
+    let mut line = String::new();
+    match io::stdin().lock().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(line.trim().to_string()),
+    }
+}
+
+/// Состояние, которое в окне хранит `AssistantApp`
+struct Cli {
+    config: Config,
+    dialog: DialogState,
+    tasks: TaskManager,
+    results: Receiver<String>,
+    guides: GuideRegistry,
+    macros: Vec<Macro>,
+    ai: LocalAi,
+    /// Сессия чата REPL; разовые команды историю не ведут
+    chat: Option<ChatHistory>,
+    json: bool,
+    yes: bool,
+    /// Выполняются команды `[CMD:...]` из ответа ИИ
+    from_ai: bool,
+}
+
+impl Cli {
+    fn new(json: bool, yes: bool) -> Self {
+        let config = Config::load();
+        i18n::set_language(config.language);
+        let ai = LocalAi::new();
+        ai.set_model(&config.ollama_model);
+        let (tasks, results) = TaskManager::new();
+
+        Self {
+            config,
+            dialog: DialogState::new(),
+            tasks,
+            results,
+            guides: GuideRegistry::new(),
+            macros: macros::load(),
+            ai,
+            chat: None,
+            json,
+            yes,
+            from_ai: false,
+        }
+    }
+
+    /// Вывод ответа: текст или одна строка JSON
+    fn print(&self, input: &str, reply: &Reply) {
+        if self.json {
+            let line = json!({
+                "input": input,
+                "status": reply.status,
+                "output": reply.output,
+            });
+            out(&line.to_string());
+        } else if !reply.output.is_empty() {
+            out(reply.output.trim_end());
+        }
+    }
+
+    /// Ответ ИИ; команды `[CMD:...]` из ответа выполняются
+    async fn ask(&mut self, question: &str) -> Reply {
+        let answer = match self.ai.generate(question).await {
+            Ok(answer) => answer,
+            Err(e) => return Reply::with_status(trf("Ошибка ИИ: {}", &[&e]), Status::Failed),
+        };
+
+        let commands: Vec<String> = cmd_regex()
+            .captures_iter(&answer)
+            .map(|cap| cap[1].to_string())
+            .collect();
+        let mut reply = Reply::with_status(cmd_regex().replace_all(&answer, ""), Status::Ok);
+        self.from_ai = true;
+        for cmd in commands {
+            let result = self.execute(&cmd).unwrap_or_else(|| {
+                Reply::with_status(
+                    trf("[!] команда '{}' не распознана", &[&cmd]),
+                    Status::Unknown,
+                )
+            });
+            reply.append(result);
+        }
+        self.from_ai = false;
+        reply
+    }
+
+    /// `alfons run`: только команды, без обращения к ИИ
+    fn run_command(&mut self, input: &str) -> Reply {
+        self.execute(input).unwrap_or_else(|| {
+            Reply::with_status(
+                trf("[!] команда '{}' не распознана", &[&input]),
+                Status::Unknown,
+            )
+        })
+    }
+
+    /// `alfons guide`: гайд по теме или список гайдов
+    fn guide(&mut self, topic: &str) -> Reply {
+        if topic.is_empty() {
+            return Reply::new(self.guides.format_list());
+        }
+        let topic = topic.to_lowercase();
+        if self.guides.get(&topic).is_none() && self.guides.search(&topic).is_empty() {
+            return Reply::with_status(
+                trf(
+                    "Гайд '{}' не найден.\n\nИспользуйте 'гайды' для списка доступных.",
+                    &[&topic],
+                ),
+                Status::Unknown,
+            );
+        }
+        self.execute_command(&format!("гайд {}", topic))
+            .unwrap_or_else(|| Reply::with_status(String::new(), Status::Unknown))
+    }
+
+    /// `alfons status`: состояние открытого окна, иначе проверка на месте
+    async fn status(&self) -> i32 {
+        let state = match ipc::request(&Request::GetStatus).and_then(|r| r.state) {
+            Some(state) => state,
+            None => AppState {
+                ollama_online: super::ai::local_provider::check_ollama_status().await,
+                busy: false,
+                updates_pending: package::check_updates(),
+                vulnerable: security::last_audit_count(),
+            },
+        };
+
+        if self.json {
+            out(&json!(state).to_string());
+            return 0;
+        }
+        let online = if state.ollama_online {
+            tr("работает")
+        } else {
+            tr("не запущена")
+        };
+        let unknown = tr("неизвестно");
+        out(&trf("Ollama: {}", &[&online]));
+        out(&trf(
+            "Обновления: {}",
+            &[&state
+                .updates_pending
+                .map_or(unknown.to_string(), |n| n.to_string())],
+        ));
+        out(&trf(
+            "Уязвимые пакеты: {}",
+            &[&state
+                .vulnerable
+                .map_or(unknown.to_string(), |n| n.to_string())],
+        ));
+        if state.busy {
+            out(tr("Выполняется задача"));
+        }
+        0
+    }
+
+    /// `alfons watch`: сигналы открытого окна по строке JSON
+    fn watch(&self) -> i32 {
+        let json = self.json;
+        let connected = ipc::subscribe(|line| {
+            if json {
+                out(line);
+            } else if let Ok(signal) = serde_json::from_str::<serde_json::Value>(line) {
+                out(signal["output"].as_str().unwrap_or_default());
+            }
+        });
+        if connected {
+            0
+        } else {
+            eprintln!("{}", tr("[X] Окно Альфонса не открыто"));
+            1
+        }
+    }
+
+    /// Диалог в терминале: команды как в окне, остальное — вопрос ИИ.
+    /// Переписка сохраняется новой сессией и видна в окне приложения
+    async fn repl(&mut self) -> i32 {
+        let mut chat = ChatHistory::new(MAX_CHAT_MESSAGES);
+        chat.new_session();
+        sessions::prune(self.config.session_limit);
+        self.chat = Some(chat);
+
+        if !self.json {
+            out(&trf(
+                "{} в терминале. Команды — как в окне, остальное — вопрос ИИ. Выход: exit или Ctrl+D",
+                &[&APP_NAME],
+            ));
+        }
+
+        loop {
+            if !self.json {
+                let mut stdout = io::stdout().lock();
+                let _ = write!(stdout, "> ").and_then(|_| stdout.flush());
+            }
+            let Some(line) = read_line() else {
+                break;
+            };
+            if line.is_empty() {
+                continue;
+            }
+            if matches!(line.to_lowercase().as_str(), "exit" | "quit" | "выход") {
+                break;
+            }
+
+            if let Some(chat) = &mut self.chat {
+                chat.add_message(USER_SENDER, &line);
+            }
+            let (reply, generated) = match self.execute(&line) {
+                Some(reply) => (reply, false),
+                None => (self.ask(&line).await, true),
+            };
+            if let Some(chat) = &mut self.chat {
+                if generated {
+                    chat.add_reply(&self.config.assistant_name, &reply.output);
+                } else {
+                    chat.add_message(&self.config.assistant_name, &reply.output);
+                }
+            }
+            self.print(&line, &reply);
+        }
+
+        // Пустую сессию не оставляем
+        if let Some(chat) = self.chat.take() {
+            if let Some(id) = chat.session_id().filter(|_| !chat.has_user_messages()) {
+                sessions::delete(id);
+            }
+        }
+        0
+    }
+
+    /// Выполняет макрос или команду; None — не распознано
+    fn execute(&mut self, input: &str) -> Option<Reply> {
+        match macros::find(&self.macros, input).cloned() {
+            Some(m) => Some(self.run_macro(&m)),
+            None => self.execute_command(input),
+        }
+    }
+
+    /// Шаги макроса по очереди, в конце — сводка
+    fn run_macro(&mut self, m: &Macro) -> Reply {
+        let mut run = MacroRun::new(m);
+        let mut reply = Reply::with_status(String::new(), Status::Ok);
+
+        while let Some((index, step)) = run.next_step() {
+            if !self.json {
+                eprintln!("{}", trf("Шаг {}/{}: {}", &[&index, &run.total(), &step]));
+            }
+            let outcome = match self.execute_command(&step) {
+                Some(result) => {
+                    let outcome = match result.status {
+                        // Локально команды выполняются сразу, Pending бывает только у окна
+                        Status::Ok | Status::Pending => StepOutcome::Done,
+                        Status::Cancelled => StepOutcome::Cancelled,
+                        Status::Failed | Status::Unknown => StepOutcome::Failed,
+                    };
+                    reply.append(result);
+                    outcome
+                }
+                None => {
+                    reply.status = reply.status.max(Status::Failed);
+                    StepOutcome::NotRecognized
+                }
+            };
+            run.record(step, outcome);
+        }
+
+        reply.append(Reply::with_status(run.summary(), Status::Ok));
+        reply
+    }
+
+    /// Команда через общий обработчик; диалог подтверждения спрашивается
+    /// в терминале, результаты фоновых задач дожидаются
+    fn execute_command(&mut self, input: &str) -> Option<Reply> {
+        let response = commands::process_command(
+            input,
+            &self.config,
+            &mut self.dialog,
+            &self.tasks,
+            &self.guides,
+        )?;
+        let mut reply = self
+            .ui_action(&response)
+            .unwrap_or_else(|| Reply::new(response));
+
+        if self.dialog.visible && !self.confirm() {
+            reply.append(Reply::with_status(tr("Отменено"), Status::Cancelled));
+            return Some(reply);
+        }
+
+        for result in self.wait_tasks() {
+            reply.append(Reply::new(result));
+        }
+        Some(reply)
+    }
+
+    /// Служебные ответы окна. В REPL работают очистка, новый чат и экспорт
+    /// в файл, остальное доступно только в окне приложения
+    fn ui_action(&mut self, response: &str) -> Option<Reply> {
+        if let Some(chat) = &mut self.chat {
+            if response == CMD_CLEAR_CHAT {
+                chat.clear();
+                return Some(Reply::new(tr(messages::CHAT_CLEARED)));
+            }
+            if response == CMD_NEW_CHAT {
+                chat.new_session();
+                sessions::prune(self.config.session_limit);
+                return Some(Reply::new(tr(messages::WELCOME)));
+            }
+            if let Some(format) = response
+                .strip_prefix(CMD_EXPORT_CHAT_PREFIX)
+                .and_then(ExportFormat::from_id)
+            {
+                let messages: Vec<_> = chat.messages().cloned().collect();
+                return Some(Reply::new(export::save(&messages, &chat.title(), format)));
+            }
+        }
+
+        let window_only = [CMD_CLEAR_CHAT, CMD_NEW_CHAT, CMD_SHOW_SESSIONS].contains(&response)
+            || [
+                CMD_EXPORT_CHAT_PREFIX,
+                CMD_SEARCH_HISTORY_PREFIX,
+                CMD_OPEN_PANEL_PREFIX,
+            ]
+            .iter()
+            .any(|prefix| response.starts_with(prefix));
+        window_only.then(|| {
+            Reply::with_status(
+                tr("[X] Эта команда доступна только в окне приложения"),
+                Status::Failed,
+            )
+        })
+    }
+
+    /// Подтверждение диалога в терминале; при согласии запускает его задачу
+    fn confirm(&mut self) -> bool {
+        eprintln!("{}", tr(&self.dialog.title));
+        for line in self.dialog.message.lines() {
+            eprintln!("{}", tr(line));
+        }
+        let package = &self.dialog.package;
+        if !package.is_empty() && !package.starts_with("__") {
+            eprintln!("  {}", package);
+        }
+
+        // Пакеты из AUR: показываем проверку PKGBUILD, как диалог в окне
+        let mut from_aur = false;
+        for review in self.dialog.aur_reviews.iter().map(aur::wait_review) {
+            if !matches!(review, AurReview::Official) {
+                eprintln!("{}", aur::format_review(&review));
+                from_aur = true;
+            }
+        }
+
+        // AUR и критические команды из ответа ИИ подтверждаются только вручную
+        let manual = if from_aur {
+            Some(tr("[!] --yes не действует для пакетов из AUR"))
+        } else if self.dialog.critical && self.from_ai {
+            Some(tr(
+                "[!] --yes не действует для критических команд из ответа ИИ",
+            ))
+        } else {
+            None
+        };
+        if let Some(reason) = manual.filter(|_| self.yes) {
+            eprintln!("{}", reason);
+        }
+
+        if self.dialog.dialog_type == DialogType::PackageSearch {
+            eprint!("{} ", tr("Пакет:"));
+            self.dialog.input = read_line().unwrap_or_default();
+        } else if !self.yes || manual.is_some() {
+            eprint!("{} ", tr("Подтвердить? [y/N]"));
+            let answer = read_line().unwrap_or_default().to_lowercase();
+            if !matches!(answer.as_str(), "y" | "yes" | "д" | "да") {
+                self.dialog.hide();
+                return false;
+            }
+        }
+
+        let task = self.dialog.take_action(self.config.snapshot_before_upgrade);
+        let confirmed = task.is_some() || self.dialog.dialog_type == DialogType::Confirmation;
+        if let Some(task) = task {
+            self.tasks.execute(task);
+        }
+        self.dialog.hide();
+        confirmed
+    }
+
+    /// Ждёт завершения фоновых задач и собирает их результаты
+    fn wait_tasks(&self) -> Vec<String> {
+        let mut results = Vec::new();
+        while self.tasks.is_busy() {
+            match self.results.recv_timeout(Duration::from_millis(100)) {
+                Ok(result) => results.push(result),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+        results.extend(self.results.try_iter());
+        results
+    }
+}
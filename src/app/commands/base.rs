@@ -1,79 +1,98 @@
+use super::registry::{self, Arg, CommandSpec, Group, Values};
+use crate::app::i18n::trf;
 use chrono::Local;
 
 /// Специальные команды для перехвата в UI
 pub const CMD_CLEAR_CHAT: &str = "COMMAND_ACTION_CLEAR";
-
-/// Обработка базовых текстовых команд (приветствие, время, дата, помощь)
-pub fn process_basic_command(cmd: &str, assistant_name: &str) -> Option<String> {
-    match cmd {
-        // Приветствие
-        "привет" | "здравствуй" | "хай" | "hello" => Some(format!(
-            "Привет! Я {}, твой помощник для Arch Linux.",
-            assistant_name
-        )),
-
-        // Очистка чата — возвращаем маркер для перехвата в UI
-        "очистить" | "очистить чат" | "clear" => {
-            Some(CMD_CLEAR_CHAT.to_string())
-        }
-
-        // Повторение фразы
-        cmd if cmd.starts_with("скажи ") => {
-            let message = cmd.trim_start_matches("скажи ").trim();
-            if message.is_empty() {
-                Some("Что именно сказать?".to_string())
-            } else {
-                Some(message.to_string())
-            }
-        }
-
-        // Время
-        "время" | "который час" | "time" => Some(format!(
-            "Текущее время: {}",
-            Local::now().format("%H:%M:%S")
-        )),
-
-        // Дата
-        "дата" | "какое сегодня число" | "date" => {
-            Some(format!("Сегодня: {}", Local::now().format("%d.%m.%Y")))
-        }
-
-        // Дата и время
-        "дата и время" => Some(format!(
-            "Сейчас: {}",
-            Local::now().format("%d.%m.%Y %H:%M:%S")
-        )),
-
-        // Помощь
-        "помощь" | "help" | "?" => Some(HELP_TEXT.to_string()),
-
-        _ => None,
-    }
-}
-
-/// Текст справки
-const HELP_TEXT: &str = "\
-📋 Доступные команды:
-
-▸ Базовые:
-  время, дата, дата и время
-
-▸ Пакеты (через yay):
-  поиск <запрос>
-  установить <пакет>
-  удалить <пакет>
-  обновить систему
-
-▸ Система:
-  выключить пк
-  перезагрузить
-
-▸ Гайды:
-  гайды — список всех гайдов
-  гайд <тема> — показать гайд
-
-▸ Прочее:
-  очистить — очистить чат
-  помощь — эта справка
-
-💡 Или просто задайте вопрос — ИИ постарается помочь!";
+pub const CMD_NEW_CHAT: &str = "COMMAND_ACTION_NEW_CHAT";
+pub const CMD_SHOW_SESSIONS: &str = "COMMAND_ACTION_SESSIONS";
+/// Открыть поиск по истории: за префиксом следует запрос
+pub const CMD_SEARCH_HISTORY_PREFIX: &str = "COMMAND_ACTION_SEARCH:";
+/// Экспорт чата: за префиксом следует формат (пусто — открыть окно)
+pub const CMD_EXPORT_CHAT_PREFIX: &str = "COMMAND_ACTION_EXPORT:";
+/// Открыть панель: за префиксом следует идентификатор панели
+pub const CMD_OPEN_PANEL_PREFIX: &str = "COMMAND_ACTION_PANEL:";
+
+/// Базовые команды, чаты и справка
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Basic,
+        &["время", "который час", "time"],
+        "текущее время",
+        |_, _| trf("Текущее время: {}", &[&Local::now().format("%H:%M:%S")]),
+    ),
+    CommandSpec::new(
+        Group::Basic,
+        &["дата", "какое сегодня число", "date"],
+        "сегодняшняя дата",
+        |_, _| trf("Сегодня: {}", &[&Local::now().format("%d.%m.%Y")]),
+    ),
+    CommandSpec::new(
+        Group::Basic,
+        &["дата и время", "datetime"],
+        "дата и время",
+        |_, _| trf("Сейчас: {}", &[&Local::now().format("%d.%m.%Y %H:%M:%S")]),
+    ),
+    CommandSpec::new(
+        Group::Chats,
+        &["новый чат", "new chat"],
+        "начать новую сессию (Ctrl+N)",
+        |_, _| CMD_NEW_CHAT.to_string(),
+    ),
+    CommandSpec::new(
+        Group::Chats,
+        &["чаты", "история чатов", "сессии", "chats"],
+        "список сохранённых сессий",
+        |_, _| CMD_SHOW_SESSIONS.to_string(),
+    ),
+    // Раньше пакетного «поиск <запрос>» за счёт более длинной фразы
+    CommandSpec::new(
+        Group::Chats,
+        &["найти в истории", "поиск по истории", "search history"],
+        "поиск по всем чатам (Ctrl+F)",
+        |_, query| format!("{}{}", CMD_SEARCH_HISTORY_PREFIX, query),
+    )
+    .arg(Arg::Optional("запрос")),
+    CommandSpec::new(
+        Group::Chats,
+        &["экспорт чата", "экспортировать чат", "export chat"],
+        "сохранить переписку в файл (без формата — окно, Ctrl+E)",
+        |_, format| {
+            let format = format.strip_prefix("в ").unwrap_or(format);
+            format!("{}{}", CMD_EXPORT_CHAT_PREFIX, format.trim())
+        },
+    )
+    .arg(Arg::Optional("md|json|html"))
+    .values(Values::List(&["md", "json", "html"])),
+    // Очистка чата — возвращаем маркер для перехвата в UI
+    CommandSpec::new(
+        Group::Chats,
+        &["очистить", "очистить чат", "clear"],
+        "очистить текущий чат",
+        |_, _| CMD_CLEAR_CHAT.to_string(),
+    ),
+    CommandSpec::new(
+        Group::Other,
+        &["привет", "здравствуй", "хай", "hello"],
+        "поздороваться",
+        |ctx, _| {
+            trf(
+                "Привет! Я {}, твой помощник для Arch Linux.",
+                &[&ctx.config.assistant_name],
+            )
+        },
+    ),
+    CommandSpec::new(
+        Group::Other,
+        &["скажи", "say"],
+        "повторить фразу",
+        |_, text| text.to_string(),
+    )
+    .arg(Arg::Required("фраза")),
+    CommandSpec::new(
+        Group::Other,
+        &["помощь", "help", "?"],
+        "эта справка",
+        |_, _| registry::help(),
+    ),
+];
@@ -0,0 +1,396 @@
+//! Видеокарты: определение через /sys/bus/pci и lspci, рекомендуемые драйверы
+
+use super::kernels;
+use super::registry::{CommandSpec, Group, Risk};
+use crate::app::chat::{BackgroundTask, DialogState};
+use crate::app::config::Config;
+use crate::app::i18n::{tr, trf};
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+
+const PCI_DEVICES: &str = "/sys/bus/pci/devices";
+const PACMAN_CONF: &str = "/etc/pacman.conf";
+
+/// Первое поколение NVIDIA с открытыми модулями ядра (Turing, TU102)
+const NVIDIA_TURING_FIRST: u16 = 0x1e00;
+/// Первое поколение, поддерживаемое веткой 580xx (Maxwell, GM108)
+const NVIDIA_MAXWELL_FIRST: u16 = 0x1340;
+
+/// Последнее определение (для настроек)
+static LAST_REPORT: Mutex<Option<GpuReport>> = Mutex::new(None);
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GpuVendor {
+    Nvidia,
+    Amd,
+    Intel,
+    Other(u16),
+}
+
+impl GpuVendor {
+    fn from_id(id: u16) -> Self {
+        match id {
+            0x10de => Self::Nvidia,
+            0x1002 => Self::Amd,
+            0x8086 => Self::Intel,
+            other => Self::Other(other),
+        }
+    }
+
+    pub fn name(&self) -> String {
+        match self {
+            Self::Nvidia => "NVIDIA".into(),
+            Self::Amd => "AMD".into(),
+            Self::Intel => "Intel".into(),
+            Self::Other(id) => format!("{:04x}", id),
+        }
+    }
+}
+
+/// Видеоадаптер на шине PCI
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gpu {
+    pub slot: String,
+    pub vendor: GpuVendor,
+    pub device_id: u16,
+    pub model: String,
+    /// Загруженный драйвер ядра (nvidia, amdgpu, i915, nouveau...)
+    pub driver: Option<String>,
+}
+
+/// Рекомендуемые пакеты и пояснения
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recommendation {
+    pub packages: Vec<String>,
+    /// Ещё не установленные из `packages`
+    pub missing: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GpuReport {
+    pub gpus: Vec<Gpu>,
+    pub recommendation: Recommendation,
+}
+
+// ============================================================================
+// Определение
+// ============================================================================
+
+/// Видеоадаптеры (PCI-класс 0x03xx)
+pub fn detect_gpus() -> Vec<Gpu> {
+    let mut gpus: Vec<Gpu> = std::fs::read_dir(PCI_DEVICES)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| read_gpu(&e.path()))
+        .collect();
+    gpus.sort_by(|a, b| a.slot.cmp(&b.slot));
+    gpus
+}
+
+fn read_gpu(dir: &Path) -> Option<Gpu> {
+    let read_hex = |name: &str| {
+        std::fs::read_to_string(dir.join(name))
+            .ok()
+            .and_then(|s| u32::from_str_radix(s.trim().trim_start_matches("0x"), 16).ok())
+    };
+    // class = 0xCCSSPP, 0x03 — контроллер дисплея
+    if read_hex("class")? >> 16 != 0x03 {
+        return None;
+    }
+    let vendor = read_hex("vendor")? as u16;
+    let device_id = read_hex("device")? as u16;
+    let slot = dir.file_name()?.to_string_lossy().into_owned();
+    let driver = std::fs::read_link(dir.join("driver"))
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()));
+
+    Some(Gpu {
+        model: lspci_model(&slot)
+            .unwrap_or_else(|| trf("устройство {}", &[&format!("{:04x}", device_id)])),
+        vendor: GpuVendor::from_id(vendor),
+        slot,
+        device_id,
+        driver,
+    })
+}
+
+/// Название модели из `lspci -mm -s <слот>`
+fn lspci_model(slot: &str) -> Option<String> {
+    let output = Command::new("lspci")
+        .args(["-mm", "-s", slot])
+        .output()
+        .ok()?;
+    parse_lspci_model(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// `00:02.0 "VGA compatible controller" "Intel Corporation" "UHD Graphics 620" ...`
+/// — третье поле в кавычках
+pub fn parse_lspci_model(line: &str) -> Option<String> {
+    line.split('"')
+        .skip(1)
+        .step_by(2)
+        .nth(2)
+        .map(str::to_string)
+        .filter(|m| !m.is_empty())
+}
+
+/// Включён ли репозиторий [multilib] (для 32-битных библиотек Steam/Wine)
+fn multilib_enabled() -> bool {
+    std::fs::read_to_string(PACMAN_CONF)
+        .map(|conf| conf.lines().any(|l| l.trim() == "[multilib]"))
+        .unwrap_or(false)
+}
+
+fn is_installed(package: &str) -> bool {
+    Command::new("pacman")
+        .args(["-Q", package])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+// ============================================================================
+// Рекомендации
+// ============================================================================
+
+/// Пакеты для набора видеокарт. `kernels` — установленные пакеты ядер:
+/// для ядер кроме `linux` модуль NVIDIA собирается через DKMS
+pub fn recommend(gpus: &[Gpu], kernels: &[String], multilib: bool) -> Recommendation {
+    let mut rec = Recommendation::default();
+
+    for gpu in gpus {
+        match gpu.vendor {
+            GpuVendor::Intel => {
+                add(&mut rec, ["mesa", "vulkan-intel", "intel-media-driver"]);
+                if multilib {
+                    add(&mut rec, ["lib32-mesa", "lib32-vulkan-intel"]);
+                }
+            }
+            GpuVendor::Amd => {
+                add(&mut rec, ["mesa", "vulkan-radeon"]);
+                if multilib {
+                    add(&mut rec, ["lib32-mesa", "lib32-vulkan-radeon"]);
+                }
+                if gpu.driver.as_deref() == Some("radeon") {
+                    rec.notes.push(trf(
+                        "{} работает на старом драйвере radeon. Для GCN 1/2 можно включить amdgpu: \
+                         radeon.si_support=0 amdgpu.si_support=1 (или cik_support) в параметрах ядра",
+                        &[&gpu.model],
+                    ));
+                }
+            }
+            GpuVendor::Nvidia if gpu.device_id >= NVIDIA_TURING_FIRST => {
+                // Стандартное ядро — готовый модуль, остальные — DKMS с заголовками
+                if kernels.iter().all(|k| k == "linux") {
+                    add(&mut rec, ["nvidia-open"]);
+                } else {
+                    add(&mut rec, ["nvidia-open-dkms"]);
+                    add(&mut rec, kernels.iter().map(|k| format!("{}-headers", k)));
+                }
+                add(&mut rec, ["nvidia-utils"]);
+                if multilib {
+                    add(&mut rec, ["lib32-nvidia-utils"]);
+                }
+                if gpu.driver.as_deref() == Some("nouveau") {
+                    rec.notes.push(
+                        tr("Сейчас загружен nouveau: после установки nvidia-open перезагрузитесь")
+                            .into(),
+                    );
+                }
+            }
+            GpuVendor::Nvidia if gpu.device_id >= NVIDIA_MAXWELL_FIRST => {
+                add(&mut rec, ["nvidia-580xx-dkms", "nvidia-580xx-utils"]);
+                add(&mut rec, kernels.iter().map(|k| format!("{}-headers", k)));
+                if multilib {
+                    add(&mut rec, ["lib32-nvidia-580xx-utils"]);
+                }
+                rec.notes.push(trf(
+                    "{} (Maxwell/Pascal/Volta) не поддерживается открытым модулем — \
+                     ветка 580xx из AUR",
+                    &[&gpu.model],
+                ));
+            }
+            GpuVendor::Nvidia => {
+                add(&mut rec, ["mesa"]);
+                if multilib {
+                    add(&mut rec, ["lib32-mesa"]);
+                }
+                rec.notes.push(trf(
+                    "{} слишком старая для текущих драйверов NVIDIA — используется nouveau (mesa)",
+                    &[&gpu.model],
+                ));
+            }
+            GpuVendor::Other(_) => {}
+        }
+    }
+
+    // Гибридная графика: запуск на дискретной через prime-run
+    let nvidia_open_or_legacy = gpus
+        .iter()
+        .any(|g| g.vendor == GpuVendor::Nvidia && g.device_id >= NVIDIA_MAXWELL_FIRST);
+    if nvidia_open_or_legacy && gpus.iter().any(|g| g.vendor != GpuVendor::Nvidia) {
+        add(&mut rec, ["nvidia-prime"]);
+        rec.notes
+            .push(tr("Гибридная графика: игры на дискретной — prime-run <программа>").into());
+    }
+
+    if !multilib && !rec.packages.is_empty() {
+        rec.notes.push(
+            tr("Для Steam и Wine включите [multilib] в /etc/pacman.conf — добавятся lib32-пакеты")
+                .into(),
+        );
+    }
+    rec
+}
+
+/// Добавляет пакеты без повторов
+fn add<S: AsRef<str>>(rec: &mut Recommendation, packages: impl IntoIterator<Item = S>) {
+    for p in packages {
+        let p = p.as_ref();
+        if !rec.packages.iter().any(|x| x == p) {
+            rec.packages.push(p.to_string());
+        }
+    }
+}
+
+/// Определяет видеокарты и недостающие пакеты, сохраняет результат для настроек
+pub fn detect() -> GpuReport {
+    let gpus = detect_gpus();
+    let kernels: Vec<String> = kernels::installed_kernels()
+        .into_iter()
+        .map(|k| k.package)
+        .collect();
+    let mut recommendation = recommend(&gpus, &kernels, multilib_enabled());
+    recommendation.missing = recommendation
+        .packages
+        .iter()
+        .filter(|p| !is_installed(p))
+        .cloned()
+        .collect();
+
+    let report = GpuReport {
+        gpus,
+        recommendation,
+    };
+    if let Ok(mut last) = LAST_REPORT.lock() {
+        *last = Some(report.clone());
+    }
+    report
+}
+
+/// Последнее определение
+pub fn last_report() -> Option<GpuReport> {
+    LAST_REPORT.lock().ok().and_then(|r| r.clone())
+}
+
+/// Текст отчёта для чата, инструмента ИИ и гайда
+pub fn format_report(report: &GpuReport) -> String {
+    if report.gpus.is_empty() {
+        return tr("Видеокарты не найдены в /sys/bus/pci").into();
+    }
+
+    let mut lines = vec!["Видеокарты:".to_string()];
+    for gpu in &report.gpus {
+        lines.push(trf(
+            "  {} {} — драйвер: {}",
+            &[
+                &gpu.vendor.name(),
+                &gpu.model,
+                &gpu.driver.as_deref().unwrap_or(tr("не загружен")),
+            ],
+        ));
+    }
+
+    let rec = &report.recommendation;
+    if !rec.packages.is_empty() {
+        lines.push(trf("Рекомендуемые пакеты: {}", &[&rec.packages.join(" ")]));
+        if rec.missing.is_empty() {
+            lines.push(tr("[OK] Все рекомендуемые пакеты установлены").into());
+        } else {
+            lines.push(trf(
+                "Не установлены: {}. Установить: установить драйверы",
+                &[&rec.missing.join(" ")],
+            ));
+        }
+    }
+    lines.extend(rec.notes.iter().map(|n| format!("• {}", n)));
+    lines.join("\n")
+}
+
+// ============================================================================
+// Команды
+// ============================================================================
+
+/// Команды видеокарты
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Gpu,
+        &[
+            "видеокарта",
+            "видеокарты",
+            "gpu",
+            "драйверы",
+            "видеодрайвер",
+            "драйвер видеокарты",
+        ],
+        "модель, драйвер, рекомендуемые пакеты",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::GpuInfo);
+            tr("Определяю видеокарту...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Gpu,
+        &[
+            "установить драйверы",
+            "установить видеодрайвер",
+            "установить драйверы видеокарты",
+            "install drivers",
+        ],
+        "недостающие драйверы",
+        |ctx, _| show_install_dialog(&detect(), ctx.config, ctx.dialog),
+    )
+    .risk(Risk::Confirm),
+];
+
+/// Диалог установки недостающих драйверов (из чата и настроек)
+pub fn show_install_dialog(
+    report: &GpuReport,
+    config: &Config,
+    dialog: &mut DialogState,
+) -> String {
+    let rec = &report.recommendation;
+    if report.gpus.is_empty() {
+        return tr("Видеокарты не найдены").into();
+    }
+    if rec.missing.is_empty() {
+        return tr("[OK] Рекомендуемые драйверы уже установлены").into();
+    }
+
+    let mut message = trf("Установить через yay: {}?", &[&rec.missing.join(" ")]);
+    for note in &rec.notes {
+        message.push_str(&format!("\n• {}", note));
+    }
+    dialog.show_confirm_task(
+        "Установка драйверов",
+        &message,
+        BackgroundTask::InstallPackage(rec.missing.join(" "), config.snapshot_before_upgrade),
+    );
+    tr("Подтвердите установку драйверов...").into()
+}
+
+// ============================================================================
+// Функции выполнения (вызываются из фонового потока)
+// ============================================================================
+
+/// Отчёт о видеокартах (для чата и инструмента ИИ)
+pub fn gpu_info() -> String {
+    format_report(&detect())
+}
@@ -1,65 +1,79 @@
-use crate::app::guides::GuideRegistry;
+use super::gpu;
+use super::registry::{Arg, CommandContext, CommandSpec, Group, Values};
+use crate::app::guides::Guide;
+use crate::app::i18n::{tr, trf};
 
-/// Обработка команд работы с гайдами
-pub fn process_guide_command(cmd: &str, guides: &GuideRegistry) -> Option<String> {
-    // Список всех гайдов
-    if cmd == "гайды" || cmd == "guides" || cmd == "обучение" {
-        return Some(guides.format_list());
-    }
-
-    // Показать конкретный гайд: "гайд pacman" или "гайд wifi"
-    if cmd.starts_with("гайд ") || cmd.starts_with("guide ") {
-        let guide_id = cmd
-            .trim_start_matches("гайд ")
-            .trim_start_matches("guide ")
-            .trim();
-
-        if let Some(guide) = guides.get(guide_id) {
-            return Some(guide.format());
-        }
+/// Команды гайдов
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Guides,
+        &["гайды", "guides", "обучение"],
+        "список всех гайдов",
+        |ctx, _| ctx.guides.format_list(),
+    ),
+    // "гайд pacman" или "гайд wifi"
+    CommandSpec::new(
+        Group::Guides,
+        &["гайд", "guide"],
+        "показать гайд",
+        open_guide,
+    )
+    .arg(Arg::Required("тема"))
+    .values(Values::Guides),
+    CommandSpec::new(
+        Group::Guides,
+        &["найти гайд", "поиск гайдов", "find guide"],
+        "поиск гайдов по слову",
+        |ctx, query| {
+            let results = ctx.guides.search(query);
+            if results.is_empty() {
+                return trf("Гайды по запросу '{}' не найдены.", &[&query]);
+            }
 
-        // Поиск по ключевому слову если точный ID не найден
-        let results = guides.search(guide_id);
-        if results.is_empty() {
-            return Some(format!(
-                "Гайд '{}' не найден.\n\nИспользуйте 'гайды' для списка доступных.",
-                guide_id
-            ));
-        } else if results.len() == 1 {
-            return Some(results[0].format());
-        } else {
-            let mut output = format!(
-                "Найдено {} гайдов по запросу '{}':\n\n",
-                results.len(),
-                guide_id
-            );
+            let mut output = trf("Найдено {} гайдов:\n\n", &[&results.len()]);
             for guide in results {
-                output.push_str(&format!("• {} — {}\n", guide.id, guide.title));
+                output.push_str(&format!("• {} — {}\n", guide.id, tr(&guide.title)));
             }
-            output.push_str("\nУточните запрос: гайд <название>");
-            return Some(output);
-        }
-    }
-
-    // Поиск гайдов
-    if cmd.starts_with("найти гайд ") || cmd.starts_with("поиск гайдов ") {
-        let query = cmd
-            .trim_start_matches("найти гайд ")
-            .trim_start_matches("поиск гайдов ")
-            .trim();
+            output.push_str(tr("\nИспользуйте: гайд <название>"));
+            output
+        },
+    )
+    .arg(Arg::Required("запрос")),
+];
 
-        let results = guides.search(query);
-        if results.is_empty() {
-            return Some(format!("Гайды по запросу '{}' не найдены.", query));
-        }
+fn open_guide(ctx: &mut CommandContext, guide_id: &str) -> String {
+    if let Some(guide) = ctx.guides.get(guide_id) {
+        return show_guide(guide);
+    }
 
-        let mut output = format!("Найдено {} гайдов:\n\n", results.len());
+    // Поиск по ключевому слову если точный ID не найден
+    let results = ctx.guides.search(guide_id);
+    if results.is_empty() {
+        trf(
+            "Гайд '{}' не найден.\n\nИспользуйте 'гайды' для списка доступных.",
+            &[&guide_id],
+        )
+    } else if results.len() == 1 {
+        show_guide(results[0])
+    } else {
+        let mut output = trf(
+            "Найдено {} гайдов по запросу '{}':\n\n",
+            &[&results.len(), &guide_id],
+        );
         for guide in results {
-            output.push_str(&format!("• {} — {}\n", guide.id, guide.title));
+            output.push_str(&format!("• {} — {}\n", guide.id, tr(&guide.title)));
         }
-        output.push_str("\nИспользуйте: гайд <название>");
-        return Some(output);
+        output.push_str(tr("\nУточните запрос: гайд <название>"));
+        output
     }
+}
 
-    None
+/// Текст гайда; к гайду gpu добавляется найденное оборудование
+fn show_guide(guide: &Guide) -> String {
+    let mut output = guide.format();
+    if guide.id == "gpu" {
+        output.push_str(tr("Ваша система:\n"));
+        output.push_str(&gpu::format_report(&gpu::detect()));
+    }
+    output
 }
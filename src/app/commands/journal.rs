@@ -0,0 +1,316 @@
+//! Просмотр системного журнала (journalctl) и объяснение ошибок через ИИ
+
+use super::base::CMD_OPEN_PANEL_PREFIX;
+use super::registry::{Arg, CommandSpec, Group, Values};
+use super::services::normalize_unit;
+use crate::app::ai::local_provider::LocalAi;
+use crate::app::chat::BackgroundTask;
+use crate::app::constants::{JOURNAL_EXPLAIN_MAX, JOURNAL_MAX_ENTRIES, JOURNAL_TOOL_ENTRIES};
+use crate::app::i18n::{tr, trf};
+use chrono::{Local, TimeZone};
+use serde_json::Value;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+/// Загруженные записи: `None` — идёт загрузка
+pub type SharedJournal = Arc<Mutex<Option<Result<Vec<JournalEntry>, String>>>>;
+
+/// Объяснение ИИ: пустая строка — ответ ещё не получен
+pub type SharedExplanation = Arc<Mutex<Option<String>>>;
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Уровни важности syslog (индекс = значение PRIORITY)
+pub const PRIORITY_NAMES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+/// Приоритет, начиная с которого запись считается ошибкой
+pub const PRIORITY_ERR: u8 = 3;
+
+/// Запись журнала
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalEntry {
+    /// Время в микросекундах с начала эпохи
+    pub timestamp: i64,
+    pub unit: String,
+    pub priority: u8,
+    pub message: String,
+}
+
+impl JournalEntry {
+    pub fn is_error(&self) -> bool {
+        self.priority <= PRIORITY_ERR
+    }
+
+    /// Время записи в локальном часовом поясе
+    pub fn time(&self) -> String {
+        Local
+            .timestamp_micros(self.timestamp)
+            .single()
+            .map(|d| d.format("%d.%m %H:%M:%S").to_string())
+            .unwrap_or_else(|| "?".into())
+    }
+}
+
+/// Фильтры журнала
+#[derive(Debug, Clone, PartialEq)]
+pub struct JournalQuery {
+    /// Юнит (пусто — все)
+    pub unit: String,
+    /// Максимальный приоритет (0..=7)
+    pub priority: u8,
+    /// Загрузка: 0 — текущая, -1 — предыдущая, `None` — все
+    pub boot: Option<i32>,
+    /// Начало и конец интервала в формате journalctl ("-1h", "today", "2024-05-01 10:00")
+    pub since: String,
+    pub until: String,
+    pub limit: usize,
+}
+
+impl Default for JournalQuery {
+    fn default() -> Self {
+        Self {
+            unit: String::new(),
+            priority: 4,
+            boot: Some(0),
+            since: String::new(),
+            until: String::new(),
+            limit: JOURNAL_MAX_ENTRIES,
+        }
+    }
+}
+
+impl JournalQuery {
+    /// Только ошибки текущей загрузки (для объяснения и инструмента ИИ)
+    pub fn errors(unit: &str, limit: usize) -> Self {
+        Self {
+            unit: unit.to_string(),
+            priority: PRIORITY_ERR,
+            limit,
+            ..Self::default()
+        }
+    }
+
+    /// Аргументы journalctl
+    pub fn args(&self) -> Result<Vec<String>, String> {
+        let mut args = vec![
+            "-o".to_string(),
+            "json".to_string(),
+            "--no-pager".to_string(),
+            format!("--lines={}", self.limit),
+            format!("--priority={}", self.priority.min(7)),
+        ];
+
+        if let Some(boot) = self.boot {
+            args.push(format!("--boot={}", boot));
+        }
+
+        let unit = self.unit.trim();
+        if !unit.is_empty() {
+            let unit =
+                normalize_unit(unit).ok_or_else(|| trf("Некорректное имя юнита: {}", &[&unit]))?;
+            // Звук, portal и т.п. — пользовательские сервисы, ищем и среди них
+            args.push(format!("--unit={}", unit));
+            args.push(format!("--user-unit={}", unit));
+        }
+
+        // Значения передаются через "=", поэтому не могут стать отдельным флагом
+        if !self.since.trim().is_empty() {
+            args.push(format!("--since={}", self.since.trim()));
+        }
+        if !self.until.trim().is_empty() {
+            args.push(format!("--until={}", self.until.trim()));
+        }
+
+        Ok(args)
+    }
+}
+
+// ============================================================================
+// Парсинг `journalctl -o json`
+// ============================================================================
+
+/// Строковое поле записи (journald отдаёт бинарные значения массивом байт)
+fn field(obj: &Value, key: &str) -> Option<String> {
+    match obj.get(key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes
+                .iter()
+                .filter_map(|b| b.as_u64().map(|b| b as u8))
+                .collect();
+            Some(String::from_utf8_lossy(&bytes).into_owned())
+        }
+        _ => None,
+    }
+}
+
+/// Разбирает вывод journalctl (по одному JSON-объекту на строку)
+pub fn parse_entries(output: &str) -> Vec<JournalEntry> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+        .filter_map(|obj| {
+            let message = field(&obj, "MESSAGE")?;
+            let unit = field(&obj, "_SYSTEMD_USER_UNIT")
+                .or_else(|| field(&obj, "_SYSTEMD_UNIT"))
+                .or_else(|| field(&obj, "SYSLOG_IDENTIFIER"))
+                .or_else(|| {
+                    (field(&obj, "_TRANSPORT").as_deref() == Some("kernel"))
+                        .then(|| "kernel".to_string())
+                })
+                .unwrap_or_else(|| "?".into());
+
+            Some(JournalEntry {
+                timestamp: field(&obj, "__REALTIME_TIMESTAMP")
+                    .and_then(|t| t.parse().ok())
+                    .unwrap_or(0),
+                unit,
+                priority: field(&obj, "PRIORITY")
+                    .and_then(|p| p.parse().ok())
+                    .unwrap_or(6),
+                message: message.trim_end().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Читает журнал с заданными фильтрами
+pub fn read_journal(query: &JournalQuery) -> Result<Vec<JournalEntry>, String> {
+    let output = Command::new("journalctl")
+        .args(query.args()?)
+        .output()
+        .map_err(|e| trf("Ошибка journalctl: {}", &[&e]))?;
+
+    if !output.status.success() && output.stdout.is_empty() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    Ok(parse_entries(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Перечитывает журнал в фоновом потоке
+pub fn refresh(shared: &SharedJournal, query: JournalQuery) {
+    if let Ok(mut j) = shared.lock() {
+        *j = None;
+    }
+
+    let shared = shared.clone();
+    std::thread::spawn(move || {
+        let result = read_journal(&query);
+        if let Ok(mut j) = shared.lock() {
+            *j = Some(result);
+        }
+    });
+}
+
+/// Текстовое представление записей (для чата и ИИ)
+pub fn format_entries(entries: &[JournalEntry]) -> String {
+    entries
+        .iter()
+        .map(|e| {
+            format!(
+                "{} {} [{}]: {}",
+                e.time(),
+                e.unit,
+                PRIORITY_NAMES[e.priority.min(7) as usize],
+                e.message
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ============================================================================
+// Объяснение через ИИ
+// ============================================================================
+
+/// Системный промпт для разбора ошибок журнала
+pub const EXPLAIN_PROMPT: &str = "\
+Ты разбираешь записи системного журнала Arch Linux (journalctl). На русском, кратко: \
+1) что пошло не так, простыми словами; 2) вероятная причина; \
+3) конкретные шаги или команды для исправления. \
+Опирайся только на приведённые записи, не выдумывай. Если записи не похожи на ошибку — так и скажи.";
+
+/// Отправляет записи ИИ и сохраняет объяснение
+pub fn request_explanation(
+    entries: &[JournalEntry],
+    ai: Arc<LocalAi>,
+    explanation: &SharedExplanation,
+) {
+    // Берём последние записи, чтобы не переполнить контекст модели
+    let start = entries.len().saturating_sub(JOURNAL_EXPLAIN_MAX);
+    let text = format_entries(&entries[start..]);
+
+    if let Ok(mut e) = explanation.lock() {
+        *e = Some(String::new());
+    }
+
+    let shared = explanation.clone();
+    tokio::spawn(async move {
+        let answer = ai
+            .ask(tr(EXPLAIN_PROMPT), &text)
+            .await
+            .unwrap_or_else(|e| trf("Ошибка ИИ: {}", &[&e]));
+
+        if let Ok(mut e) = shared.lock() {
+            *e = Some(answer);
+        }
+    });
+}
+
+// ============================================================================
+// Команды
+// ============================================================================
+
+/// Команды системного журнала
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Journal,
+        &["журнал", "логи", "journal"],
+        "просмотр журнала с фильтрами",
+        |_, unit| format!("{}journal:{}", CMD_OPEN_PANEL_PREFIX, unit),
+    )
+    .arg(Arg::Optional("сервис"))
+    .values(Values::Services),
+    CommandSpec::new(
+        Group::Journal,
+        &["ошибки", "ошибки сервиса", "errors"],
+        "последние ошибки в чат",
+        |ctx, unit| {
+            ctx.tasks
+                .execute(BackgroundTask::JournalErrors(unit.to_string()));
+            tr("Читаю журнал...").into()
+        },
+    )
+    .arg(Arg::Optional("сервис"))
+    .values(Values::Services),
+    // Панель с ошибками и автоматическим объяснением
+    CommandSpec::new(
+        Group::Journal,
+        &["объясни ошибки", "объяснить ошибки", "explain errors"],
+        "разбор ошибок ИИ",
+        |_, unit| format!("{}journal:{}:explain", CMD_OPEN_PANEL_PREFIX, unit),
+    )
+    .arg(Arg::Optional("сервис"))
+    .values(Values::Services),
+];
+
+/// Последние ошибки юнита за текущую загрузку (пустой юнит — все)
+pub fn recent_errors(unit: &str) -> String {
+    let query = JournalQuery::errors(unit.trim(), JOURNAL_TOOL_ENTRIES);
+    match read_journal(&query) {
+        Ok(entries) if entries.is_empty() => {
+            if unit.trim().is_empty() {
+                tr("ошибок в журнале нет").into()
+            } else {
+                trf("ошибок для {} нет", &[&unit.trim()])
+            }
+        }
+        Ok(entries) => format_entries(&entries),
+        Err(e) => format!("[X] {}", e),
+    }
+}
@@ -0,0 +1,389 @@
+//! Ядра и записи загрузчика
+
+use super::package;
+use super::registry::{CommandContext, CommandSpec, Group, Risk};
+use crate::app::chat::BackgroundTask;
+use crate::app::i18n::{tr, trf};
+use std::path::Path;
+use std::process::Command;
+
+/// Пакеты ядер из официальных репозиториев
+const KERNEL_PACKAGES: &[&str] = &[
+    "linux",
+    "linux-lts",
+    "linux-zen",
+    "linux-hardened",
+    "linux-rt",
+    "linux-rt-lts",
+];
+
+const MODULES_DIR: &str = "/usr/lib/modules";
+const GRUB_CONFIG: &str = "/boot/grub/grub.cfg";
+/// Возможные точки монтирования ESP для systemd-boot
+const ESP_PATHS: &[&str] = &["/boot", "/efi", "/boot/efi"];
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Установленный пакет ядра
+#[derive(Debug, Clone, PartialEq)]
+pub struct Kernel {
+    pub package: String,
+    pub version: String,
+}
+
+/// Загрузчик и его записи
+#[derive(Debug, Clone, PartialEq)]
+pub enum Bootloader {
+    SystemdBoot {
+        esp: String,
+        entries: Vec<BootEntry>,
+    },
+    Grub {
+        entries: Vec<BootEntry>,
+    },
+}
+
+/// Запись меню загрузки
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootEntry {
+    pub title: String,
+    pub default: bool,
+}
+
+// ============================================================================
+// Чтение состояния
+// ============================================================================
+
+/// Версия работающего ядра (`uname -r`)
+pub fn running_kernel() -> String {
+    std::fs::read_to_string("/proc/sys/kernel/osrelease")
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Установленные пакеты ядер по `pacman -Q`
+pub fn installed_kernels() -> Vec<Kernel> {
+    let output = Command::new("pacman")
+        .arg("-Q")
+        .args(KERNEL_PACKAGES)
+        .output()
+        .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        .unwrap_or_default();
+    parse_kernels(&output)
+}
+
+fn parse_kernels(output: &str) -> Vec<Kernel> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (package, version) = line.split_once(' ')?;
+            Some(Kernel {
+                package: package.to_string(),
+                version: version.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Нужна ли перезагрузка: после обновления ядра pacman удаляет модули
+/// работающей версии, и новые модули (USB, GPU) перестают загружаться
+pub fn reboot_needed(running: &str) -> bool {
+    !running.is_empty()
+        && Path::new(MODULES_DIR).exists()
+        && !Path::new(MODULES_DIR).join(running).exists()
+}
+
+/// Определяет загрузчик: systemd-boot по loader/entries на ESP, иначе GRUB
+pub fn detect_bootloader() -> Option<Result<Bootloader, String>> {
+    for esp in ESP_PATHS {
+        let loader = Path::new(esp).join("loader");
+        if loader.join("entries").is_dir() || loader.join("loader.conf").exists() {
+            return Some(Ok(Bootloader::SystemdBoot {
+                esp: esp.to_string(),
+                entries: systemd_boot_entries(&loader, Path::new(esp)),
+            }));
+        }
+    }
+
+    if Path::new(GRUB_CONFIG).exists() {
+        return Some(
+            std::fs::read_to_string(GRUB_CONFIG)
+                .map(|cfg| Bootloader::Grub {
+                    entries: parse_grub_entries(&cfg),
+                })
+                .map_err(|e| format!("{}: {}", GRUB_CONFIG, e)),
+        );
+    }
+    None
+}
+
+/// Записи loader/entries/*.conf и UKI из EFI/Linux
+fn systemd_boot_entries(loader: &Path, esp: &Path) -> Vec<BootEntry> {
+    let default = std::fs::read_to_string(loader.join("loader.conf"))
+        .ok()
+        .and_then(|conf| {
+            conf.lines()
+                .find_map(|l| l.trim().strip_prefix("default"))
+                .map(|d| d.trim().to_string())
+        })
+        .unwrap_or_default();
+    let is_default = |file: &str| {
+        !default.is_empty()
+            && (file == default || default.contains('*') && matches_glob(&default, file))
+    };
+
+    let mut entries = Vec::new();
+    let mut files: Vec<_> = std::fs::read_dir(loader.join("entries"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "conf"))
+        .collect();
+    files.sort();
+    for path in files {
+        let file = path
+            .file_name()
+            .map(|f| f.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let title = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|c| parse_entry_title(&c))
+            .unwrap_or_else(|| file.trim_end_matches(".conf").to_string());
+        entries.push(BootEntry {
+            default: is_default(&file),
+            title,
+        });
+    }
+
+    // Унифицированные образы ядра (UKI) находятся автоматически
+    let mut ukis: Vec<String> = std::fs::read_dir(esp.join("EFI/Linux"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+        .filter(|f| f.ends_with(".efi"))
+        .collect();
+    ukis.sort();
+    entries.extend(ukis.into_iter().map(|f| BootEntry {
+        default: is_default(&f),
+        title: format!("{} (UKI)", f.trim_end_matches(".efi")),
+    }));
+
+    entries
+}
+
+/// Строка `title` записи systemd-boot
+fn parse_entry_title(conf: &str) -> Option<String> {
+    conf.lines()
+        .find_map(|l| l.trim().strip_prefix("title"))
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+}
+
+/// Шаблон `default` из loader.conf (только `*`)
+fn matches_glob(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let Some(first) = parts.next() else {
+        return false;
+    };
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    pattern.ends_with('*') || rest.is_empty()
+}
+
+/// Пункты `menuentry` и `submenu` из grub.cfg; первый пункт загружается по умолчанию
+pub fn parse_grub_entries(cfg: &str) -> Vec<BootEntry> {
+    let mut entries: Vec<BootEntry> = Vec::new();
+    // Открытые блоки: true — submenu (его пункты показываем с отступом)
+    let mut blocks: Vec<bool> = Vec::new();
+
+    for line in cfg.lines().map(str::trim) {
+        let submenu = line.starts_with("submenu ");
+        if let Some(title) = line
+            .strip_prefix("menuentry ")
+            .or_else(|| line.strip_prefix("submenu "))
+            .and_then(quoted)
+        {
+            let depth = blocks.iter().filter(|b| **b).count();
+            entries.push(BootEntry {
+                default: entries.is_empty(),
+                title: format!("{}{}", "  ".repeat(depth), title),
+            });
+        }
+
+        if line.ends_with('{') {
+            blocks.push(submenu);
+        } else if line == "}" {
+            blocks.pop();
+        }
+    }
+    entries
+}
+
+/// Первая строка в кавычках: 'Arch Linux' или "Arch Linux"
+fn quoted(s: &str) -> Option<&str> {
+    let quote = s.chars().next().filter(|c| *c == '\'' || *c == '"')?;
+    let rest = &s[1..];
+    rest.find(quote).map(|end| &rest[..end])
+}
+
+// ============================================================================
+// Команды
+// ============================================================================
+
+/// Команды ядер и загрузчика
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Kernels,
+        &[
+            "ядра",
+            "ядро",
+            "kernels",
+            "kernel",
+            "загрузчик",
+            "записи загрузки",
+        ],
+        "ядра, загрузчик, нужна ли перезагрузка",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::KernelInfo);
+            tr("Проверяю ядра и загрузчик...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Kernels,
+        &[
+            "установить lts ядро",
+            "установить ядро lts",
+            "установить резервное ядро",
+            "установить linux-lts",
+            "резервное ядро",
+            "install lts kernel",
+        ],
+        "резервное ядро linux-lts",
+        install_lts,
+    )
+    .risk(Risk::Confirm),
+];
+
+fn install_lts(ctx: &mut CommandContext, _: &str) -> String {
+    if installed_kernels().iter().any(|k| k.package == "linux-lts") {
+        return tr("[OK] Ядро linux-lts уже установлено").into();
+    }
+    ctx.dialog.show_confirm_task(
+        "Установка ядра LTS",
+        &trf(
+            "Установить {} как резервное ядро?\n{}",
+            &[&lts_packages().join(" "), &bootloader_note()],
+        ),
+        BackgroundTask::InstallLtsKernel(ctx.config.snapshot_before_upgrade),
+    );
+    tr("Подтвердите установку ядра...").into()
+}
+
+/// linux-lts и заголовки, если они нужны модулям DKMS (nvidia-dkms и др.)
+fn lts_packages() -> Vec<&'static str> {
+    let needs_headers = Command::new("pacman")
+        .args(["-Q", "dkms"])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    if needs_headers {
+        vec!["linux-lts", "linux-lts-headers"]
+    } else {
+        vec!["linux-lts"]
+    }
+}
+
+/// Что произойдёт с меню загрузки после установки ядра
+fn bootloader_note() -> &'static str {
+    match detect_bootloader() {
+        Some(Ok(Bootloader::Grub { .. })) => {
+            tr("После установки меню GRUB будет обновлено (grub-mkconfig)")
+        }
+        Some(Ok(Bootloader::SystemdBoot { .. })) => {
+            tr("Для systemd-boot может понадобиться запись в loader/entries (если не используются UKI)")
+        }
+        _ => tr("Не забудьте добавить ядро в меню загрузчика"),
+    }
+}
+
+// ============================================================================
+// Функции выполнения (вызываются из фонового потока)
+// ============================================================================
+
+/// Отчёт о ядрах и загрузчике (для чата и инструмента ИИ)
+pub fn kernel_info() -> String {
+    let running = running_kernel();
+    let kernels = installed_kernels();
+    let mut lines = vec![trf("Работающее ядро: {}", &[&running])];
+
+    if kernels.is_empty() {
+        lines.push(tr("Пакеты ядер не найдены (pacman недоступен?)").into());
+    } else {
+        lines.push(tr("Установленные ядра:").into());
+        lines.extend(
+            kernels
+                .iter()
+                .map(|k| format!("  {} {}", k.package, k.version)),
+        );
+    }
+
+    if reboot_needed(&running) {
+        lines.push(trf(
+            "[!] Ядро обновлено, нужна перезагрузка: модулей {} больше нет в {}",
+            &[&running, &MODULES_DIR],
+        ));
+    }
+
+    match detect_bootloader() {
+        Some(Ok(loader)) => {
+            let (name, entries) = match &loader {
+                Bootloader::SystemdBoot { esp, entries } => {
+                    (format!("systemd-boot ({})", esp), entries)
+                }
+                Bootloader::Grub { entries } => ("GRUB".to_string(), entries),
+            };
+            lines.push(trf("Загрузчик: {}", &[&name]));
+            lines.extend(entries.iter().map(|e| {
+                format!(
+                    "  {}{}",
+                    e.title,
+                    if e.default {
+                        tr(" (по умолчанию)")
+                    } else {
+                        ""
+                    }
+                )
+            }));
+        }
+        Some(Err(e)) => lines.push(trf("Загрузчик: нет доступа к меню ({})", &[&e])),
+        None => lines.push(tr("Загрузчик не определён").into()),
+    }
+
+    if !kernels.iter().any(|k| k.package.starts_with("linux-lts")) && !kernels.is_empty() {
+        lines.push(tr("Резервного ядра нет. Установить: установить lts ядро").into());
+    }
+
+    lines.join("\n")
+}
+
+/// Установка linux-lts в терминале, для GRUB — с обновлением меню
+pub fn install_lts_kernel(snapshot: bool) -> String {
+    let mut cmd = format!("yay -S {}", lts_packages().join(" "));
+    if matches!(detect_bootloader(), Some(Ok(Bootloader::Grub { .. }))) {
+        cmd.push_str(&format!(" && sudo grub-mkconfig -o {}", GRUB_CONFIG));
+    }
+    package::with_snapshot(snapshot, tr("Установка ядра LTS"), || {
+        package::run_in_terminal(&cmd, tr("Установка ядра LTS"))
+    })
+}
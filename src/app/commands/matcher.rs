@@ -0,0 +1,298 @@
+//! Нечёткий разбор команд
+//!
+//! Срабатывает, когда ввод не совпал с фразами реестра дословно. Слова
+//! сводятся к основе (отбрасываются окончания инфинитива, повелительного
+//! наклонения и падежей), порядок слов не важен, опечатки допускаются по
+//! расстоянию Левенштейна. Слишком далёкие совпадения не выполняются,
+//! а предлагаются пользователю: «Вы имели в виду ...?»
+
+use super::registry::{self, Arg, CommandSpec};
+use crate::app::i18n::trf;
+
+// ============================================================================
+// Нормализация слов
+// ============================================================================
+
+/// Окончания, отбрасываемые при поиске основы (длинные раньше коротких)
+const SUFFIXES: &[&str] = &[
+    "ться", "тесь", "ами", "ями", "ого", "его", "ому", "ему", "ыми", "ими", "ить", "ать", "ять",
+    "еть", "уть", "ыть", "ите", "йте", "ете", "ишь", "ешь", "ит", "ет", "ут", "ют", "ат", "ят",
+    "ть", "ой", "ей", "ую", "юю", "ая", "яя", "ое", "ее", "ые", "ие", "ый", "ий", "ом", "ем", "ам",
+    "ям", "ах", "ях", "ов", "ев", "и", "й", "ь", "а", "я", "о", "е", "ы", "у", "ю",
+];
+
+/// Вежливые и служебные слова, которые не влияют на команду
+const FILLERS: &[&str] = &["пожалуйста", "плиз", "please", "мне", "давай", "ка"];
+
+/// Короче этого основа не обрезается: «сон», «пк», «дата» остаются как есть
+const MIN_STEM: usize = 3;
+
+/// Основа слова: «установи», «установить» → «установ»
+fn stem(word: &str) -> String {
+    let word = word.replace('ё', "е");
+    let len = word.chars().count();
+    if len <= MIN_STEM + 1 {
+        return word;
+    }
+    for suffix in SUFFIXES {
+        if let Some(base) = word.strip_suffix(suffix) {
+            if base.chars().count() >= MIN_STEM {
+                return base.to_string();
+            }
+        }
+    }
+    word
+}
+
+/// Расстояние Левенштейна по символам
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { prev } else { prev + 1 };
+            prev = row[j + 1];
+            row[j + 1] = cost.min(row[j] + 1).min(prev + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Опечатки между основами. Начало слова должно совпадать: иначе «включи»
+/// оказалось бы в одной опечатке от «выключить»
+fn typos(input: &str, phrase: &str) -> usize {
+    let head = |s: &str| s.chars().take(2).collect::<String>();
+    if input == phrase {
+        0
+    } else if head(input) != head(phrase) {
+        usize::MAX
+    } else {
+        levenshtein(input, phrase)
+    }
+}
+
+/// Допустимое число опечаток для основы: выполнить и предложить
+fn tolerance(stem: &str) -> (usize, usize) {
+    match stem.chars().count() {
+        0..=4 => (0, 0),
+        5..=7 => (1, 1),
+        _ => (2, 3),
+    }
+}
+
+/// Слово ввода: основа и позиция в исходной строке
+struct Token {
+    stem: String,
+    start: usize,
+}
+
+fn tokenize(cmd: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = cmd;
+    while let Some(begin) = rest.find(|c: char| !c.is_whitespace()) {
+        let word_len = rest[begin..]
+            .find(char::is_whitespace)
+            .unwrap_or(rest.len() - begin);
+        let word = rest[begin..begin + word_len].trim_matches(|c: char| !c.is_alphanumeric());
+        if !word.is_empty() && !FILLERS.contains(&word) {
+            tokens.push(Token {
+                stem: stem(word),
+                start: cmd.len() - rest.len() + begin,
+            });
+        }
+        rest = &rest[begin + word_len..];
+    }
+    tokens
+}
+
+// ============================================================================
+// Поиск команды
+// ============================================================================
+
+/// Результат нечёткого разбора
+pub enum Fuzzy<'a> {
+    /// Команда распознана уверенно
+    Command(&'static CommandSpec, &'a str),
+    /// Похоже на команду — переспрашиваем
+    DidYouMean(String),
+}
+
+/// Сопоставляет слова ввода со словами фразы без учёта порядка.
+/// Возвращает сумму опечаток и признак, что все слова в пределах «выполнить»
+fn match_words(input: &[Token], phrase: &[String]) -> Option<(usize, bool)> {
+    let mut used = vec![false; input.len()];
+    let mut total = 0;
+    let mut confident = true;
+    for word in phrase {
+        let (run, suggest) = tolerance(word);
+        let (index, distance) = input
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !used[*i])
+            .map(|(i, token)| (i, typos(&token.stem, word)))
+            .min_by_key(|(_, distance)| *distance)?;
+        if distance > suggest {
+            return None;
+        }
+        confident &= distance <= run;
+        used[index] = true;
+        total += distance;
+    }
+    Some((total, confident))
+}
+
+/// Нечёткий поиск команды по вводу в нижнем регистре
+pub fn find(cmd: &str) -> Option<Fuzzy<'_>> {
+    let tokens = tokenize(cmd);
+    if tokens.is_empty() {
+        return None;
+    }
+
+    // (слов во фразе, опечатки) — лучше длиннее фраза, затем меньше опечаток:
+    // иначе «выключить компютер» стал бы «выключить» с аргументом «компютер»
+    let mut best: Option<((usize, usize), Fuzzy)> = None;
+    for spec in registry::all() {
+        for phrase in spec.phrases {
+            let words: Vec<String> = phrase.split_whitespace().map(stem).collect();
+            let takes_arg = spec.arg != Arg::None;
+            if tokens.len() < words.len() || (!takes_arg && tokens.len() != words.len()) {
+                continue;
+            }
+
+            let head = &tokens[..words.len()];
+            let Some((typos, confident)) = match_words(head, &words) else {
+                continue;
+            };
+            let arg = tokens
+                .get(words.len())
+                .map(|t| cmd[t.start..].trim_end_matches(['.', '!', '?', ',']))
+                .unwrap_or("");
+
+            if !spec.accepts_arg(arg) {
+                continue;
+            }
+
+            let found = if takes_arg {
+                // Аргумент — свободный текст, поэтому выполняем только при
+                // точной основе; с опечаткой переспрашиваем, если аргумент
+                // похож на имя (одно слово), иначе это скорее вопрос для ИИ
+                if typos == 0 {
+                    Fuzzy::Command(spec, arg)
+                } else if confident && !arg.contains(char::is_whitespace) {
+                    Fuzzy::DidYouMean(format!("{} {}", phrase, arg).trim_end().to_string())
+                } else {
+                    continue;
+                }
+            } else if confident {
+                Fuzzy::Command(spec, "")
+            } else {
+                Fuzzy::DidYouMean(phrase.to_string())
+            };
+
+            let rank = (usize::MAX - words.len(), typos);
+            if best.as_ref().is_none_or(|(r, _)| rank < *r) {
+                best = Some((rank, found));
+            }
+        }
+    }
+    best.map(|(_, found)| found)
+}
+
+/// Ответ на почти совпавшую команду
+pub fn did_you_mean(phrase: &str) -> String {
+    trf(
+        "Вы имели в виду «{}»? Повторите команду в этой форме.",
+        &[&phrase],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Итог разбора: основная фраза команды и аргумент,
+    /// «?фраза» — переспрос, None — уходит ИИ
+    fn parse(input: &str) -> Option<String> {
+        find(input).map(|found| match found {
+            Fuzzy::Command(spec, "") => spec.phrases[0].to_string(),
+            Fuzzy::Command(spec, arg) => format!("{} | {}", spec.phrases[0], arg),
+            Fuzzy::DidYouMean(phrase) => format!("?{}", phrase),
+        })
+    }
+
+    #[test]
+    fn table() {
+        let cases: &[(&str, Option<&str>)] = &[
+            // Формы глагола и порядок слов
+            ("обнови систему", Some("обновить систему")),
+            ("систему обнови", Some("обновить систему")),
+            ("выключи компьютер", Some("выключить пк")),
+            ("компьютер выключи", Some("выключить пк")),
+            ("звук включи", Some("включи звук")),
+            ("перезагрузи", Some("перезагрузить")),
+            // Аргументы
+            ("установи firefox", Some("установить | firefox")),
+            ("громкость 50", Some("громкость | 50")),
+            ("включить сервис sshd", Some("включить сервис | sshd")),
+            // Слова-заполнители
+            ("обнови систему пожалуйста", Some("обновить систему")),
+            ("установи пожалуйста firefox", Some("установить | firefox")),
+            ("давай перезагрузить", Some("перезагрузить")),
+            ("пожалуйста", None),
+            // Опечатки: без аргумента выполняем, с аргументом переспрашиваем
+            ("обнавить систему", Some("обновить систему")),
+            ("перезогрузить", Some("?перезагрузить")),
+            ("процесы", Some("?процессы")),
+            ("устоновить firefox", Some("?установить firefox")),
+            ("громкасть 50", Some("?громкость 50")),
+            ("выклюци компьютер", Some("?выключить компьютер")),
+            // Опечатка во втором слове не превращает его в аргумент
+            ("выключить компютер", Some("?выключить компьютер")),
+            // «включи» не путается с «выключить»
+            ("включи звук", Some("включи звук")),
+            ("выключи звук", Some("выключи звук")),
+            ("включи компьютер", None),
+            ("включи", None),
+            ("ключи звук", None),
+            // Свободные вопросы уходят ИИ
+            ("как установить firefox на arch", None),
+            ("почему не работает звук", None),
+            ("что такое systemd", None),
+            ("покажи процессы", None),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parse(input).as_deref(), *expected, "{}", input);
+        }
+    }
+
+    #[test]
+    fn stem_strips_endings() {
+        assert_eq!(stem("установи"), "установ");
+        assert_eq!(stem("установить"), "установ");
+        assert_eq!(stem("обнови"), stem("обновить"));
+        assert_eq!(stem("включиться"), "включи");
+        assert_eq!(stem("зелёный"), "зелен");
+        // Короткие слова не обрезаются
+        assert_eq!(stem("сон"), "сон");
+        assert_eq!(stem("дата"), "дата");
+        assert_eq!(stem("пк"), "пк");
+    }
+
+    #[test]
+    fn levenshtein_counts_chars() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("звук", ""), 4);
+        assert_eq!(levenshtein("звук", "звук"), 0);
+        assert_eq!(levenshtein("обнов", "обнав"), 1);
+        assert_eq!(levenshtein("процесс", "процес"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn typos_require_same_head() {
+        assert_eq!(typos("включ", "выключ"), usize::MAX);
+        assert_eq!(typos("выклюц", "выключ"), 1);
+    }
+}
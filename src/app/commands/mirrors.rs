@@ -0,0 +1,616 @@
+//! Управление зеркалами pacman (/etc/pacman.d/mirrorlist)
+
+use super::registry::{Arg, CommandSpec, Group, Risk};
+use crate::app::chat::{BackgroundTask, DialogState};
+use crate::app::config::Config;
+use crate::app::constants::{MIRRORLIST_PATH, MIRROR_PROBE_BATCH, MIRROR_PROBE_TIMEOUT_MS};
+use crate::app::i18n::{tr, trf};
+use crate::app::privileged;
+use chrono::Local;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Подготовленный, но ещё не записанный список зеркал
+static PENDING_PLAN: Mutex<Option<MirrorPlan>> = Mutex::new(None);
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Одно зеркало из mirrorlist
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mirror {
+    pub url: String,
+    pub country: Option<String>,
+    pub protocol: String,
+    pub enabled: bool,
+    pub latency: Option<Duration>,
+}
+
+/// Параметры ранжирования
+#[derive(Debug, Clone, Default)]
+pub struct MirrorFilter {
+    pub countries: Vec<String>,
+    pub https_only: bool,
+    pub count: usize,
+}
+
+impl MirrorFilter {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            countries: config.mirror_countries.clone(),
+            https_only: config.mirror_https_only,
+            count: config.mirror_count,
+        }
+    }
+
+    fn accepts(&self, mirror: &Mirror) -> bool {
+        let protocol_ok = match mirror.protocol.as_str() {
+            "https" => true,
+            "http" => !self.https_only,
+            _ => false,
+        };
+
+        let country_ok = self.countries.is_empty()
+            || mirror.country.as_ref().is_some_and(|c| {
+                self.countries
+                    .iter()
+                    .any(|wanted| wanted.eq_ignore_ascii_case(c))
+            });
+
+        protocol_ok && country_ok
+    }
+}
+
+/// Строка сравнения старого и нового списка
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+/// Результат ранжирования: новый файл и diff относительно текущего
+#[derive(Debug, Clone)]
+pub struct MirrorPlan {
+    pub mirrors: Vec<Mirror>,
+    pub content: String,
+    pub diff: Vec<DiffLine>,
+}
+
+// ============================================================================
+// Измерение задержки
+// ============================================================================
+
+/// Источник замеров задержки (подменяется при офлайн-проверке)
+pub trait LatencyProbe: Sync {
+    /// Возвращает время отклика зеркала или `None`, если оно недоступно
+    fn measure(&self, url: &str) -> Option<Duration>;
+}
+
+/// Замер через время установки TCP-соединения с хостом зеркала
+pub struct TcpProbe {
+    pub timeout: Duration,
+}
+
+impl Default for TcpProbe {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_millis(MIRROR_PROBE_TIMEOUT_MS),
+        }
+    }
+}
+
+impl LatencyProbe for TcpProbe {
+    fn measure(&self, url: &str) -> Option<Duration> {
+        let (host, port) = host_and_port(url)?;
+        let addr = (host.as_str(), port).to_socket_addrs().ok()?.next()?;
+
+        let start = Instant::now();
+        TcpStream::connect_timeout(&addr, self.timeout).ok()?;
+        Some(start.elapsed())
+    }
+}
+
+/// Извлекает хост и порт из URL зеркала
+fn host_and_port(url: &str) -> Option<(String, u16)> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split('/').next()?;
+    let default_port = if scheme == "https" { 443 } else { 80 };
+
+    // IPv6 в квадратных скобках: "[::1]:443"
+    if let Some(rest) = authority.strip_prefix('[') {
+        let (host, tail) = rest.split_once(']')?;
+        let port = match tail.strip_prefix(':') {
+            Some(port) => port.parse().ok()?,
+            None if tail.is_empty() => default_port,
+            None => return None,
+        };
+        return Some((host.to_string(), port));
+    }
+
+    match authority.rsplit_once(':') {
+        Some((host, port)) => Some((host.to_string(), port.parse().ok()?)),
+        None => Some((authority.to_string(), default_port)),
+    }
+}
+
+// ============================================================================
+// Парсинг и ранжирование
+// ============================================================================
+
+/// Разбирает содержимое mirrorlist.
+/// Страна берётся из ближайшего предыдущего комментария вида `## Germany`.
+pub fn parse_mirrorlist(content: &str) -> Vec<Mirror> {
+    let mut mirrors = Vec::new();
+    let mut country: Option<String> = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        // Закомментированный сервер: "#Server = ..."
+        let (enabled, body) = match line.strip_prefix('#') {
+            Some(rest) if rest.trim_start().starts_with("Server") => (false, rest.trim_start()),
+            Some(rest) => {
+                // Заголовок страны: "## Germany"
+                let name = rest.trim_start_matches('#').trim();
+                if !name.is_empty() && !name.contains(':') && !name.contains('=') {
+                    country = Some(name.to_string());
+                }
+                continue;
+            }
+            None => (true, line),
+        };
+
+        let Some(url) = body
+            .strip_prefix("Server")
+            .and_then(|r| r.trim_start().strip_prefix('='))
+            .map(str::trim)
+        else {
+            continue;
+        };
+
+        let protocol = url.split("://").next().unwrap_or_default().to_lowercase();
+
+        mirrors.push(Mirror {
+            url: url.to_string(),
+            country: country.clone(),
+            protocol,
+            enabled,
+            latency: None,
+        });
+    }
+
+    mirrors
+}
+
+/// Отбирает зеркала по фильтру, замеряет задержку и сортирует по ней
+pub fn rank_mirrors(
+    mirrors: &[Mirror],
+    filter: &MirrorFilter,
+    probe: &dyn LatencyProbe,
+) -> Vec<Mirror> {
+    let mut candidates: Vec<Mirror> = mirrors
+        .iter()
+        .filter(|m| filter.accepts(m))
+        .cloned()
+        .collect();
+
+    // Замеры параллельно, пачками, чтобы не открывать сотни потоков
+    for batch in candidates.chunks_mut(MIRROR_PROBE_BATCH) {
+        std::thread::scope(|s| {
+            for mirror in batch.iter_mut() {
+                s.spawn(move || mirror.latency = probe.measure(&mirror.url));
+            }
+        });
+    }
+
+    // Недоступные зеркала отбрасываем, при равной задержке https выше
+    candidates.retain(|m| m.latency.is_some());
+    candidates.sort_by(|a, b| {
+        a.latency
+            .cmp(&b.latency)
+            .then_with(|| (b.protocol == "https").cmp(&(a.protocol == "https")))
+    });
+
+    if filter.count > 0 {
+        candidates.truncate(filter.count);
+    }
+    candidates
+}
+
+/// Формирует текст нового mirrorlist
+pub fn render_mirrorlist(mirrors: &[Mirror]) -> String {
+    let mut output = trf(
+        "##\n## Arch Linux repository mirrorlist\n## Ранжировано Альфонсом: {}\n##\n",
+        &[&Local::now().format("%Y-%m-%d %H:%M")],
+    );
+
+    let mut last_country: Option<&str> = None;
+    for mirror in mirrors {
+        let country = mirror.country.as_deref();
+        if country != last_country {
+            output.push_str(&format!("\n## {}\n", country.unwrap_or("Worldwide")));
+            last_country = country;
+        }
+        output.push_str(&format!("Server = {}\n", mirror.url));
+    }
+
+    output
+}
+
+// ============================================================================
+// Сравнение
+// ============================================================================
+
+/// Построчное сравнение через наибольшую общую подпоследовательность
+pub fn diff_lines(old: &str, new: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] — длина НОП для a[i..] и b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut result = Vec::new();
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            result.push(DiffLine::Same(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(DiffLine::Removed(a[i].to_string()));
+            i += 1;
+        } else {
+            result.push(DiffLine::Added(b[j].to_string()));
+            j += 1;
+        }
+    }
+    result.extend(a[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    result.extend(b[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    result
+}
+
+/// Форматирует diff для чата: только изменённые строки, не более `max_lines`
+pub fn format_diff(diff: &[DiffLine], max_lines: usize) -> String {
+    let added = diff
+        .iter()
+        .filter(|l| matches!(l, DiffLine::Added(_)))
+        .count();
+    let removed = diff
+        .iter()
+        .filter(|l| matches!(l, DiffLine::Removed(_)))
+        .count();
+
+    let mut output = trf("Изменения: +{} / -{} строк\n\n", &[&added, &removed]);
+    let changed: Vec<String> = diff
+        .iter()
+        .filter_map(|l| match l {
+            DiffLine::Added(s) => Some(format!("+ {}", s)),
+            DiffLine::Removed(s) => Some(format!("- {}", s)),
+            DiffLine::Same(_) => None,
+        })
+        .collect();
+
+    for line in changed.iter().take(max_lines) {
+        output.push_str(line);
+        output.push('\n');
+    }
+    if changed.len() > max_lines {
+        output.push_str(&trf(
+            "... и ещё {} строк\n",
+            &[&(changed.len() - max_lines)],
+        ));
+    }
+    output
+}
+
+// ============================================================================
+// Команды
+// ============================================================================
+
+/// Команды зеркал
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Mirrors,
+        &["зеркала", "mirrors"],
+        "текущий список",
+        |_, _| mirrors_summary(),
+    ),
+    CommandSpec::new(
+        Group::Mirrors,
+        &["зеркала ранжировать", "ранжировать зеркала", "rank mirrors"],
+        "найти самые быстрые зеркала",
+        |ctx, countries| {
+            let mut filter = MirrorFilter::from_config(ctx.config);
+            let countries: Vec<String> = countries.split_whitespace().map(String::from).collect();
+            if !countries.is_empty() {
+                filter.countries = countries;
+            }
+            ctx.tasks.execute(BackgroundTask::RankMirrors(filter));
+            tr("Замеряю задержку зеркал... Это может занять до минуты.").into()
+        },
+    )
+    .arg(Arg::Optional("страна ...")),
+    CommandSpec::new(
+        Group::Mirrors,
+        &["зеркала применить", "применить зеркала", "apply mirrors"],
+        "записать подготовленный список",
+        |ctx, _| match pending_plan() {
+            Some(plan) => {
+                show_apply_dialog(ctx.dialog, plan);
+                tr("Подтвердите запись списка зеркал...").into()
+            }
+            None => tr("Сначала выполните: зеркала ранжировать").into(),
+        },
+    )
+    .risk(Risk::Confirm),
+];
+
+/// Краткая сводка о текущем списке зеркал
+fn mirrors_summary() -> String {
+    let content = match std::fs::read_to_string(MIRRORLIST_PATH) {
+        Ok(c) => c,
+        Err(e) => return trf("[X] Не удалось прочитать {}: {}", &[&MIRRORLIST_PATH, &e]),
+    };
+
+    let mirrors = parse_mirrorlist(&content);
+    let enabled: Vec<&Mirror> = mirrors.iter().filter(|m| m.enabled).collect();
+
+    let mut output = trf(
+        "Зеркала ({}): активно {} из {}\n\n",
+        &[&MIRRORLIST_PATH, &enabled.len(), &mirrors.len()],
+    );
+    for mirror in enabled.iter().take(10) {
+        output.push_str(&format!(
+            "• {} [{}]\n",
+            mirror.url,
+            mirror.country.as_deref().unwrap_or("?")
+        ));
+    }
+    output.push_str(tr("\nРанжировать: зеркала ранжировать [страна ...]"));
+    output
+}
+
+/// Показывает подтверждение записи подготовленного списка
+pub fn show_apply_dialog(dialog: &mut DialogState, plan: MirrorPlan) {
+    dialog.show_confirm_task(
+        "Обновление зеркал",
+        &trf(
+            "Записать {} зеркал в {}?\nСтарый файл будет сохранён как резервная копия.",
+            &[&plan.mirrors.len(), &MIRRORLIST_PATH],
+        ),
+        BackgroundTask::WriteMirrorlist(plan.content),
+    );
+}
+
+/// Возвращает подготовленный план (если есть)
+pub fn pending_plan() -> Option<MirrorPlan> {
+    PENDING_PLAN.lock().ok().and_then(|p| p.clone())
+}
+
+// ============================================================================
+// Функции выполнения (вызываются из фонового потока)
+// ============================================================================
+
+/// Ранжирует зеркала и сохраняет план для последующей записи
+pub fn rank_and_preview(filter: &MirrorFilter) -> String {
+    let current = match std::fs::read_to_string(MIRRORLIST_PATH) {
+        Ok(c) => c,
+        Err(e) => return trf("[X] Не удалось прочитать {}: {}", &[&MIRRORLIST_PATH, &e]),
+    };
+
+    let ranked = rank_mirrors(&parse_mirrorlist(&current), filter, &TcpProbe::default());
+    if ranked.is_empty() {
+        return tr("[X] Ни одно зеркало не ответило. Проверьте фильтр стран и сеть.").into();
+    }
+
+    let content = render_mirrorlist(&ranked);
+    let diff = diff_lines(&current, &content);
+
+    let mut output = String::from(tr("[OK] Самые быстрые зеркала:\n\n"));
+    for mirror in ranked.iter().take(5) {
+        output.push_str(&trf(
+            "• {} — {} мс\n",
+            &[
+                &mirror.url,
+                &mirror.latency.map(|l| l.as_millis()).unwrap_or_default(),
+            ],
+        ));
+    }
+    output.push('\n');
+    output.push_str(&format_diff(&diff, 30));
+    output.push_str(tr("\nЗаписать: зеркала применить"));
+
+    if let Ok(mut plan) = PENDING_PLAN.lock() {
+        *plan = Some(MirrorPlan {
+            mirrors: ranked,
+            content,
+            diff,
+        });
+    }
+
+    output
+}
+
+/// Записывает новый mirrorlist с резервной копией
+pub fn write_mirrorlist(content: &str) -> String {
+    match privileged::write_file_with_backup(MIRRORLIST_PATH, content) {
+        Ok(backup) => {
+            if let Ok(mut plan) = PENDING_PLAN.lock() {
+                *plan = None;
+            }
+            trf(
+                "[OK] Список зеркал обновлён. Резервная копия: {}\nОбновите базы: yay -Syy",
+                &[&backup],
+            )
+        }
+        Err(e) => trf("[X] Не удалось записать зеркала: {}", &[&e]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// Задержки по URL; отсутствующие зеркала недоступны
+    struct FakeProbe(HashMap<&'static str, u64>);
+
+    impl LatencyProbe for FakeProbe {
+        fn measure(&self, url: &str) -> Option<Duration> {
+            self.0.get(url).map(|ms| Duration::from_millis(*ms))
+        }
+    }
+
+    fn mirror(url: &str) -> Mirror {
+        Mirror {
+            url: url.to_string(),
+            country: None,
+            protocol: url.split("://").next().unwrap().to_string(),
+            enabled: true,
+            latency: None,
+        }
+    }
+
+    fn urls(mirrors: &[Mirror]) -> Vec<&str> {
+        mirrors.iter().map(|m| m.url.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_mirrorlist_with_countries_and_disabled_servers() {
+        let content = "\
+##
+## Arch Linux repository mirrorlist
+## Generated on 2024-01-01
+##
+
+## Germany
+Server = https://de.example.org/$repo/os/$arch
+#Server = http://de2.example.org/$repo/os/$arch
+
+## Sweden
+# Server = https://se.example.org/$repo/os/$arch
+";
+        let mirrors = parse_mirrorlist(content);
+        assert_eq!(mirrors.len(), 3);
+
+        assert_eq!(mirrors[0].url, "https://de.example.org/$repo/os/$arch");
+        assert_eq!(mirrors[0].country.as_deref(), Some("Germany"));
+        assert_eq!(mirrors[0].protocol, "https");
+        assert!(mirrors[0].enabled);
+
+        assert_eq!(mirrors[1].protocol, "http");
+        assert!(!mirrors[1].enabled);
+
+        assert_eq!(mirrors[2].country.as_deref(), Some("Sweden"));
+        assert!(!mirrors[2].enabled);
+    }
+
+    #[test]
+    fn ranks_by_latency_and_drops_unreachable() {
+        let mirrors = [
+            mirror("https://slow.example/"),
+            mirror("https://fast.example/"),
+            mirror("https://dead.example/"),
+        ];
+        let probe = FakeProbe(HashMap::from([
+            ("https://slow.example/", 200),
+            ("https://fast.example/", 20),
+        ]));
+
+        let ranked = rank_mirrors(&mirrors, &MirrorFilter::default(), &probe);
+        assert_eq!(
+            urls(&ranked),
+            ["https://fast.example/", "https://slow.example/"]
+        );
+        assert_eq!(ranked[0].latency, Some(Duration::from_millis(20)));
+    }
+
+    #[test]
+    fn prefers_https_on_equal_latency() {
+        let mirrors = [mirror("http://a.example/"), mirror("https://b.example/")];
+        let probe = FakeProbe(HashMap::from([
+            ("http://a.example/", 50),
+            ("https://b.example/", 50),
+        ]));
+
+        let ranked = rank_mirrors(&mirrors, &MirrorFilter::default(), &probe);
+        assert_eq!(urls(&ranked), ["https://b.example/", "http://a.example/"]);
+    }
+
+    #[test]
+    fn applies_filter_and_count_limit() {
+        let mut de = mirror("https://de.example/");
+        de.country = Some("Germany".into());
+        let mut se = mirror("https://se.example/");
+        se.country = Some("Sweden".into());
+        let mut de_http = mirror("http://de-http.example/");
+        de_http.country = Some("Germany".into());
+        let mut de2 = mirror("https://de2.example/");
+        de2.country = Some("Germany".into());
+
+        let probe = FakeProbe(HashMap::from([
+            ("https://de.example/", 30),
+            ("https://se.example/", 10),
+            ("http://de-http.example/", 5),
+            ("https://de2.example/", 20),
+        ]));
+        let filter = MirrorFilter {
+            countries: vec!["germany".into()],
+            https_only: true,
+            count: 1,
+        };
+
+        let ranked = rank_mirrors(&[de, se, de_http, de2], &filter, &probe);
+        assert_eq!(urls(&ranked), ["https://de2.example/"]);
+    }
+
+    #[test]
+    fn diff_marks_added_and_removed_lines() {
+        let diff = diff_lines("a\nb\nc", "a\nc\nd");
+        assert_eq!(
+            diff,
+            [
+                DiffLine::Same("a".into()),
+                DiffLine::Removed("b".into()),
+                DiffLine::Same("c".into()),
+                DiffLine::Added("d".into()),
+            ]
+        );
+        assert!(diff_lines("x\ny", "x\ny")
+            .iter()
+            .all(|l| matches!(l, DiffLine::Same(_))));
+    }
+
+    #[test]
+    fn extracts_host_and_port() {
+        assert_eq!(
+            host_and_port("https://mirror.example/arch/$repo"),
+            Some(("mirror.example".into(), 443))
+        );
+        assert_eq!(
+            host_and_port("http://mirror.example:8080/arch"),
+            Some(("mirror.example".into(), 8080))
+        );
+        assert_eq!(
+            host_and_port("https://[::1]:8443/arch"),
+            Some(("::1".into(), 8443))
+        );
+        assert_eq!(
+            host_and_port("http://[2001:db8::1]/arch"),
+            Some(("2001:db8::1".into(), 80))
+        );
+        assert_eq!(host_and_port("mirror.example/arch"), None);
+    }
+}
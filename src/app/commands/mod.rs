@@ -1,49 +1,68 @@
 //! Обработка команд пользователя
 
+pub mod audio;
+pub mod aur;
 pub mod base;
+pub mod gpu;
 pub mod guide;
+pub mod journal;
+pub mod kernels;
+pub mod matcher;
+pub mod mirrors;
+pub mod network;
 pub mod package;
+pub mod processes;
+pub mod registry;
+pub mod security;
+pub mod services;
+pub mod snapshots;
 pub mod system;
 
 use super::chat::{DialogState, TaskManager};
 use super::command_log;
+use super::config::Config;
 use super::guides::GuideRegistry;
+use matcher::Fuzzy;
+use registry::{CommandContext, Group};
 
 /// Обрабатывает команду и возвращает ответ
 ///
 /// Возвращает `Some(response)` если команда распознана, `None` если нет
 pub fn process_command(
     input: &str,
-    assistant_name: &str,
+    config: &Config,
     dialog: &mut DialogState,
     tasks: &TaskManager,
     guides: &GuideRegistry,
 ) -> Option<String> {
     let cmd = input.trim().to_lowercase();
+    // Сначала дословные фразы, затем формы слов, порядок и опечатки
+    let (spec, arg) = match registry::parse(&cmd) {
+        Some(found) => found,
+        None => match matcher::find(&cmd)? {
+            Fuzzy::Command(spec, arg) => (spec, arg),
+            Fuzzy::DidYouMean(phrase) => {
+                let response = matcher::did_you_mean(&phrase);
+                command_log::log_command(&cmd, &response);
+                return Some(response);
+            }
+        },
+    };
 
-    // 1. Базовые команды (время, дата, помощь)
-    if let Some(r) = base::process_basic_command(&cmd, assistant_name) {
-        command_log::log_command(&cmd, &r);
-        return Some(r);
-    }
+    let mut ctx = CommandContext {
+        config,
+        dialog,
+        tasks,
+        guides,
+    };
+    let response = registry::execute(spec, arg, &mut ctx);
 
-    // 2. Системные команды (выключение, перезагрузка) - с диалогом подтверждения
-    if let Some(r) = system::process_system_command(&cmd, dialog) {
-        command_log::log_command(&cmd, &r);
-        return Some(r);
-    }
-
-    // 3. Пакетный менеджер
-    if let Some(r) = package::process_package_command(&cmd, dialog, tasks) {
-        command_log::log_command(&cmd, &r);
-        return Some(r);
-    }
-
-    // 4. Гайды
-    if let Some(r) = guide::process_guide_command(&cmd, guides) {
-        command_log::log_command(&cmd, "гайд показан");
-        return Some(r);
-    }
-
-    None
+    // Текст гайдов длинный — в журнал пишем только факт
+    let logged = if spec.group == Group::Guides {
+        "гайд показан"
+    } else {
+        &response
+    };
+    command_log::log_command(&cmd, logged);
+    Some(response)
 }
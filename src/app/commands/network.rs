@@ -0,0 +1,835 @@
+//! Сеть и WiFi: NetworkManager (nmcli) с запасным вариантом через iwd (iwctl)
+
+use super::base::CMD_OPEN_PANEL_PREFIX;
+use super::registry::{Arg, CommandSpec, Group, Values};
+use crate::app::chat::BackgroundTask;
+use crate::app::exec::{has_program, run, run_with_input};
+use crate::app::i18n::{tr, trf};
+use std::sync::{Arc, Mutex};
+
+/// Состояние панели сети (обновляется в фоне)
+pub type SharedNetwork = Arc<Mutex<NetworkView>>;
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Беспроводная сеть из результатов сканирования
+#[derive(Debug, Clone, PartialEq)]
+pub struct WifiNetwork {
+    pub ssid: String,
+    /// Уровень сигнала 0..=100
+    pub signal: u8,
+    /// Тип защиты ("WPA2", "psk", пусто — открытая сеть)
+    pub security: String,
+    pub in_use: bool,
+}
+
+impl WifiNetwork {
+    pub fn is_open(&self) -> bool {
+        let s = self.security.trim();
+        s.is_empty() || s == "--" || s == "open"
+    }
+}
+
+/// Текущее подключение
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkStatus {
+    pub device: String,
+    pub connection: Option<String>,
+    pub addresses: Vec<String>,
+    pub gateway: Option<String>,
+    pub dns: Vec<String>,
+    /// Все радиомодули выключены
+    pub airplane: bool,
+}
+
+/// Данные для панели
+#[derive(Debug, Default)]
+pub struct NetworkView {
+    /// Название используемого бэкенда
+    pub backend: &'static str,
+    pub status: Option<Result<NetworkStatus, String>>,
+    pub networks: Vec<WifiNetwork>,
+    pub busy: bool,
+    /// Результат последнего действия
+    pub message: Option<String>,
+}
+
+// ============================================================================
+// Бэкенды
+// ============================================================================
+
+/// Операции с сетью (реализуется для nmcli, iwctl и заглушки в тестах)
+pub trait NetworkBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn status(&self) -> Result<NetworkStatus, String>;
+    fn scan(&self) -> Result<Vec<WifiNetwork>, String>;
+    fn connect(&self, ssid: &str, password: Option<&str>) -> Result<String, String>;
+    fn disconnect(&self) -> Result<String, String>;
+    fn set_airplane(&self, enabled: bool) -> Result<String, String>;
+}
+
+/// Выбирает доступный бэкенд: NetworkManager, если он запущен, иначе iwd
+pub fn detect_backend() -> Option<Box<dyn NetworkBackend>> {
+    if has_program("nmcli") && run("nmcli", &["-t", "general", "status"]).is_ok() {
+        return Some(Box::new(NmcliBackend));
+    }
+    if has_program("iwctl") {
+        return wireless_device().map(|device| Box::new(IwctlBackend { device }) as _);
+    }
+    None
+}
+
+/// Выполняет `f` с найденным бэкендом; без бэкенда — сообщение об этом
+pub fn with_backend(f: impl FnOnce(&dyn NetworkBackend) -> String) -> String {
+    match detect_backend() {
+        Some(backend) => f(backend.as_ref()),
+        None => tr(NO_BACKEND).into(),
+    }
+}
+
+/// Сеть по имени: точное совпадение, иначе без учёта регистра
+/// (команды чата приходят в нижнем регистре, а SSID к нему чувствителен)
+pub fn find_network<'a>(networks: &'a [WifiNetwork], ssid: &str) -> Option<&'a WifiNetwork> {
+    let lower = ssid.to_lowercase();
+    networks
+        .iter()
+        .find(|n| n.ssid == ssid)
+        .or_else(|| networks.iter().find(|n| n.ssid.to_lowercase() == lower))
+}
+
+/// Первый беспроводной интерфейс из /sys/class/net
+fn wireless_device() -> Option<String> {
+    std::fs::read_dir("/sys/class/net")
+        .ok()?
+        .flatten()
+        .find(|e| e.path().join("wireless").exists())
+        .map(|e| e.file_name().to_string_lossy().into_owned())
+}
+
+/// DNS-серверы из /etc/resolv.conf
+fn resolv_dns() -> Vec<String> {
+    std::fs::read_to_string("/etc/resolv.conf")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|l| l.strip_prefix("nameserver"))
+        .map(|s| s.trim().to_string())
+        .collect()
+}
+
+// ----------------------------------------------------------------------------
+// NetworkManager
+// ----------------------------------------------------------------------------
+
+pub struct NmcliBackend;
+
+/// Делит строку вывода `nmcli -t` по ":" с учётом экранирования "\:"
+pub fn split_terse(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(next) = chars.next() {
+                    fields.last_mut().unwrap().push(next);
+                }
+            }
+            ':' => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
+/// Разбирает `nmcli -t -f IN-USE,SSID,SIGNAL,SECURITY device wifi list`
+pub fn parse_nmcli_wifi(output: &str) -> Vec<WifiNetwork> {
+    let mut networks: Vec<WifiNetwork> = Vec::new();
+
+    for line in output.lines() {
+        let f = split_terse(line);
+        if f.len() < 4 || f[1].is_empty() {
+            continue; // скрытые сети
+        }
+        let network = WifiNetwork {
+            in_use: f[0].trim() == "*",
+            ssid: f[1].clone(),
+            signal: f[2].trim().parse().unwrap_or(0),
+            security: f[3].trim().to_string(),
+        };
+
+        // Одна сеть может быть видна через несколько точек доступа
+        match networks.iter_mut().find(|n| n.ssid == network.ssid) {
+            Some(existing) => {
+                existing.in_use |= network.in_use;
+                existing.signal = existing.signal.max(network.signal);
+            }
+            None => networks.push(network),
+        }
+    }
+
+    networks.sort_by(|a, b| b.in_use.cmp(&a.in_use).then(b.signal.cmp(&a.signal)));
+    networks
+}
+
+/// Разбирает `nmcli -t -f IP4.ADDRESS,IP4.GATEWAY,IP4.DNS device show <dev>`
+pub fn parse_nmcli_device(output: &str, status: &mut NetworkStatus) {
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if value.is_empty() || value == "--" {
+            continue;
+        }
+        if key.starts_with("IP4.ADDRESS") {
+            status.addresses.push(value.to_string());
+        } else if key.starts_with("IP4.DNS") {
+            status.dns.push(value.to_string());
+        } else if key == "IP4.GATEWAY" {
+            status.gateway = Some(value.to_string());
+        }
+    }
+}
+
+impl NetworkBackend for NmcliBackend {
+    fn name(&self) -> &'static str {
+        "NetworkManager"
+    }
+
+    fn status(&self) -> Result<NetworkStatus, String> {
+        let mut status = NetworkStatus::default();
+
+        // Первое подключённое устройство (кроме loopback)
+        let devices = run(
+            "nmcli",
+            &[
+                "-t",
+                "-f",
+                "DEVICE,TYPE,STATE,CONNECTION",
+                "device",
+                "status",
+            ],
+        )?;
+        let mut wifi_device = None;
+        for line in devices.lines() {
+            let f = split_terse(line);
+            if f.len() < 4 || f[1] == "loopback" {
+                continue;
+            }
+            if f[1] == "wifi" && wifi_device.is_none() {
+                wifi_device = Some(f[0].clone());
+            }
+            if f[2] == "connected" && status.connection.is_none() {
+                status.device = f[0].clone();
+                status.connection = Some(f[3].clone());
+            }
+        }
+        if status.device.is_empty() {
+            status.device = wifi_device.unwrap_or_default();
+        }
+
+        if status.connection.is_some() {
+            let details = run(
+                "nmcli",
+                &[
+                    "-t",
+                    "-f",
+                    "IP4.ADDRESS,IP4.GATEWAY,IP4.DNS",
+                    "device",
+                    "show",
+                    &status.device,
+                ],
+            )?;
+            parse_nmcli_device(&details, &mut status);
+        }
+
+        // Режим полёта: выключены и WiFi, и WWAN
+        let radio = run("nmcli", &["-t", "-f", "WIFI,WWAN", "radio"])?;
+        status.airplane = radio
+            .trim()
+            .split(':')
+            .all(|r| r.trim() == "disabled" || r.trim() == "missing");
+
+        Ok(status)
+    }
+
+    fn scan(&self) -> Result<Vec<WifiNetwork>, String> {
+        let output = run(
+            "nmcli",
+            &[
+                "-t",
+                "-f",
+                "IN-USE,SSID,SIGNAL,SECURITY",
+                "device",
+                "wifi",
+                "list",
+                "--rescan",
+                "yes",
+            ],
+        )?;
+        Ok(parse_nmcli_wifi(&output))
+    }
+
+    fn connect(&self, ssid: &str, password: Option<&str>) -> Result<String, String> {
+        // Пароль nmcli запрашивает сам (--ask) и читает из stdin
+        match password {
+            Some(password) => run_with_input(
+                "nmcli",
+                &["--ask", "device", "wifi", "connect", ssid],
+                &format!("{}\n", password),
+            ),
+            None => run("nmcli", &["device", "wifi", "connect", ssid]),
+        }
+        .map(|_| trf("[OK] Подключено к {}", &[&ssid]))
+    }
+
+    fn disconnect(&self) -> Result<String, String> {
+        let status = self.status()?;
+        if status.connection.is_none() {
+            return Err(tr("Нет активного подключения").into());
+        }
+        run("nmcli", &["device", "disconnect", &status.device])
+            .map(|_| trf("[OK] {} отключено", &[&status.device]))
+    }
+
+    fn set_airplane(&self, enabled: bool) -> Result<String, String> {
+        let state = if enabled { "off" } else { "on" };
+        run("nmcli", &["radio", "all", state]).map(|_| airplane_message(enabled))
+    }
+}
+
+// ----------------------------------------------------------------------------
+// iwd
+// ----------------------------------------------------------------------------
+
+pub struct IwctlBackend {
+    pub device: String,
+}
+
+/// Убирает ANSI-последовательности из вывода iwctl
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            // ESC [ ... буква
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Разбирает таблицу `iwctl station <dev> get-networks`
+pub fn parse_iwctl_networks(output: &str) -> Vec<WifiNetwork> {
+    let mut networks = Vec::new();
+
+    for line in strip_ansi(output).lines() {
+        let in_use = line.trim_start().starts_with('>');
+        let line = line.trim().trim_start_matches('>').trim();
+        // Колонки разделены двумя и более пробелами
+        let cols: Vec<&str> = line
+            .split("  ")
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .collect();
+        if cols.len() < 3 || !cols[2].chars().all(|c| c == '*') {
+            continue; // заголовки и разделители
+        }
+        networks.push(WifiNetwork {
+            ssid: cols[0].to_string(),
+            security: cols[1].to_string(),
+            signal: (cols[2].len() * 25).min(100) as u8,
+            in_use,
+        });
+    }
+
+    networks
+}
+
+/// Разбирает `iwctl station <dev> show` в пары "свойство → значение"
+fn parse_iwctl_show(output: &str) -> Vec<(String, String)> {
+    strip_ansi(output)
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let (key, value) = line.split_once("  ")?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+impl NetworkBackend for IwctlBackend {
+    fn name(&self) -> &'static str {
+        "iwd"
+    }
+
+    fn status(&self) -> Result<NetworkStatus, String> {
+        let show = run("iwctl", &["station", &self.device, "show"])?;
+        let props = parse_iwctl_show(&show);
+        let get = |key: &str| props.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+
+        let addresses = run("ip", &["-4", "-o", "addr", "show", "dev", &self.device])
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|l| {
+                let mut parts = l.split_whitespace();
+                parts.find(|p| *p == "inet")?;
+                parts.next().map(str::to_string)
+            })
+            .collect();
+
+        let rfkill = run("rfkill", &["list", "wlan"]).unwrap_or_default();
+
+        Ok(NetworkStatus {
+            device: self.device.clone(),
+            connection: get("Connected network"),
+            addresses,
+            gateway: None,
+            dns: resolv_dns(),
+            airplane: rfkill.contains("Soft blocked: yes"),
+        })
+    }
+
+    fn scan(&self) -> Result<Vec<WifiNetwork>, String> {
+        run("iwctl", &["station", &self.device, "scan"])?;
+        // Результаты появляются не сразу
+        std::thread::sleep(std::time::Duration::from_secs(2));
+        let output = run("iwctl", &["station", &self.device, "get-networks"])?;
+        Ok(parse_iwctl_networks(&output))
+    }
+
+    fn connect(&self, ssid: &str, password: Option<&str>) -> Result<String, String> {
+        let args = ["station", &self.device, "connect", ssid];
+        // Пароль — ответом на запрос агента iwctl, а не через --passphrase
+        match password {
+            Some(password) => run_with_input("iwctl", &args, &format!("{}\n", password)),
+            None => run("iwctl", &args),
+        }
+        .map(|_| trf("[OK] Подключено к {}", &[&ssid]))
+    }
+
+    fn disconnect(&self) -> Result<String, String> {
+        run("iwctl", &["station", &self.device, "disconnect"])
+            .map(|_| trf("[OK] {} отключено", &[&self.device]))
+    }
+
+    fn set_airplane(&self, enabled: bool) -> Result<String, String> {
+        let action = if enabled { "block" } else { "unblock" };
+        run("rfkill", &[action, "all"]).map(|_| airplane_message(enabled))
+    }
+}
+
+fn airplane_message(enabled: bool) -> String {
+    if enabled {
+        tr("[OK] Режим полёта включён").into()
+    } else {
+        tr("[OK] Режим полёта выключен").into()
+    }
+}
+
+// ============================================================================
+// Текстовые отчёты
+// ============================================================================
+
+/// Текущее подключение одной строкой на параметр
+pub fn format_status(status: &NetworkStatus) -> String {
+    let mut lines = Vec::new();
+    match &status.connection {
+        Some(c) => lines.push(trf("Подключение: {} ({})", &[&c, &status.device])),
+        None => lines.push(tr("Нет активного подключения").into()),
+    }
+    if !status.addresses.is_empty() {
+        lines.push(format!("IP: {}", status.addresses.join(", ")));
+    }
+    if let Some(gw) = &status.gateway {
+        lines.push(trf("Шлюз: {}", &[&gw]));
+    }
+    if !status.dns.is_empty() {
+        lines.push(format!("DNS: {}", status.dns.join(", ")));
+    }
+    if status.airplane {
+        lines.push(tr("Режим полёта включён").into());
+    }
+    lines.join("\n")
+}
+
+/// Список сетей для чата
+pub fn format_networks(networks: &[WifiNetwork]) -> String {
+    if networks.is_empty() {
+        return tr("Сети не найдены").into();
+    }
+    networks
+        .iter()
+        .map(|n| {
+            format!(
+                "{} {:<32} {:>3}%  {}",
+                if n.in_use { "*" } else { " " },
+                n.ssid,
+                n.signal,
+                if n.is_open() {
+                    tr("открытая")
+                } else {
+                    &n.security
+                }
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ============================================================================
+// Фоновые действия для панели
+// ============================================================================
+
+/// Выполняет действие бэкенда в фоне и обновляет состояние панели
+fn spawn_action<F>(shared: &SharedNetwork, action: F)
+where
+    F: FnOnce(&dyn NetworkBackend) -> Result<String, String> + Send + 'static,
+{
+    if let Ok(mut v) = shared.lock() {
+        v.busy = true;
+    }
+
+    let shared = shared.clone();
+    std::thread::spawn(move || {
+        let Some(backend) = detect_backend() else {
+            if let Ok(mut v) = shared.lock() {
+                v.busy = false;
+                v.status = Some(Err(tr(NO_BACKEND).into()));
+            }
+            return;
+        };
+
+        let message = action(backend.as_ref()).unwrap_or_else(|e| format!("[X] {}", e));
+        let status = backend.status();
+        if let Ok(mut v) = shared.lock() {
+            v.busy = false;
+            v.backend = backend.name();
+            if !message.is_empty() {
+                v.message = Some(message);
+            }
+            v.status = Some(status);
+        }
+    });
+}
+
+/// Обновляет статус и список сетей
+pub fn refresh(shared: &SharedNetwork) {
+    let networks = shared.clone();
+    spawn_action(shared, move |backend| {
+        let found = backend.scan()?;
+        if let Ok(mut v) = networks.lock() {
+            v.networks = found;
+        }
+        Ok(String::new())
+    });
+}
+
+pub fn connect(shared: &SharedNetwork, ssid: String, password: Option<String>) {
+    let networks = shared.clone();
+    spawn_action(shared, move |backend| {
+        let result = connect_to(backend, &ssid, password.as_deref());
+        if let (Ok(found), Ok(mut v)) = (backend.scan(), networks.lock()) {
+            v.networks = found;
+        }
+        result
+    });
+}
+
+pub fn disconnect(shared: &SharedNetwork) {
+    spawn_action(shared, |backend| backend.disconnect());
+}
+
+pub fn set_airplane(shared: &SharedNetwork, enabled: bool) {
+    spawn_action(shared, move |backend| backend.set_airplane(enabled));
+}
+
+const NO_BACKEND: &str = "Не найден NetworkManager (nmcli) или iwd (iwctl)";
+
+// ============================================================================
+// Команды
+// ============================================================================
+
+/// Сетевые команды
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Network,
+        &["wifi", "вайфай", "сеть"],
+        "панель сетей",
+        |_, _| format!("{}network", CMD_OPEN_PANEL_PREFIX),
+    ),
+    CommandSpec::new(
+        Group::Network,
+        &[
+            "wifi список",
+            "wifi сканировать",
+            "wifi scan",
+            "вайфай список",
+            "сеть список",
+        ],
+        "найти сети",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::WifiScan);
+            tr("Ищу сети...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Network,
+        &[
+            "wifi статус",
+            "wifi status",
+            "вайфай статус",
+            "сеть статус",
+            "мой ip",
+            "ip",
+            "dns",
+        ],
+        "подключение, IP и DNS",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::NetworkStatus);
+            tr("Проверяю подключение...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Network,
+        &[
+            "wifi отключить",
+            "wifi отключиться",
+            "wifi disconnect",
+            "вайфай отключить",
+            "сеть отключить",
+        ],
+        "отключиться от сети",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::WifiDisconnect);
+            tr("Отключаюсь...").into()
+        },
+    ),
+    // Пароль вводится в панели, а не в чате
+    CommandSpec::new(
+        Group::Network,
+        &[
+            "wifi подключить",
+            "wifi подключиться к",
+            "wifi connect",
+            "вайфай подключить",
+            "сеть подключить",
+        ],
+        "подключиться (пароль вводится в панели)",
+        |_, ssid| format!("{}network:{}", CMD_OPEN_PANEL_PREFIX, ssid),
+    )
+    .arg(Arg::Required("сеть")),
+    CommandSpec::new(
+        Group::Network,
+        &["режим полёта", "режим полета", "авиарежим", "airplane mode"],
+        "все радиомодули",
+        |ctx, state| {
+            let enabled = match state {
+                "вкл" | "включить" | "on" => true,
+                "выкл" | "выключить" | "off" => false,
+                _ => return tr("Укажите: режим полёта вкл / выкл").into(),
+            };
+            ctx.tasks.execute(BackgroundTask::AirplaneMode(enabled));
+            tr("Переключаю радиомодули...").into()
+        },
+    )
+    .arg(Arg::Required("вкл|выкл"))
+    .values(Values::List(&["вкл", "выкл"])),
+];
+
+// ============================================================================
+// Функции выполнения (вызываются из фонового потока)
+// ============================================================================
+
+/// Подключение по имени сети. Имя сверяется с результатами сканирования,
+/// чтобы вернуть SSID его настоящий регистр
+pub fn connect_to(
+    backend: &dyn NetworkBackend,
+    ssid: &str,
+    password: Option<&str>,
+) -> Result<String, String> {
+    let networks = backend.scan().unwrap_or_default();
+    let ssid = find_network(&networks, ssid).map_or(ssid, |n| n.ssid.as_str());
+    backend.connect(ssid, password)
+}
+
+pub fn network_status(backend: &dyn NetworkBackend) -> String {
+    match backend.status() {
+        Ok(status) => format_status(&status),
+        Err(e) => format!("[X] {}", e),
+    }
+}
+
+pub fn wifi_scan(backend: &dyn NetworkBackend) -> String {
+    match backend.scan() {
+        Ok(networks) => format_networks(&networks),
+        Err(e) => format!("[X] {}", e),
+    }
+}
+
+pub fn wifi_disconnect(backend: &dyn NetworkBackend) -> String {
+    backend
+        .disconnect()
+        .unwrap_or_else(|e| format!("[X] {}", e))
+}
+
+pub fn airplane_mode(backend: &dyn NetworkBackend, enabled: bool) -> String {
+    backend
+        .set_airplane(enabled)
+        .unwrap_or_else(|e| format!("[X] {}", e))
+}
+
+/// Текущее подключение для инструмента ИИ
+pub fn status_summary() -> String {
+    with_backend(network_status)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Заглушка бэкенда: фиксированные сети и журнал подключений
+    #[derive(Default)]
+    struct MockBackend {
+        networks: Vec<WifiNetwork>,
+        status: NetworkStatus,
+        connected: Mutex<Vec<(String, Option<String>)>>,
+    }
+
+    impl NetworkBackend for MockBackend {
+        fn name(&self) -> &'static str {
+            "mock"
+        }
+
+        fn status(&self) -> Result<NetworkStatus, String> {
+            Ok(self.status.clone())
+        }
+
+        fn scan(&self) -> Result<Vec<WifiNetwork>, String> {
+            Ok(self.networks.clone())
+        }
+
+        fn connect(&self, ssid: &str, password: Option<&str>) -> Result<String, String> {
+            self.connected
+                .lock()
+                .unwrap()
+                .push((ssid.to_string(), password.map(String::from)));
+            Ok(format!("[OK] {}", ssid))
+        }
+
+        fn disconnect(&self) -> Result<String, String> {
+            Err("no connection".into())
+        }
+
+        fn set_airplane(&self, enabled: bool) -> Result<String, String> {
+            Ok(airplane_message(enabled))
+        }
+    }
+
+    fn network(ssid: &str, signal: u8) -> WifiNetwork {
+        WifiNetwork {
+            ssid: ssid.into(),
+            signal,
+            security: "WPA2".into(),
+            in_use: false,
+        }
+    }
+
+    #[test]
+    fn splits_terse_fields_with_escapes() {
+        assert_eq!(split_terse("a:b\\:c:"), ["a", "b:c", ""]);
+        assert_eq!(split_terse("C:\\\\path"), ["C", "\\path"]);
+    }
+
+    #[test]
+    fn parses_nmcli_wifi_list() {
+        let output = "\
+ :Home:70:WPA2
+*:Office\\:5G:55:WPA1 WPA2
+ :Home:80:WPA2
+ ::90:WPA2
+ :Cafe:40:
+";
+        let networks = parse_nmcli_wifi(output);
+        let names: Vec<&str> = networks.iter().map(|n| n.ssid.as_str()).collect();
+        assert_eq!(names, ["Office:5G", "Home", "Cafe"]);
+        assert!(networks[0].in_use);
+        assert_eq!(networks[1].signal, 80);
+        assert!(networks[2].is_open());
+    }
+
+    #[test]
+    fn parses_iwctl_networks() {
+        let output = "\
+                               Available networks
+--------------------------------------------------------------------------------
+      Network name                      Security            Signal
+--------------------------------------------------------------------------------
+  \u{1b}[1;90m> \u{1b}[0m    Home Net                          psk                 ****
+      Cafe                              open                **
+";
+        let networks = parse_iwctl_networks(output);
+        assert_eq!(networks.len(), 2);
+        assert_eq!(networks[0].ssid, "Home Net");
+        assert!(networks[0].in_use);
+        assert_eq!(networks[0].signal, 100);
+        assert_eq!(networks[1].ssid, "Cafe");
+        assert!(networks[1].is_open());
+        assert_eq!(networks[1].signal, 50);
+    }
+
+    #[test]
+    fn connect_restores_ssid_case_from_scan() {
+        let backend = MockBackend {
+            networks: vec![network("MyHome", 60), network("myhome-guest", 30)],
+            ..Default::default()
+        };
+        connect_to(&backend, "myhome", Some("secret")).unwrap();
+        connect_to(&backend, "Hidden", None).unwrap();
+
+        let connected = backend.connected.lock().unwrap();
+        assert_eq!(
+            *connected,
+            [
+                ("MyHome".to_string(), Some("secret".to_string())),
+                ("Hidden".to_string(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_network_prefers_exact_match() {
+        let networks = [network("home", 10), network("Home", 20)];
+        assert_eq!(find_network(&networks, "Home").unwrap().signal, 20);
+        assert_eq!(find_network(&networks, "HOME").unwrap().signal, 10);
+        assert!(find_network(&networks, "office").is_none());
+    }
+
+    #[test]
+    fn reports_through_backend() {
+        let backend = MockBackend {
+            networks: vec![network("Home", 70)],
+            status: NetworkStatus {
+                device: "wlan0".into(),
+                connection: Some("Home".into()),
+                addresses: vec!["192.168.1.5/24".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let status = network_status(&backend);
+        assert!(status.contains("Home (wlan0)"));
+        assert!(status.contains("IP: 192.168.1.5/24"));
+        assert!(wifi_scan(&backend).contains("Home"));
+        assert_eq!(wifi_disconnect(&backend), "[X] no connection");
+        assert!(airplane_mode(&backend, true).starts_with("[OK]"));
+    }
+}
@@ -1,75 +1,97 @@
 //! Команды управления пакетами (через yay)
 
-use crate::app::chat::{BackgroundTask, DialogState, TaskManager};
+use super::aur;
+use super::registry::{Arg, CommandSpec, Group, Risk, Values};
+use super::snapshots;
+use crate::app::chat::BackgroundTask;
 use crate::app::constants::{errors, messages, YAY_AUR_URL, YAY_INSTALL_DIR};
 use crate::app::desktop::DesktopEnvironment;
+use crate::app::exec::has_program;
+use crate::app::i18n::{tr, trf};
 use std::process::Command;
+use std::sync::Mutex;
 
-/// Обработка команд пакетного менеджера
-pub fn process_package_command(
-    cmd: &str,
-    dialog: &mut DialogState,
-    tasks: &TaskManager,
-) -> Option<String> {
-    // Открыть диалог поиска
-    if cmd == "поиск пакетов" || cmd == "найти пакеты" {
-        dialog.show_search();
-        return Some("Открываю поиск пакетов...".into());
-    }
-
-    // Установка: "установить <пакет>"
-    if let Some(package) = cmd.strip_prefix("установить ") {
-        let package = package.trim();
-        if package.is_empty() {
-            return Some("Укажите пакет. Пример: установить firefox".into());
-        }
-        dialog.show_confirm(
-            "Установка пакета",
-            &format!("Установить '{}' через yay?", package),
-            package,
-        );
-        return Some(format!("Подготовка к установке '{}'...", package));
-    }
-
-    // Удаление: "удалить <пакет>"
-    if let Some(package) = cmd.strip_prefix("удалить ") {
-        let package = package.trim();
-        if package.is_empty() {
-            return Some("Укажите пакет для удаления.".into());
-        }
-        dialog.show_confirm(
-            "Удаление пакета",
-            &format!("Удалить '{}' из системы?", package),
-            package,
-        );
-        return Some(format!("Подготовка к удалению '{}'...", package));
-    }
-
-    // Обновление системы
-    if cmd == "обновить систему"
-        || cmd == "обновить система"
-        || cmd == "обновление"
-        || cmd == "обновить"
-    {
-        dialog.show_confirm(
-            "Обновление системы",
-            "Выполнить полное обновление (yay -Syu)?",
-            "",
-        );
-        return Some("Подготовка к обновлению...".into());
-    }
+/// Число доступных обновлений по последней проверке (для статуса по IPC)
+static PENDING_UPDATES: Mutex<Option<usize>> = Mutex::new(None);
 
-    // Быстрый поиск: "поиск <запрос>"
-    if let Some(query) = cmd.strip_prefix("поиск ") {
-        let query = query.trim();
-        if !query.is_empty() {
-            tasks.execute(BackgroundTask::SearchPackages(query.into()));
-            return Some(format!("Ищу пакеты '{}'...", query));
-        }
-    }
-
-    None
-}
+/// Команды пакетного менеджера
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Packages,
+        &["поиск пакетов", "найти пакеты", "search packages"],
+        "окно поиска пакетов",
+        |ctx, _| {
+            ctx.dialog.show_search();
+            tr("Открываю поиск пакетов...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Packages,
+        &["поиск", "search"],
+        "найти пакеты",
+        |ctx, query| {
+            ctx.tasks
+                .execute(BackgroundTask::SearchPackages(query.into()));
+            trf("Ищу пакеты '{}'...", &[&query])
+        },
+    )
+    .arg(Arg::Required("запрос")),
+    CommandSpec::new(
+        Group::Packages,
+        &["установить", "install"],
+        "установить пакет",
+        |ctx, package| {
+            ctx.dialog.show_confirm(
+                "Установка пакета",
+                &trf("Установить '{}' через yay?", &[&package]),
+                package,
+            );
+            // Для пакетов AUR диалог покажет метаданные и PKGBUILD;
+            // несколько пакетов через пробел проверяются по отдельности
+            ctx.dialog.aur_reviews = package.split_whitespace().map(aur::start_review).collect();
+            trf("Подготовка к установке '{}'...", &[&package])
+        },
+    )
+    .arg(Arg::Required("пакет"))
+    .values(Values::Packages)
+    .risk(Risk::Confirm),
+    CommandSpec::new(
+        Group::Packages,
+        &["удалить", "remove"],
+        "удалить пакет",
+        |ctx, package| {
+            ctx.dialog.show_confirm(
+                "Удаление пакета",
+                &trf("Удалить '{}' из системы?", &[&package]),
+                package,
+            );
+            trf("Подготовка к удалению '{}'...", &[&package])
+        },
+    )
+    .arg(Arg::Required("пакет"))
+    .values(Values::Installed)
+    .risk(Risk::Confirm),
+    CommandSpec::new(
+        Group::Packages,
+        &[
+            "обновить систему",
+            "обновить система",
+            "обновление",
+            "обновить",
+            "update",
+        ],
+        "полное обновление (yay -Syu)",
+        |ctx, _| {
+            ctx.dialog.show_confirm(
+                "Обновление системы",
+                tr("Выполнить полное обновление (yay -Syu)?"),
+                "",
+            );
+            tr("Подготовка к обновлению...").into()
+        },
+    )
+    .risk(Risk::Confirm),
+];
 
 // ============================================================================
 // Функции выполнения (вызываются из фонового потока)
@@ -81,22 +103,24 @@ pub fn search_packages(query: &str) -> String {
         Ok(out) => {
             let result = String::from_utf8_lossy(&out.stdout);
             if result.trim().is_empty() {
-                errors::PACKAGE_NOT_FOUND.into()
+                tr(errors::PACKAGE_NOT_FOUND).into()
             } else {
                 result.into()
             }
         }
-        Err(e) => format!("Ошибка yay: {}", e),
+        Err(e) => trf("Ошибка yay: {}", &[&e]),
     }
 }
 
 /// Установка пакета
-/// Запускаем в терминале для интерактивного sudo
-pub fn install_package(package: &str) -> String {
-    run_in_terminal(
-        &format!("yay -S {}", package),
-        &format!("Установка {}", package),
-    )
+/// Запускаем в терминале для интерактивного sudo.
+/// Перед пакетной установкой (несколько пакетов) делается снимок, если `snapshot`
+pub fn install_package(package: &str, snapshot: bool) -> String {
+    let action = trf("Установка {}", &[&package]);
+    let batch = package.split_whitespace().count() > 1;
+    with_snapshot(snapshot && batch, &action, || {
+        run_in_terminal(&format!("yay -S {}", package), &action)
+    })
 }
 
 /// Удаление пакета
@@ -104,7 +128,7 @@ pub fn install_package(package: &str) -> String {
 pub fn remove_package(package: &str) -> String {
     run_in_terminal(
         &format!("yay -R {}", package),
-        &format!("Удаление {}", package),
+        &trf("Удаление {}", &[&package]),
     )
 }
 
@@ -122,23 +146,23 @@ fn get_terminal_args(term: &str, cmd: &str) -> Option<Vec<String>> {
             "-e".to_string(),
             "sh".to_string(),
             "-c".to_string(),
-            format!("{}; echo 'Нажмите Enter...'; read", cmd),
+            trf("{}; echo 'Нажмите Enter...'; read", &[&cmd]),
         ],
         "gnome-terminal" | "kgx" => vec![
             "--".to_string(),
             "sh".to_string(),
             "-c".to_string(),
-            format!("{}; echo 'Нажмите Enter...'; read", cmd),
+            trf("{}; echo 'Нажмите Enter...'; read", &[&cmd]),
         ],
         "konsole" => vec![
             "-e".to_string(),
             "sh".to_string(),
             "-c".to_string(),
-            format!("{}; echo 'Нажмите Enter...'; read", cmd),
+            trf("{}; echo 'Нажмите Enter...'; read", &[&cmd]),
         ],
         "xfce4-terminal" => vec![
             "-e".to_string(),
-            format!("sh -c '{}; echo Нажмите Enter...; read'", cmd),
+            trf("sh -c '{}; echo Нажмите Enter...; read'", &[&cmd]),
         ],
         "xterm" => vec![
             "-hold".to_string(),
@@ -152,19 +176,23 @@ fn get_terminal_args(term: &str, cmd: &str) -> Option<Vec<String>> {
     Some(args)
 }
 
+/// Блок команд из чата — в терминале. Приглашение `$ ` из console-блоков убирается
+pub fn run_snippet(code: &str) -> String {
+    let script: Vec<&str> = code
+        .lines()
+        .map(|l| l.trim_start().strip_prefix("$ ").unwrap_or(l))
+        .collect();
+    run_in_terminal(&script.join("\n"), tr("Выполнение команды"))
+}
+
 /// Запускает команду в терминале (с учётом текущего DE)
-fn run_in_terminal(cmd: &str, action: &str) -> String {
+pub fn run_in_terminal(cmd: &str, action: &str) -> String {
     let de = DesktopEnvironment::detect();
     let terminals = de.terminal_priority();
 
     for term in terminals {
         // Проверяем, установлен ли терминал
-        if !Command::new("which")
-            .arg(term)
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
-        {
+        if !has_program(term) {
             continue;
         }
 
@@ -176,37 +204,67 @@ fn run_in_terminal(cmd: &str, action: &str) -> String {
 
         // Запускаем
         match Command::new(term).args(&args).spawn() {
-            Ok(_) => return format!("[OK] {} запущено в {}", action, term),
+            Ok(_) => return trf("[OK] {} запущено в {}", &[&action, &term]),
             Err(_) => continue,
         }
     }
 
-    format!(
+    trf(
         "[X] Не найден терминал для {}. Установите {} или другой терминал.",
-        de.name(),
-        de.preferred_terminal()
+        &[&de.name(), &de.preferred_terminal()],
     )
 }
 
 /// Обновление системы
 /// Запускаем в терминале, т.к. yay требует интерактивный ввод для sudo
-pub fn update_system() -> String {
-    run_in_terminal("yay -Syu", "Обновление системы")
+pub fn update_system(snapshot: bool) -> String {
+    with_snapshot(snapshot, tr("Обновление системы"), || {
+        run_in_terminal("yay -Syu", tr("Обновление системы"))
+    })
+}
+
+/// Делает снимок перед действием; если снимок не удался, действие отменяется
+pub fn with_snapshot(snapshot: bool, action: &str, run: impl FnOnce() -> String) -> String {
+    if !snapshot {
+        return run();
+    }
+    match snapshots::pre_upgrade_snapshot(action) {
+        Ok(Some(done)) => format!("{}\n{}", done, run()),
+        Ok(None) => run(),
+        Err(e) => trf("[X] Не удалось создать снимок: {}\n{} отменено. Повторите или отключите снимки в настройках", &[&e, &action]),
+    }
+}
+
+/// Число доступных обновлений из официальных репозиториев (checkupdates
+/// из pacman-contrib). None — проверка недоступна. Результат запоминается
+pub fn check_updates() -> Option<usize> {
+    let out = Command::new("checkupdates").output().ok()?;
+    // Код 2 — обновлений нет, 1 — ошибка
+    let count = match out.status.code() {
+        Some(0) => String::from_utf8_lossy(&out.stdout).lines().count(),
+        Some(2) => 0,
+        _ => return None,
+    };
+    if let Ok(mut last) = PENDING_UPDATES.lock() {
+        *last = Some(count);
+    }
+    Some(count)
+}
+
+/// Число обновлений по последней проверке
+pub fn last_updates_count() -> Option<usize> {
+    PENDING_UPDATES.lock().ok().and_then(|l| *l)
 }
 
 /// Проверка yay (возвращает bool)
 pub fn is_yay_installed() -> bool {
-    Command::new("which")
-        .arg("yay")
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
+    has_program("yay")
 }
 
 /// Установка yay из AUR
 pub fn install_yay() -> String {
     if is_yay_installed() {
-        return messages::YAY_ALREADY.into();
+        return tr(messages::YAY_ALREADY).into();
     }
 
     // 1. Установка зависимостей
@@ -222,7 +280,7 @@ pub fn install_yay() -> String {
         .status();
 
     if deps.is_err() || !deps.unwrap().success() {
-        return errors::YAY_DEPS_FAILED.into();
+        return tr(errors::YAY_DEPS_FAILED).into();
     }
 
     // 2. Клонирование репозитория
@@ -233,7 +291,7 @@ pub fn install_yay() -> String {
         .status();
 
     if clone.is_err() || !clone.unwrap().success() {
-        return errors::YAY_CLONE_FAILED.into();
+        return tr(errors::YAY_CLONE_FAILED).into();
     }
 
     // 3. Сборка и установка
@@ -248,7 +306,7 @@ pub fn install_yay() -> String {
     let _ = Command::new("rm").args(["-rf", YAY_INSTALL_DIR]).status();
 
     match build {
-        Ok(s) if s.success() && is_yay_installed() => messages::YAY_INSTALLED.into(),
-        _ => errors::YAY_BUILD_FAILED.into(),
+        Ok(s) if s.success() && is_yay_installed() => tr(messages::YAY_INSTALLED).into(),
+        _ => tr(errors::YAY_BUILD_FAILED).into(),
     }
 }
@@ -0,0 +1,448 @@
+//! Диспетчер процессов: таблица из /proc, завершение и приоритет
+
+use super::base::CMD_OPEN_PANEL_PREFIX;
+use super::registry::{Arg, CommandContext, CommandSpec, Group, Risk};
+use crate::app::chat::{BackgroundTask, DialogState};
+use crate::app::constants::{PROCESS_REFRESH_MS, TOP_PROCESSES};
+use crate::app::i18n::{tr, trf};
+use crate::app::monitor::{format_bytes, CpuTimes, SystemReader};
+use crate::app::privileged;
+use std::collections::HashMap;
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Строка таблицы процессов
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    pub user: String,
+    /// Загрузка CPU, 100% — одно ядро (как в top)
+    pub cpu: f32,
+    pub rss: u64,
+    pub nice: i32,
+    pub cmdline: String,
+}
+
+/// Сортировка таблицы
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortKey {
+    #[default]
+    Cpu,
+    Memory,
+    Pid,
+    Name,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 4] = [Self::Cpu, Self::Memory, Self::Pid, Self::Name];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Cpu => "CPU",
+            Self::Memory => tr("Память"),
+            Self::Pid => "PID",
+            Self::Name => tr("Имя"),
+        }
+    }
+}
+
+/// Действие над процессами (через диалог подтверждения)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProcessAction {
+    /// SIGTERM — попросить завершиться
+    Terminate,
+    /// SIGKILL — завершить принудительно
+    Kill,
+    /// Новое значение nice (-20..19)
+    Renice(i32),
+}
+
+impl ProcessAction {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Terminate => tr("Завершение процесса"),
+            Self::Kill => tr("Принудительное завершение"),
+            Self::Renice(_) => tr("Изменение приоритета"),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Terminate => tr("Завершить (SIGTERM)").into(),
+            Self::Kill => tr("Принудительно завершить (SIGKILL)").into(),
+            Self::Renice(n) => trf("Установить приоритет nice {}", &[&n]),
+        }
+    }
+}
+
+// ============================================================================
+// Таблица процессов
+// ============================================================================
+
+/// Снимки /proc с расчётом загрузки CPU между ними
+pub struct ProcessTable {
+    reader: SystemReader,
+    users: HashMap<u32, String>,
+    prev_ticks: HashMap<u32, u64>,
+    prev_total: Option<CpuTimes>,
+    last_sample: Option<Instant>,
+    pub processes: Vec<ProcessInfo>,
+}
+
+impl Default for ProcessTable {
+    fn default() -> Self {
+        Self::new(SystemReader::default())
+    }
+}
+
+impl ProcessTable {
+    pub fn new(reader: SystemReader) -> Self {
+        Self {
+            users: reader.users(),
+            reader,
+            prev_ticks: HashMap::new(),
+            prev_total: None,
+            last_sample: None,
+            processes: Vec::new(),
+        }
+    }
+
+    /// Обновляет таблицу, если с прошлого замера прошло не меньше интервала
+    pub fn sample_if_due(&mut self) -> bool {
+        let due = self
+            .last_sample
+            .map(|t| t.elapsed() >= Duration::from_millis(PROCESS_REFRESH_MS))
+            .unwrap_or(true);
+        if due {
+            self.sample();
+        }
+        due
+    }
+
+    /// Перечитывает процессы; CPU% считается от предыдущего замера
+    pub fn sample(&mut self) {
+        self.last_sample = Some(Instant::now());
+
+        let (total, cores) = self.reader.cpu_times();
+        // Тиков на одно ядро за интервал
+        let per_core = self
+            .prev_total
+            .map(|prev| total.total.saturating_sub(prev.total) as f32 / cores.len().max(1) as f32)
+            .unwrap_or(0.0);
+        self.prev_total = Some(total);
+
+        let stats = self.reader.processes();
+        let mut ticks = HashMap::with_capacity(stats.len());
+
+        self.processes = stats
+            .into_iter()
+            .map(|p| {
+                let cpu = match self.prev_ticks.get(&p.pid) {
+                    Some(prev) if per_core > 0.0 => {
+                        p.cpu_ticks.saturating_sub(*prev) as f32 * 100.0 / per_core
+                    }
+                    _ => 0.0,
+                };
+                ticks.insert(p.pid, p.cpu_ticks);
+
+                ProcessInfo {
+                    user: self
+                        .users
+                        .get(&p.uid)
+                        .cloned()
+                        .unwrap_or_else(|| p.uid.to_string()),
+                    pid: p.pid,
+                    name: p.name,
+                    cpu,
+                    rss: p.rss,
+                    nice: p.nice,
+                    cmdline: p.cmdline,
+                }
+            })
+            .collect();
+
+        self.prev_ticks = ticks;
+    }
+
+    /// Процессы, подходящие под поиск, в заданном порядке
+    pub fn filtered(&self, query: &str, sort: SortKey) -> Vec<&ProcessInfo> {
+        let query = query.trim().to_lowercase();
+        let mut list: Vec<&ProcessInfo> = self
+            .processes
+            .iter()
+            .filter(|p| query.is_empty() || matches_query(p, &query))
+            .collect();
+
+        match sort {
+            SortKey::Cpu => list.sort_by(|a, b| b.cpu.total_cmp(&a.cpu).then(b.rss.cmp(&a.rss))),
+            SortKey::Memory => list.sort_by_key(|p| std::cmp::Reverse(p.rss)),
+            SortKey::Pid => list.sort_by_key(|p| p.pid),
+            SortKey::Name => list.sort_by_key(|p| p.name.to_lowercase()),
+        }
+        list
+    }
+}
+
+/// Совпадение по PID, имени, пользователю или командной строке
+fn matches_query(p: &ProcessInfo, query: &str) -> bool {
+    p.pid.to_string() == query
+        || p.name.to_lowercase().contains(query)
+        || p.user.to_lowercase() == query
+        || p.cmdline.to_lowercase().contains(query)
+}
+
+/// Замер с паузой для разовых запросов
+fn quick_table() -> ProcessTable {
+    let mut table = ProcessTable::default();
+    table.sample();
+    std::thread::sleep(Duration::from_millis(500));
+    table.sample();
+    table
+}
+
+// ============================================================================
+// Команды
+// ============================================================================
+
+/// Команды диспетчера процессов
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Processes,
+        &["процессы", "processes"],
+        "диспетчер процессов",
+        |_, query| format!("{}processes:{}", CMD_OPEN_PANEL_PREFIX, query),
+    )
+    .arg(Arg::Optional("поиск")),
+    CommandSpec::new(
+        Group::Processes,
+        &[
+            "что грузит процессор",
+            "что грузит систему",
+            "топ процессов",
+            "top",
+        ],
+        "самые прожорливые процессы",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::TopProcesses);
+            tr("Смотрю процессы...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Processes,
+        &["завершить процесс", "terminate"],
+        "завершить (SIGTERM)",
+        |ctx, target| process_action(ctx, ProcessAction::Terminate, target),
+    )
+    .arg(Arg::Required("pid|имя"))
+    .risk(Risk::Confirm),
+    CommandSpec::new(
+        Group::Processes,
+        &["убить процесс", "kill"],
+        "принудительно (SIGKILL)",
+        |ctx, target| process_action(ctx, ProcessAction::Kill, target),
+    )
+    .arg(Arg::Required("pid|имя"))
+    .risk(Risk::Confirm),
+    // "приоритет процесса <pid|имя> <nice>"
+    CommandSpec::new(
+        Group::Processes,
+        &["приоритет процесса", "renice"],
+        "изменить приоритет",
+        |ctx, args| {
+            let Some((target, nice)) = args.rsplit_once(' ') else {
+                return tr("Формат: приоритет процесса <pid|имя> <nice>").into();
+            };
+            let Some(nice) = nice.parse::<i32>().ok().filter(|n| (-20..=19).contains(n)) else {
+                return tr("Приоритет nice — число от -20 до 19").into();
+            };
+            process_action(ctx, ProcessAction::Renice(nice), target)
+        },
+    )
+    .arg(Arg::Required("pid|имя nice"))
+    .risk(Risk::Confirm),
+];
+
+fn process_action(ctx: &mut CommandContext, action: ProcessAction, target: &str) -> String {
+    let targets = find_processes(target.trim());
+    if targets.is_empty() {
+        return trf("Процесс «{}» не найден", &[&target.trim()]);
+    }
+    show_action_dialog(ctx.dialog, action, &targets);
+    tr("Подтвердите действие...").into()
+}
+
+/// PID и имена процессов по PID или имени (без самого ассистента и init)
+pub fn find_processes(target: &str) -> Vec<(u32, String)> {
+    let reader = SystemReader::default();
+    let own = std::process::id();
+
+    if let Ok(pid) = target.parse::<u32>() {
+        return reader
+            .process(pid)
+            .filter(|p| p.pid != 1 && p.pid != own)
+            .map(|p| vec![(p.pid, p.name)])
+            .unwrap_or_default();
+    }
+
+    let target = target.to_lowercase();
+    reader
+        .processes()
+        .into_iter()
+        .filter(|p| p.pid != 1 && p.pid != own && p.name.to_lowercase() == target)
+        .map(|p| (p.pid, p.name))
+        .collect()
+}
+
+/// Показывает подтверждение действия над процессами
+pub fn show_action_dialog(
+    dialog: &mut DialogState,
+    action: ProcessAction,
+    targets: &[(u32, String)],
+) {
+    let list = targets
+        .iter()
+        .map(|(pid, name)| format!("{} ({})", name, pid))
+        .collect::<Vec<_>>()
+        .join(", ");
+    dialog.show_confirm_task(
+        action.title(),
+        &format!("{}: {}?", action.describe(), list),
+        BackgroundTask::ProcessAction(action, targets.iter().map(|(pid, _)| *pid).collect()),
+    );
+}
+
+// ============================================================================
+// Функции выполнения (вызываются из фонового потока)
+// ============================================================================
+
+/// Программа и аргументы для действия. `--` только у kill: renice принимает
+/// его за PID и завершается с ошибкой, хотя приоритет уже изменён
+fn action_command(action: ProcessAction, pids: &[String]) -> (&'static str, Vec<String>) {
+    let (program, mut args) = match action {
+        ProcessAction::Terminate => ("kill", vec!["-s".to_string(), "TERM".into(), "--".into()]),
+        ProcessAction::Kill => ("kill", vec!["-s".to_string(), "KILL".into(), "--".into()]),
+        ProcessAction::Renice(n) => ("renice", vec!["-n".to_string(), n.to_string(), "-p".into()]),
+    };
+    args.extend(pids.iter().cloned());
+    (program, args)
+}
+
+/// Ошибка kill/renice из-за нехватки прав (EPERM, EACCES)
+fn permission_denied(stderr: &str) -> bool {
+    stderr.contains("Operation not permitted") || stderr.contains("Permission denied")
+}
+
+/// Выполняет действие; только при нехватке прав повторяет через pkexec
+pub fn execute_action(action: ProcessAction, pids: &[u32]) -> String {
+    let pids: Vec<String> = pids.iter().map(u32::to_string).collect();
+    let (program, args) = action_command(action, &pids);
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let done = format!("[OK] {}: {}", action.describe(), pids.join(", "));
+    let failed = |e: &dyn std::fmt::Display| format!("[X] {}: {}", action.describe(), e);
+    // Английские сообщения об ошибках, чтобы распознать EPERM
+    let output = match Command::new(program)
+        .args(&args)
+        .env("LC_ALL", "C")
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => return failed(&e),
+    };
+    if output.status.success() {
+        return done;
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if stderr.is_empty() {
+        return failed(&trf("{} завершился с ошибкой", &[&program]));
+    }
+    if !permission_denied(&stderr) {
+        // Процесс уже завершился и прочие ошибки root не исправит
+        return failed(&stderr);
+    }
+    // Чужие процессы и отрицательный nice требуют root
+    match privileged::run(program, &args) {
+        Ok(_) => done,
+        Err(e) => failed(&e),
+    }
+}
+
+/// Самые загруженные процессы (для чата и инструмента ИИ)
+pub fn top_processes() -> String {
+    let table = quick_table();
+    let top: Vec<String> = table
+        .filtered("", SortKey::Cpu)
+        .into_iter()
+        .take(TOP_PROCESSES)
+        .map(|p| {
+            trf(
+                "{} (PID {}, {}) — CPU {}%, память {}",
+                &[
+                    &p.name,
+                    &p.pid,
+                    &p.user,
+                    &format!("{:.0}", p.cpu),
+                    &format_bytes(p.rss),
+                ],
+            )
+        })
+        .collect();
+
+    if top.is_empty() {
+        tr("Не удалось прочитать процессы").into()
+    } else {
+        top.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn separator_only_for_kill() {
+        let pids = ["42".to_string(), "43".to_string()];
+        assert_eq!(
+            action_command(ProcessAction::Kill, &pids),
+            (
+                "kill",
+                vec![
+                    "-s".into(),
+                    "KILL".into(),
+                    "--".into(),
+                    "42".into(),
+                    "43".into()
+                ]
+            )
+        );
+        assert_eq!(
+            action_command(ProcessAction::Renice(5), &pids),
+            (
+                "renice",
+                vec![
+                    "-n".into(),
+                    "5".into(),
+                    "-p".into(),
+                    "42".into(),
+                    "43".into()
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn escalates_only_on_permission_errors() {
+        assert!(permission_denied("kill: (1): Operation not permitted"));
+        assert!(permission_denied(
+            "renice: failed to set priority for 1 (process ID): Permission denied"
+        ));
+        assert!(!permission_denied("kill: (99999): No such process"));
+        assert!(!permission_denied("renice: bad value"));
+    }
+}
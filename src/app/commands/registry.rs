@@ -0,0 +1,329 @@
+//! Реестр команд
+//!
+//! Каждая команда объявляется один раз в таблице `COMMANDS` своего модуля:
+//! фразы на русском и английском, аргумент, уровень риска, описание и
+//! обработчик. По реестру строятся разбор ввода, справка, автодополнение
+//! и список команд в системном промпте ИИ.
+
+use super::{
+    audio, base, gpu, guide, journal, kernels, mirrors, network, package, processes, security,
+    services, snapshots, system,
+};
+use crate::app::chat::{DialogState, TaskManager};
+use crate::app::config::Config;
+use crate::app::guides::GuideRegistry;
+use crate::app::i18n::{language, tr, trf, Language};
+
+// ============================================================================
+// Описание команды
+// ============================================================================
+
+/// Раздел справки
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Group {
+    Basic,
+    Packages,
+    Mirrors,
+    Security,
+    Gpu,
+    Kernels,
+    Snapshots,
+    System,
+    Services,
+    Network,
+    Audio,
+    Processes,
+    Journal,
+    Guides,
+    Chats,
+    Other,
+}
+
+impl Group {
+    /// Порядок разделов в справке
+    pub const ALL: &'static [Group] = &[
+        Group::Basic,
+        Group::Packages,
+        Group::Mirrors,
+        Group::Security,
+        Group::Gpu,
+        Group::Kernels,
+        Group::Snapshots,
+        Group::System,
+        Group::Services,
+        Group::Network,
+        Group::Audio,
+        Group::Processes,
+        Group::Journal,
+        Group::Guides,
+        Group::Chats,
+        Group::Other,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            Group::Basic => tr("Базовые"),
+            Group::Packages => tr("Пакеты (через yay)"),
+            Group::Mirrors => tr("Зеркала"),
+            Group::Security => tr("Безопасность"),
+            Group::Gpu => tr("Видеокарта"),
+            Group::Kernels => tr("Ядра"),
+            Group::Snapshots => tr("Снимки"),
+            Group::System => tr("Система"),
+            Group::Services => tr("Сервисы systemd"),
+            Group::Network => tr("Сеть"),
+            Group::Audio => tr("Звук"),
+            Group::Processes => tr("Процессы"),
+            Group::Journal => tr("Журнал"),
+            Group::Guides => tr("Гайды"),
+            Group::Chats => tr("Чаты"),
+            Group::Other => tr("Прочее"),
+        }
+    }
+}
+
+/// Аргумент команды; строка — его название в справке
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Arg {
+    None,
+    Optional(&'static str),
+    Required(&'static str),
+}
+
+impl Arg {
+    /// `<пакет>` для обязательного, `[сервис]` для необязательного
+    pub fn hint(&self) -> String {
+        match self {
+            Arg::None => String::new(),
+            Arg::Optional(name) => format!("[{}]", tr(name)),
+            Arg::Required(name) => format!("<{}>", tr(name)),
+        }
+    }
+}
+
+/// Откуда брать значения аргумента для автодополнения
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Values {
+    /// Произвольный текст
+    Free,
+    /// Пакеты из репозиториев
+    Packages,
+    /// Установленные пакеты
+    Installed,
+    Services,
+    Guides,
+    List(&'static [&'static str]),
+}
+
+/// Уровень риска команды
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Risk {
+    /// Только читает или меняет мелочи (громкость, панели)
+    Safe,
+    /// Меняет систему после диалога подтверждения
+    Confirm,
+    /// Прерывает работу (выключение, откат) — ИИ вызывает только по явной просьбе
+    Critical,
+}
+
+/// Окружение, доступное обработчикам команд
+pub struct CommandContext<'a> {
+    pub config: &'a Config,
+    pub dialog: &'a mut DialogState,
+    pub tasks: &'a TaskManager,
+    pub guides: &'a GuideRegistry,
+}
+
+/// Обработчик: получает аргумент (пустой, если его нет) и возвращает ответ
+pub type Handler = fn(&mut CommandContext, &str) -> String;
+
+/// Объявление команды
+pub struct CommandSpec {
+    pub group: Group,
+    /// Первая фраза — основная (справка, подсказки), остальные — синонимы
+    pub phrases: &'static [&'static str],
+    pub arg: Arg,
+    pub values: Values,
+    pub risk: Risk,
+    pub description: &'static str,
+    pub handler: Handler,
+    /// Проверка аргумента: не прошёл — ввод относится не к этой команде
+    pub accepts: Option<fn(&str) -> bool>,
+}
+
+impl CommandSpec {
+    pub const fn new(
+        group: Group,
+        phrases: &'static [&'static str],
+        description: &'static str,
+        handler: Handler,
+    ) -> Self {
+        Self {
+            group,
+            phrases,
+            arg: Arg::None,
+            values: Values::Free,
+            risk: Risk::Safe,
+            description,
+            handler,
+            accepts: None,
+        }
+    }
+
+    pub const fn arg(self, arg: Arg) -> Self {
+        Self { arg, ..self }
+    }
+
+    pub const fn values(self, values: Values) -> Self {
+        Self { values, ..self }
+    }
+
+    pub const fn risk(self, risk: Risk) -> Self {
+        Self { risk, ..self }
+    }
+
+    pub const fn accepts(self, accepts: fn(&str) -> bool) -> Self {
+        Self {
+            accepts: Some(accepts),
+            ..self
+        }
+    }
+
+    /// Подходит ли аргумент команде (пустой подходит всегда)
+    pub fn accepts_arg(&self, arg: &str) -> bool {
+        arg.is_empty() || self.accepts.is_none_or(|accepts| accepts(arg))
+    }
+
+    /// Основная фраза на языке интерфейса: в английском — первая
+    /// латинская фраза, если она есть
+    pub fn phrase(&self) -> &'static str {
+        let english = match language() {
+            Language::En => self.phrases.iter().find(|p| p.is_ascii()),
+            Language::Ru => None,
+        };
+        english.copied().unwrap_or(self.phrases[0])
+    }
+
+    /// Фраза с аргументом: «установить <пакет>»
+    pub fn usage(&self) -> String {
+        match self.arg {
+            Arg::None => self.phrase().to_string(),
+            arg => format!("{} {}", self.phrase(), arg.hint()),
+        }
+    }
+}
+
+// ============================================================================
+// Разбор и выполнение
+// ============================================================================
+
+/// Все команды в порядке модулей
+pub fn all() -> impl Iterator<Item = &'static CommandSpec> {
+    [
+        base::COMMANDS,
+        system::COMMANDS,
+        kernels::COMMANDS,
+        gpu::COMMANDS,
+        package::COMMANDS,
+        snapshots::COMMANDS,
+        mirrors::COMMANDS,
+        security::COMMANDS,
+        services::COMMANDS,
+        journal::COMMANDS,
+        network::COMMANDS,
+        audio::COMMANDS,
+        processes::COMMANDS,
+        guide::COMMANDS,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Находит команду по вводу (в нижнем регистре).
+/// Побеждает самая длинная фраза: «установить драйверы» раньше «установить <пакет>»
+pub fn parse(cmd: &str) -> Option<(&'static CommandSpec, &str)> {
+    let mut best: Option<(&'static CommandSpec, &str, usize)> = None;
+    for spec in all() {
+        for phrase in spec.phrases {
+            let arg = if cmd == *phrase {
+                Some("")
+            } else if spec.arg != Arg::None {
+                cmd.strip_prefix(phrase)
+                    .and_then(|rest| rest.strip_prefix(' '))
+            } else {
+                None
+            };
+            if let Some(arg) = arg.map(str::trim).filter(|arg| spec.accepts_arg(arg)) {
+                if best.is_none_or(|(_, _, len)| phrase.len() > len) {
+                    best = Some((spec, arg, phrase.len()));
+                }
+            }
+        }
+    }
+    best.map(|(spec, arg, _)| (spec, arg))
+}
+
+/// Выполняет команду; без обязательного аргумента подсказывает формат
+pub fn execute(spec: &CommandSpec, arg: &str, ctx: &mut CommandContext) -> String {
+    if arg.is_empty() && matches!(spec.arg, Arg::Required(_)) {
+        return trf("Не хватает аргумента. Формат: {}", &[&spec.usage()]);
+    }
+    let response = (spec.handler)(ctx, arg);
+    // Диалог этой команды; show_confirm уже сбросил признак прежнего
+    if ctx.dialog.visible && spec.risk == Risk::Critical {
+        ctx.dialog.critical = true;
+    }
+    response
+}
+
+// ============================================================================
+// Справка и промпт ИИ
+// ============================================================================
+
+/// Текст справки
+pub fn help() -> String {
+    let mut output = String::from(tr("📋 Доступные команды:\n"));
+    for group in Group::ALL {
+        output.push_str(&format!("\n▸ {}:\n", group.title()));
+        for spec in all().filter(|s| s.group == *group) {
+            output.push_str(&format!(
+                "  {}{} — {}\n",
+                spec.usage(),
+                if spec.risk == Risk::Safe { "" } else { " ⚠" },
+                tr(spec.description)
+            ));
+        }
+    }
+    output.push_str(tr("\n⚠ — с подтверждением\n"));
+    output.push_str(tr("💡 Или просто задайте вопрос — ИИ постарается помочь!"));
+    output
+}
+
+/// Список команд для системного промпта ИИ
+pub fn ai_command_list() -> String {
+    let mut output = String::new();
+    for group in Group::ALL {
+        output.push_str(&format!("\n▸ {}:\n", group.title()));
+        for spec in all().filter(|s| s.group == *group) {
+            output.push_str(&format!(
+                "  [CMD:{}] - {}{}\n",
+                spec.usage(),
+                tr(spec.description),
+                if spec.risk == Risk::Safe {
+                    ""
+                } else {
+                    tr(" (откроется диалог подтверждения!)")
+                }
+            ));
+        }
+    }
+    output
+}
+
+/// Фразы команд, которые ИИ вызывает только по явной просьбе
+pub fn critical_phrases() -> Vec<&'static str> {
+    all()
+        .filter(|s| s.risk == Risk::Critical)
+        .map(CommandSpec::phrase)
+        .collect()
+}
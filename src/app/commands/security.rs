@@ -0,0 +1,591 @@
+//! Проверка установленных пакетов по Arch Security Tracker
+
+use super::registry::{CommandContext, CommandSpec, Group, Risk};
+use crate::app::chat::BackgroundTask;
+use crate::app::config::Config;
+use crate::app::constants::{
+    CONFIG_APP_NAME, SECURITY_CACHE_MAX_AGE_SECS, SECURITY_FEED_TIMEOUT_SECS,
+};
+use crate::app::i18n::{tr, trf};
+use reqwest::Client;
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+/// Результат последней проверки (для индикатора в шапке)
+static LAST_AUDIT: Mutex<Option<Vec<Vulnerability>>> = Mutex::new(None);
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Запись трекера (группа уязвимостей AVG)
+#[derive(Debug, Clone, Deserialize)]
+pub struct Advisory {
+    pub name: String,
+    pub packages: Vec<String>,
+    pub status: String,
+    pub severity: String,
+    #[serde(default)]
+    pub fixed: Option<String>,
+    #[serde(default)]
+    pub issues: Vec<String>,
+}
+
+/// Уязвимый установленный пакет со всеми затрагивающими его группами AVG
+#[derive(Debug, Clone)]
+pub struct Vulnerability {
+    pub package: String,
+    pub installed: String,
+    /// Версия, закрывающая все группы; None — хотя бы одна ещё не исправлена
+    pub fixed: Option<String>,
+    /// Наибольшая важность среди групп
+    pub severity: String,
+    pub avgs: Vec<String>,
+    pub cves: Vec<String>,
+}
+
+impl Vulnerability {
+    /// Добавляет ещё одну группу AVG того же пакета
+    fn merge(&mut self, avg: &Advisory) {
+        self.fixed = match (self.fixed.take(), &avg.fixed) {
+            (Some(a), Some(b)) => Some(if vercmp(&a, b) == Ordering::Less {
+                b.clone()
+            } else {
+                a
+            }),
+            _ => None,
+        };
+        if severity_rank(&avg.severity) > severity_rank(&self.severity) {
+            self.severity = avg.severity.clone();
+        }
+        self.avgs.push(avg.name.clone());
+        for cve in &avg.issues {
+            if !self.cves.contains(cve) {
+                self.cves.push(cve.clone());
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Сравнение версий (алгоритм vercmp из pacman)
+// ============================================================================
+
+/// Сравнивает версии пакетов в формате `[epoch:]version[-release]`
+pub fn vercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+
+    let (epoch_a, ver_a, rel_a) = split_evr(a);
+    let (epoch_b, ver_b, rel_b) = split_evr(b);
+
+    rpmvercmp(epoch_a, epoch_b)
+        .then_with(|| rpmvercmp(ver_a, ver_b))
+        .then_with(|| match (rel_a, rel_b) {
+            (Some(ra), Some(rb)) => rpmvercmp(ra, rb),
+            _ => Ordering::Equal,
+        })
+}
+
+/// Делит строку версии на эпоху, версию и релиз
+fn split_evr(evr: &str) -> (&str, &str, Option<&str>) {
+    let (epoch, rest) = match evr.split_once(':') {
+        Some((e, r)) if !e.is_empty() && e.chars().all(|c| c.is_ascii_digit()) => (e, r),
+        _ => ("0", evr),
+    };
+    match rest.rsplit_once('-') {
+        Some((v, r)) => (epoch, v, Some(r)),
+        None => (epoch, rest, None),
+    }
+}
+
+/// Посегментное сравнение: числа сравниваются как числа, буквы — лексикографически
+fn rpmvercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal;
+    }
+
+    let mut one = a;
+    let mut two = b;
+
+    loop {
+        // Пропускаем разделители
+        let sep_one = one.len()
+            - one
+                .trim_start_matches(|c: char| !c.is_ascii_alphanumeric())
+                .len();
+        let sep_two = two.len()
+            - two
+                .trim_start_matches(|c: char| !c.is_ascii_alphanumeric())
+                .len();
+        one = &one[sep_one..];
+        two = &two[sep_two..];
+
+        if one.is_empty() || two.is_empty() {
+            break;
+        }
+
+        // Разное количество разделителей — более длинный разделитель «новее»
+        if sep_one != sep_two {
+            return sep_one.cmp(&sep_two);
+        }
+
+        let is_num = one.starts_with(|c: char| c.is_ascii_digit());
+        let take = |s: &str| -> usize {
+            s.find(|c: char| {
+                if is_num {
+                    !c.is_ascii_digit()
+                } else {
+                    !c.is_ascii_alphabetic()
+                }
+            })
+            .unwrap_or(s.len())
+        };
+
+        let len_one = take(one);
+        let len_two = take(two);
+        let (seg_one, seg_two) = (&one[..len_one], &two[..len_two]);
+
+        // Сегменты разного типа: число новее буквы
+        if seg_two.is_empty() {
+            return if is_num {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            };
+        }
+
+        let ord = if is_num {
+            let n1 = seg_one.trim_start_matches('0');
+            let n2 = seg_two.trim_start_matches('0');
+            n1.len().cmp(&n2.len()).then_with(|| n1.cmp(n2))
+        } else {
+            seg_one.cmp(seg_two)
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+
+        one = &one[len_one..];
+        two = &two[len_two..];
+    }
+
+    match (one.is_empty(), two.is_empty()) {
+        (true, true) => Ordering::Equal,
+        // Оставшийся буквенный хвост считается предрелизом: 1.0a < 1.0
+        (true, false) => {
+            if two.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                Ordering::Greater
+            } else {
+                Ordering::Less
+            }
+        }
+        (false, _) => {
+            if one.starts_with(|c: char| c.is_ascii_alphabetic()) {
+                Ordering::Less
+            } else {
+                Ordering::Greater
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Сопоставление
+// ============================================================================
+
+/// Разбирает вывод `pacman -Q` в карту имя → версия
+pub fn parse_installed(output: &str) -> HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|l| {
+            let mut parts = l.split_whitespace();
+            Some((parts.next()?.to_string(), parts.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// Находит уязвимые установленные пакеты; группы AVG одного пакета
+/// объединяются в одну запись
+pub fn find_vulnerable(
+    advisories: &[Advisory],
+    installed: &HashMap<String, String>,
+) -> Vec<Vulnerability> {
+    let mut by_package: HashMap<&str, Vulnerability> = HashMap::new();
+
+    for avg in advisories {
+        if avg.status != "Vulnerable" && avg.status != "Fixed" {
+            continue;
+        }
+
+        for package in &avg.packages {
+            let Some(version) = installed.get(package) else {
+                continue;
+            };
+
+            // С исправлением — уязвим, пока версия ниже исправленной
+            let affected = match &avg.fixed {
+                Some(fixed) => vercmp(version, fixed) == Ordering::Less,
+                None => avg.status == "Vulnerable",
+            };
+
+            if !affected {
+                continue;
+            }
+            match by_package.get_mut(package.as_str()) {
+                Some(existing) => existing.merge(avg),
+                None => {
+                    by_package.insert(
+                        package,
+                        Vulnerability {
+                            package: package.clone(),
+                            installed: version.clone(),
+                            fixed: avg.fixed.clone(),
+                            severity: avg.severity.clone(),
+                            avgs: vec![avg.name.clone()],
+                            cves: avg.issues.clone(),
+                        },
+                    );
+                }
+            }
+        }
+    }
+
+    let mut result: Vec<Vulnerability> = by_package.into_values().collect();
+    result.sort_by(|a, b| {
+        severity_rank(&b.severity)
+            .cmp(&severity_rank(&a.severity))
+            .then_with(|| a.package.cmp(&b.package))
+    });
+    result
+}
+
+/// Порядок важности для сортировки
+fn severity_rank(severity: &str) -> u8 {
+    match severity {
+        "Critical" => 4,
+        "High" => 3,
+        "Medium" => 2,
+        "Low" => 1,
+        _ => 0,
+    }
+}
+
+// ============================================================================
+// Загрузка данных трекера
+// ============================================================================
+
+/// Путь к кэшу данных трекера
+fn cache_path() -> Option<PathBuf> {
+    dirs::cache_dir().map(|p| p.join(CONFIG_APP_NAME).join("security.json"))
+}
+
+/// Загружает JSON трекера: из локального файла, из свежего кэша или по URL
+fn load_feed(source: &str) -> Result<String, String> {
+    // Источник — локальный файл (как `arch-audit --source`)
+    if !source.starts_with("http://") && !source.starts_with("https://") {
+        return std::fs::read_to_string(source)
+            .map_err(|e| trf("Не удалось прочитать {}: {}", &[&source, &e]));
+    }
+
+    let cache = cache_path().ok_or(tr("Не удалось определить каталог кэша"))?;
+    let fresh = std::fs::metadata(&cache)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| SystemTime::now().duration_since(t).ok())
+        .is_some_and(|age| age < Duration::from_secs(SECURITY_CACHE_MAX_AGE_SECS));
+
+    if !fresh {
+        if let Some(parent) = cache.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        match download(source) {
+            Ok(feed) => {
+                let _ = std::fs::write(&cache, &feed);
+                return Ok(feed);
+            }
+            // При ошибке загрузки используем устаревший кэш, если он есть
+            Err(e) if !cache.exists() => {
+                return Err(trf("Не удалось загрузить {}: {}", &[&source, &e]))
+            }
+            Err(_) => {}
+        }
+    }
+
+    std::fs::read_to_string(&cache).map_err(|e| trf("Ошибка чтения кэша: {}", &[&e]))
+}
+
+/// Скачивает документ по URL. Вызывается и из фоновых задач, и из
+/// инструментов ИИ внутри tokio, поэтому запрос идёт в отдельном потоке
+/// со своим runtime
+fn download(url: &str) -> Result<String, String> {
+    let url = url.to_string();
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| e.to_string())?;
+        runtime.block_on(async {
+            let client = Client::builder()
+                .timeout(Duration::from_secs(SECURITY_FEED_TIMEOUT_SECS))
+                .build()
+                .map_err(|e| e.to_string())?;
+            client
+                .get(&url)
+                .send()
+                .await
+                .and_then(|r| r.error_for_status())
+                .map_err(|e| e.to_string())?
+                .text()
+                .await
+                .map_err(|e| e.to_string())
+        })
+    })
+    .join()
+    .unwrap_or_else(|_| Err(tr("поток загрузки завершился аварийно").to_string()))
+}
+
+/// Выполняет проверку и сохраняет результат для шапки
+pub fn run_audit(source: &str) -> Result<Vec<Vulnerability>, String> {
+    let feed = load_feed(source)?;
+    let advisories: Vec<Advisory> =
+        serde_json::from_str(&feed).map_err(|e| trf("Ошибка разбора JSON трекера: {}", &[&e]))?;
+
+    let output = Command::new("pacman")
+        .arg("-Q")
+        .output()
+        .map_err(|e| trf("Ошибка pacman: {}", &[&e]))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(trf("Ошибка pacman: {}", &[&stderr.trim()]));
+    }
+    let installed = parse_installed(&String::from_utf8_lossy(&output.stdout));
+
+    let vulnerable = find_vulnerable(&advisories, &installed);
+    if let Ok(mut last) = LAST_AUDIT.lock() {
+        *last = Some(vulnerable.clone());
+    }
+    Ok(vulnerable)
+}
+
+/// Количество уязвимых пакетов по последней проверке
+pub fn last_audit_count() -> Option<usize> {
+    LAST_AUDIT
+        .lock()
+        .ok()
+        .and_then(|l| l.as_ref().map(Vec::len))
+}
+
+/// Форматирует отчёт для чата
+pub fn format_report(vulnerable: &[Vulnerability]) -> String {
+    if vulnerable.is_empty() {
+        return tr("[OK] Уязвимых пакетов не найдено.").into();
+    }
+
+    let mut output = trf("[!] Уязвимых пакетов: {}\n\n", &[&vulnerable.len()]);
+    for v in vulnerable {
+        output.push_str(&trf(
+            "• {} {} — {} ({})\n  {}\n  Исправлено в: {}\n",
+            &[
+                &v.package,
+                &v.installed,
+                &v.severity,
+                &v.avgs.join(", "),
+                &v.cves.join(", "),
+                &v.fixed.as_deref().unwrap_or(tr("исправления пока нет")),
+            ],
+        ));
+    }
+
+    if vulnerable.iter().any(|v| v.fixed.is_some()) {
+        output.push_str(tr("\nУстановить исправления: безопасность исправить"));
+    }
+    output
+}
+
+// ============================================================================
+// Команды
+// ============================================================================
+
+/// Команды проверки безопасности
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Security,
+        &["безопасность", "уязвимости", "security", "audit"],
+        "проверить пакеты по Arch Security Tracker",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::SecurityAudit(
+                ctx.config.security_feed.clone(),
+            ));
+            tr("Проверяю пакеты по Arch Security Tracker...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Security,
+        &[
+            "безопасность исправить",
+            "исправить уязвимости",
+            "fix vulnerabilities",
+        ],
+        "установить исправления",
+        fix_vulnerabilities,
+    )
+    .risk(Risk::Confirm),
+];
+
+fn fix_vulnerabilities(ctx: &mut CommandContext, _: &str) -> String {
+    let fixable: Vec<String> = LAST_AUDIT
+        .lock()
+        .ok()
+        .and_then(|l| l.clone())
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|v| v.fixed.is_some())
+        .map(|v| v.package)
+        .collect();
+
+    if fixable.is_empty() {
+        return tr("Нет исправлений для установки. Сначала: безопасность").into();
+    }
+
+    // На Arch частичные обновления не поддерживаются — обновляем всю систему
+    ctx.dialog.show_confirm_task(
+        "Обновление системы",
+        &trf(
+            "Исправления для: {}\nВыполнить полное обновление (yay -Syu)?",
+            &[&fixable.join(", ")],
+        ),
+        BackgroundTask::UpdateSystem(ctx.config.snapshot_before_upgrade),
+    );
+    tr("Подтвердите установку исправлений...").into()
+}
+
+// ============================================================================
+// Функции выполнения (вызываются из фонового потока)
+// ============================================================================
+
+/// Проверка безопасности для фоновой задачи
+pub fn security_audit(source: &str) -> String {
+    match run_audit(source) {
+        Ok(vulnerable) => format_report(&vulnerable),
+        Err(e) => format!("[X] {}", e),
+    }
+}
+
+/// Краткая сводка для инструмента ИИ
+pub fn audit_summary() -> String {
+    match run_audit(&Config::load().security_feed) {
+        Ok(v) if v.is_empty() => tr("уязвимых пакетов нет").into(),
+        Ok(v) => v
+            .iter()
+            .map(|v| {
+                trf(
+                    "{} {} ({}, {}, исправлено в {})",
+                    &[
+                        &v.package,
+                        &v.installed,
+                        &v.severity,
+                        &v.cves.join(" "),
+                        &v.fixed.as_deref().unwrap_or("-"),
+                    ],
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("; "),
+        Err(e) => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advisory(name: &str, package: &str, fixed: Option<&str>, severity: &str) -> Advisory {
+        Advisory {
+            name: name.into(),
+            packages: vec![package.into()],
+            status: if fixed.is_some() {
+                "Fixed"
+            } else {
+                "Vulnerable"
+            }
+            .into(),
+            severity: severity.into(),
+            fixed: fixed.map(String::from),
+            issues: vec![format!("CVE-{}", name)],
+        }
+    }
+
+    #[test]
+    fn vercmp_matches_pacman() {
+        let cases = [
+            ("1.0", "1.0", Ordering::Equal),
+            ("1.0", "1.1", Ordering::Less),
+            ("1.10", "1.9", Ordering::Greater),
+            ("1.0-1", "1.0-2", Ordering::Less),
+            ("1.0-2", "1.0", Ordering::Equal),
+            ("1:1.0", "2.0", Ordering::Greater),
+            ("1.0a", "1.0", Ordering::Less),
+            ("1.0", "1.0.1", Ordering::Less),
+            ("1.0.0", "1.0a", Ordering::Greater),
+            ("1.001", "1.1", Ordering::Equal),
+            ("2.0.r12.gabc-1", "2.0.r9.gdef-1", Ordering::Greater),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(vercmp(a, b), expected, "vercmp({a}, {b})");
+            assert_eq!(vercmp(b, a), expected.reverse(), "vercmp({b}, {a})");
+        }
+    }
+
+    #[test]
+    fn parses_pacman_query() {
+        let installed = parse_installed("openssl 3.1.0-1\nbash 5.2.015-1\n\n");
+        assert_eq!(installed.len(), 2);
+        assert_eq!(installed["openssl"], "3.1.0-1");
+    }
+
+    #[test]
+    fn groups_advisories_by_package() {
+        let advisories = [
+            advisory("AVG-1", "openssl", Some("3.1.1-1"), "Medium"),
+            advisory("AVG-2", "openssl", Some("3.1.2-1"), "High"),
+            advisory("AVG-3", "openssl", Some("3.0.0-1"), "Critical"),
+            advisory("AVG-4", "curl", None, "Low"),
+            advisory("AVG-5", "absent", None, "Critical"),
+        ];
+        let installed = parse_installed("openssl 3.1.0-1\ncurl 8.0-1\n");
+
+        let vulnerable = find_vulnerable(&advisories, &installed);
+        assert_eq!(vulnerable.len(), 2);
+
+        let openssl = &vulnerable[0];
+        assert_eq!(openssl.package, "openssl");
+        assert_eq!(openssl.avgs, ["AVG-1", "AVG-2"]);
+        assert_eq!(openssl.fixed.as_deref(), Some("3.1.2-1"));
+        assert_eq!(openssl.severity, "High");
+        assert_eq!(openssl.cves, ["CVE-AVG-1", "CVE-AVG-2"]);
+
+        let curl = &vulnerable[1];
+        assert_eq!(curl.package, "curl");
+        assert_eq!(curl.fixed, None);
+    }
+
+    #[test]
+    fn unfixed_group_clears_fixed_version() {
+        let advisories = [
+            advisory("AVG-1", "openssl", Some("3.1.1-1"), "Low"),
+            advisory("AVG-2", "openssl", None, "Low"),
+        ];
+        let installed = parse_installed("openssl 3.1.0-1\n");
+
+        let vulnerable = find_vulnerable(&advisories, &installed);
+        assert_eq!(vulnerable.len(), 1);
+        assert_eq!(vulnerable[0].fixed, None);
+    }
+}
@@ -0,0 +1,341 @@
+//! Управление сервисами systemd
+
+use super::base::CMD_OPEN_PANEL_PREFIX;
+use super::registry::{Arg, CommandContext, CommandSpec, Group, Risk, Values};
+use crate::app::chat::{BackgroundTask, DialogState};
+use crate::app::i18n::{tr, trf};
+use crate::app::privileged;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+/// Список юнитов для панели (обновляется в фоне)
+pub type SharedUnits = Arc<Mutex<Vec<ServiceUnit>>>;
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Сервис systemd и его состояние
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceUnit {
+    pub name: String,
+    pub load: String,
+    pub active: String,
+    pub sub: String,
+    pub enabled: String,
+    pub description: String,
+}
+
+impl ServiceUnit {
+    pub fn is_failed(&self) -> bool {
+        self.active == "failed"
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active == "active"
+    }
+}
+
+/// Действие над сервисом (требует прав root)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ServiceAction {
+    Start,
+    Stop,
+    Restart,
+    Enable,
+    Disable,
+}
+
+impl ServiceAction {
+    /// Подкоманда systemctl
+    pub fn verb(&self) -> &'static str {
+        match self {
+            Self::Start => "start",
+            Self::Stop => "stop",
+            Self::Restart => "restart",
+            Self::Enable => "enable",
+            Self::Disable => "disable",
+        }
+    }
+
+    /// Название действия для диалога
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Start => tr("Запуск сервиса"),
+            Self::Stop => tr("Остановка сервиса"),
+            Self::Restart => tr("Перезапуск сервиса"),
+            Self::Enable => tr("Включение автозапуска"),
+            Self::Disable => tr("Отключение автозапуска"),
+        }
+    }
+}
+
+// ============================================================================
+// Парсинг вывода systemctl
+// ============================================================================
+
+/// Приводит имя к виду `name.service`; отклоняет подозрительные имена
+pub fn normalize_unit(name: &str) -> Option<String> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && !name.starts_with('-')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "@._:-\\".contains(c));
+    if !valid {
+        return None;
+    }
+
+    if name.contains('.') {
+        Some(name.to_string())
+    } else {
+        Some(format!("{}.service", name))
+    }
+}
+
+/// Разбирает `systemctl list-units --plain --no-legend`
+pub fn parse_list_units(output: &str) -> Vec<ServiceUnit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            // Отмеченные строки начинаются с "●"
+            let line = line.trim_start_matches('●').trim();
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?.to_string();
+            let load = parts.next()?.to_string();
+            let active = parts.next()?.to_string();
+            let sub = parts.next()?.to_string();
+            let description = parts.collect::<Vec<_>>().join(" ");
+            Some(ServiceUnit {
+                name,
+                load,
+                active,
+                sub,
+                enabled: String::new(),
+                description,
+            })
+        })
+        .collect()
+}
+
+/// Разбирает `systemctl list-unit-files --no-legend` в пары имя → состояние
+pub fn parse_unit_files(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some((parts.next()?.to_string(), parts.next()?.to_string()))
+        })
+        .collect()
+}
+
+/// Загружает список сервисов со статусом автозапуска
+pub fn list_units() -> Result<Vec<ServiceUnit>, String> {
+    let units = Command::new("systemctl")
+        .args([
+            "list-units",
+            "--type=service",
+            "--all",
+            "--plain",
+            "--no-legend",
+            "--no-pager",
+        ])
+        .output()
+        .map_err(|e| trf("Ошибка systemctl: {}", &[&e]))?;
+
+    let files = Command::new("systemctl")
+        .args([
+            "list-unit-files",
+            "--type=service",
+            "--no-legend",
+            "--no-pager",
+        ])
+        .output()
+        .map_err(|e| trf("Ошибка systemctl: {}", &[&e]))?;
+
+    let enabled = parse_unit_files(&String::from_utf8_lossy(&files.stdout));
+    let mut units = parse_list_units(&String::from_utf8_lossy(&units.stdout));
+
+    for unit in &mut units {
+        if let Some((_, state)) = enabled.iter().find(|(name, _)| *name == unit.name) {
+            unit.enabled = state.clone();
+        }
+    }
+
+    // Упавшие сервисы — в начало списка
+    units.sort_by(|a, b| {
+        b.is_failed()
+            .cmp(&a.is_failed())
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(units)
+}
+
+/// Обновляет общий список в фоновом потоке
+pub fn refresh_units(shared: &SharedUnits) {
+    let shared = shared.clone();
+    std::thread::spawn(move || {
+        if let Ok(units) = list_units() {
+            if let Ok(mut list) = shared.lock() {
+                *list = units;
+            }
+        }
+    });
+}
+
+// ============================================================================
+// Команды
+// ============================================================================
+
+/// Команды сервисов systemd
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Services,
+        &["сервисы", "службы", "services"],
+        "панель сервисов",
+        |_, _| format!("{}services", CMD_OPEN_PANEL_PREFIX),
+    ),
+    CommandSpec::new(
+        Group::Services,
+        &["сервисы с ошибками", "упавшие сервисы", "failed"],
+        "упавшие сервисы",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::FailedServices);
+            tr("Проверяю сервисы...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Services,
+        &["статус", "статус сервиса", "status"],
+        "статус сервиса",
+        |ctx, name| match normalize_unit(name) {
+            Some(unit) => {
+                ctx.tasks
+                    .execute(BackgroundTask::ServiceStatus(unit.clone()));
+                trf("Получаю статус {}...", &[&unit])
+            }
+            None => tr("Укажите корректное имя сервиса. Пример: статус sshd").into(),
+        },
+    )
+    .arg(Arg::Required("сервис"))
+    .values(Values::Services),
+    CommandSpec::new(
+        Group::Services,
+        &["запустить сервис", "запустить службу", "start service"],
+        "запустить",
+        |ctx, name| unit_action(ctx, ServiceAction::Start, name),
+    )
+    .arg(Arg::Required("имя"))
+    .values(Values::Services)
+    .risk(Risk::Confirm),
+    CommandSpec::new(
+        Group::Services,
+        &["остановить сервис", "остановить службу", "stop service"],
+        "остановить",
+        |ctx, name| unit_action(ctx, ServiceAction::Stop, name),
+    )
+    .arg(Arg::Required("имя"))
+    .values(Values::Services)
+    .risk(Risk::Confirm),
+    CommandSpec::new(
+        Group::Services,
+        &[
+            "перезапустить сервис",
+            "перезапустить службу",
+            "restart service",
+        ],
+        "перезапустить",
+        |ctx, name| unit_action(ctx, ServiceAction::Restart, name),
+    )
+    .arg(Arg::Required("имя"))
+    .values(Values::Services)
+    .risk(Risk::Confirm),
+    CommandSpec::new(
+        Group::Services,
+        &["включить сервис", "включить службу", "enable service"],
+        "включить автозапуск",
+        |ctx, name| unit_action(ctx, ServiceAction::Enable, name),
+    )
+    .arg(Arg::Required("имя"))
+    .values(Values::Services)
+    .risk(Risk::Confirm),
+    CommandSpec::new(
+        Group::Services,
+        &[
+            "отключить сервис",
+            "отключить службу",
+            "выключить сервис",
+            "disable service",
+        ],
+        "отключить автозапуск",
+        |ctx, name| unit_action(ctx, ServiceAction::Disable, name),
+    )
+    .arg(Arg::Required("имя"))
+    .values(Values::Services)
+    .risk(Risk::Confirm),
+];
+
+/// "<действие> сервис <имя>"
+fn unit_action(ctx: &mut CommandContext, action: ServiceAction, name: &str) -> String {
+    let Some(unit) = normalize_unit(name) else {
+        return tr("Укажите имя сервиса. Пример: запустить сервис sshd").into();
+    };
+    show_action_dialog(ctx.dialog, action, &unit);
+    trf("Подтвердите действие для {}...", &[&unit])
+}
+
+/// Показывает подтверждение действия над сервисом
+pub fn show_action_dialog(dialog: &mut DialogState, action: ServiceAction, unit: &str) {
+    dialog.show_confirm_task(
+        action.title(),
+        &trf("Выполнить systemctl {} {}?", &[&action.verb(), &unit]),
+        BackgroundTask::ServiceAction(action, unit.to_string()),
+    );
+}
+
+// ============================================================================
+// Функции выполнения (вызываются из фонового потока)
+// ============================================================================
+
+/// Подробный статус сервиса
+pub fn service_status(unit: &str) -> String {
+    match Command::new("systemctl")
+        .args(["status", unit, "--no-pager", "--lines=10"])
+        .output()
+    {
+        // systemctl status возвращает ненулевой код для остановленных сервисов
+        Ok(out) if !out.stdout.is_empty() => String::from_utf8_lossy(&out.stdout).into(),
+        Ok(out) => format!("[X] {}", String::from_utf8_lossy(&out.stderr).trim()),
+        Err(e) => trf("Ошибка systemctl: {}", &[&e]),
+    }
+}
+
+/// Выполняет действие через pkexec
+pub fn execute_action(action: ServiceAction, unit: &str) -> String {
+    match privileged::run("systemctl", &[action.verb(), unit]) {
+        Ok(_) => format!("[OK] systemctl {} {}", action.verb(), unit),
+        Err(e) => format!("[X] systemctl {} {}: {}", action.verb(), unit, e),
+    }
+}
+
+/// Список упавших юнитов (`systemctl --failed`), только чтение
+pub fn failed_units() -> String {
+    match Command::new("systemctl")
+        .args(["--failed", "--plain", "--no-legend", "--no-pager"])
+        .output()
+    {
+        Ok(out) => {
+            let units = parse_list_units(&String::from_utf8_lossy(&out.stdout));
+            if units.is_empty() {
+                tr("упавших сервисов нет").into()
+            } else {
+                units
+                    .iter()
+                    .map(|u| format!("{} ({}/{}) — {}", u.name, u.active, u.sub, u.description))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+        }
+        Err(e) => trf("Ошибка systemctl: {}", &[&e]),
+    }
+}
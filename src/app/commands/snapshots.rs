@@ -0,0 +1,427 @@
+//! Снимки системы (snapper, timeshift или btrfs) и откат
+
+use super::package;
+use super::registry::{Arg, CommandContext, CommandSpec, Group, Risk};
+use crate::app::chat::BackgroundTask;
+use crate::app::constants::{SNAPSHOT_DIR, SNAPSHOT_LIST_MAX, SNAPSHOT_MANUAL_LABEL};
+use crate::app::exec::{self, has_program};
+use crate::app::i18n::{tr, trf};
+use crate::app::monitor::SystemReader;
+use crate::app::privileged;
+use chrono::Local;
+use std::path::Path;
+use std::process::Command;
+
+// ============================================================================
+// Инструменты снимков
+// ============================================================================
+
+/// Чем делаются снимки
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SnapshotTool {
+    Snapper,
+    Timeshift,
+    /// Корень на btrfs без snapper/timeshift — снимки в /.snapshots
+    Btrfs,
+}
+
+/// Снимок системы
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    /// Номер snapper, имя timeshift или имя подтома
+    pub id: String,
+    pub date: String,
+    pub description: String,
+}
+
+impl SnapshotTool {
+    /// Определяет доступный инструмент (snapper → timeshift → btrfs)
+    pub fn detect() -> Option<Self> {
+        if has_program("snapper") && Path::new("/etc/snapper/configs/root").exists() {
+            return Some(Self::Snapper);
+        }
+        if has_program("timeshift") && Path::new("/etc/timeshift/timeshift.json").exists() {
+            return Some(Self::Timeshift);
+        }
+        let root_btrfs = SystemReader::default()
+            .mounts()
+            .iter()
+            .any(|m| m.path == "/" && m.fs_type == "btrfs");
+        (root_btrfs && has_program("btrfs")).then_some(Self::Btrfs)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Snapper => "snapper",
+            Self::Timeshift => "timeshift",
+            Self::Btrfs => "btrfs",
+        }
+    }
+
+    /// pacman-хук, который уже делает снимки при обновлении
+    fn pacman_hook(&self) -> Option<&'static str> {
+        match self {
+            Self::Snapper => Some("snap-pac"),
+            Self::Timeshift => Some("timeshift-autosnap"),
+            Self::Btrfs => None,
+        }
+    }
+
+    /// Список снимков (старые первыми). Без `escalate` пароль root не
+    /// запрашивается — так список читает инструмент ИИ
+    pub fn list(&self, escalate: bool) -> Result<Vec<Snapshot>, String> {
+        match self {
+            Self::Snapper => {
+                let args = ["-c", "root", "list", "--columns", "number,date,description"];
+                // Без ALLOW_USERS в конфиге snapper требует root
+                let output = match exec::run("snapper", &args) {
+                    Ok(output) => output,
+                    Err(_) if escalate => privileged::run("snapper", &args)?,
+                    Err(_) => return Err(tr(REQUIRES_ROOT).into()),
+                };
+                Ok(parse_snapper_list(&output))
+            }
+            Self::Timeshift => {
+                let args = ["--list"];
+                let output = if escalate {
+                    privileged::run("timeshift", &args)?
+                } else {
+                    exec::run("timeshift", &args).map_err(|_| tr(REQUIRES_ROOT).to_string())?
+                };
+                Ok(parse_timeshift_list(&output))
+            }
+            Self::Btrfs => {
+                let mut snapshots: Vec<Snapshot> = std::fs::read_dir(SNAPSHOT_DIR)
+                    .map_err(|e| format!("{}: {}", SNAPSHOT_DIR, e))?
+                    .flatten()
+                    .filter_map(|e| parse_btrfs_name(&e.file_name().to_string_lossy()))
+                    .collect();
+                snapshots.sort_by(|a, b| a.id.cmp(&b.id));
+                Ok(snapshots)
+            }
+        }
+    }
+
+    /// Создаёт снимок с описанием, возвращает его идентификатор
+    pub fn create(&self, label: &str) -> Result<String, String> {
+        match self {
+            Self::Snapper => privileged::run(
+                "snapper",
+                &[
+                    "-c",
+                    "root",
+                    "create",
+                    "--cleanup-algorithm",
+                    "number",
+                    "--print-number",
+                    "--description",
+                    label,
+                ],
+            )
+            .map(|out| format!("#{}", out.trim())),
+            Self::Timeshift => privileged::run(
+                "timeshift",
+                &["--create", "--comments", label, "--tags", "O"],
+            )
+            .map(|_| label.to_string()),
+            Self::Btrfs => {
+                if !Path::new(SNAPSHOT_DIR).exists() {
+                    privileged::run("mkdir", &["-p", SNAPSHOT_DIR])?;
+                }
+                let name = btrfs_name(label);
+                let path = format!("{}/{}", SNAPSHOT_DIR, name);
+                privileged::run("btrfs", &["subvolume", "snapshot", "-r", "/", &path])?;
+                Ok(name)
+            }
+        }
+    }
+
+    /// Инструкция по откату
+    pub fn rollback_help(&self) -> String {
+        match self {
+            Self::Snapper => tr("Откат через snapper:\n\
+                 1. Посмотрите номера: снимки\n\
+                 2. Откатитесь: откатить снимок <номер> (sudo snapper rollback <номер>)\n\
+                 3. Перезагрузите компьютер\n\
+                 Если система не загружается — выберите снимок в меню загрузчика (grub-btrfs)")
+            .into(),
+            Self::Timeshift => tr("Откат через timeshift:\n\
+                 1. Посмотрите снимки: снимки\n\
+                 2. Откатитесь: откатить снимок <имя> (sudo timeshift --restore --snapshot <имя>)\n\
+                 3. Перезагрузите компьютер\n\
+                 Если система не загружается — загрузитесь с live-USB и запустите timeshift --restore")
+            .into(),
+            Self::Btrfs => trf(
+                "Снимки btrfs лежат в {}. Для отката загрузитесь с live-USB, \
+                 смонтируйте корневой btrfs и замените подтом корня снимком:\n\
+                 btrfs subvolume snapshot {}/<снимок> <новый корень>\n\
+                 затем укажите его в rootflags=subvol=... загрузчика или через btrfs subvolume set-default",
+                &[&SNAPSHOT_DIR, &SNAPSHOT_DIR],
+            ),
+        }
+    }
+
+    /// Команда отката для терминала
+    fn rollback_command(&self, id: &str) -> Option<String> {
+        match self {
+            Self::Snapper => Some(format!("sudo snapper rollback {}", id)),
+            Self::Timeshift => Some(format!("sudo timeshift --restore --snapshot '{}'", id)),
+            Self::Btrfs => None,
+        }
+    }
+}
+
+fn is_package_installed(name: &str) -> bool {
+    Command::new("pacman")
+        .args(["-Q", name])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+// ============================================================================
+// Разбор вывода
+// ============================================================================
+
+/// Таблица `snapper list` (разделители `|` или `│`), без снимка 0 (текущая система)
+pub fn parse_snapper_list(output: &str) -> Vec<Snapshot> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split(['|', '│']).map(str::trim).collect();
+            let [number, date, description] = fields[..] else {
+                return None;
+            };
+            // Текущий и активный снимки помечаются * - +
+            let number = number.trim_end_matches(['*', '-', '+']);
+            number
+                .parse::<u32>()
+                .ok()
+                .filter(|n| *n > 0)
+                .map(|_| Snapshot {
+                    id: number.to_string(),
+                    date: date.to_string(),
+                    description: description.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Строки `timeshift --list`: "0    >  2024-01-10_12-00-01  O     описание"
+pub fn parse_timeshift_list(output: &str) -> Vec<Snapshot> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace().peekable();
+            parts.next()?.parse::<u32>().ok()?;
+            if parts.peek() == Some(&">") {
+                parts.next();
+            }
+            let name = parts.next()?;
+            let rest: Vec<&str> = parts.collect();
+            // Первое слово после имени — теги (O, B, H, D, W, M)
+            let description = match rest.split_first() {
+                Some((tags, desc)) if tags.chars().all(|c| "OBHDWM".contains(c)) => desc.join(" "),
+                _ => rest.join(" "),
+            };
+            Some(Snapshot {
+                id: name.to_string(),
+                date: name.replacen('_', " ", 1),
+                description,
+            })
+        })
+        .collect()
+}
+
+/// Имя подтома: alfons-20240110-120001-описание
+fn btrfs_name(label: &str) -> String {
+    let slug: Vec<&str> = label
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    format!(
+        "alfons-{}-{}",
+        Local::now().format("%Y%m%d-%H%M%S"),
+        slug.join("-")
+    )
+}
+
+fn parse_btrfs_name(name: &str) -> Option<Snapshot> {
+    let rest = name.strip_prefix("alfons-")?;
+    let date = rest.get(..15)?;
+    let date = chrono::NaiveDateTime::parse_from_str(date, "%Y%m%d-%H%M%S").ok()?;
+    Some(Snapshot {
+        id: name.to_string(),
+        date: date.format("%Y-%m-%d %H:%M:%S").to_string(),
+        description: rest.get(16..).unwrap_or_default().replace('-', " "),
+    })
+}
+
+// ============================================================================
+// Команды
+// ============================================================================
+
+/// Команды снимков
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::Snapshots,
+        &["снимки", "снапшоты", "snapshots", "список снимков"],
+        "список снимков",
+        |ctx, _| {
+            ctx.tasks.execute(BackgroundTask::ListSnapshots);
+            tr("Получаю список снимков...").into()
+        },
+    ),
+    CommandSpec::new(
+        Group::Snapshots,
+        &["создать снимок", "сделать снимок", "create snapshot"],
+        "снимок системы",
+        |ctx, label| {
+            let label = match label {
+                "" => tr(SNAPSHOT_MANUAL_LABEL).to_string(),
+                l => l.to_string(),
+            };
+            // Снимок создаётся с правами root — только после подтверждения
+            ctx.dialog.show_confirm_task(
+                "Создание снимка",
+                &trf("Создать снимок системы «{}»?", &[&label]),
+                BackgroundTask::CreateSnapshot(label),
+            );
+            tr("Подтвердите создание снимка...").into()
+        },
+    )
+    .arg(Arg::Optional("описание"))
+    .risk(Risk::Confirm),
+    CommandSpec::new(
+        Group::Snapshots,
+        &[
+            "откат",
+            "откатить систему",
+            "как откатить",
+            "откатить обновление",
+            "rollback",
+        ],
+        "как откатить систему",
+        |_, _| match SnapshotTool::detect() {
+            Some(tool) => tool.rollback_help(),
+            None => tr(NO_SNAPSHOTS).into(),
+        },
+    ),
+    CommandSpec::new(
+        Group::Snapshots,
+        &["откатить снимок", "восстановить снимок", "restore snapshot"],
+        "откатить систему к снимку",
+        rollback,
+    )
+    .arg(Arg::Required("номер|имя"))
+    .risk(Risk::Critical),
+];
+
+/// "откатить снимок <номер|имя>"
+fn rollback(ctx: &mut CommandContext, id: &str) -> String {
+    // Идентификатор попадает в команду терминала
+    if !id
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_".contains(c))
+    {
+        return tr("Укажите номер или имя снимка из списка: снимки").into();
+    }
+    let Some(tool) = SnapshotTool::detect() else {
+        return tr(NO_SNAPSHOTS).into();
+    };
+    if tool.rollback_command(id).is_none() {
+        return tool.rollback_help();
+    }
+    ctx.dialog.show_confirm_task(
+        "Откат системы",
+        &trf(
+            "Откатить систему к снимку {} через {}?\nПосле отката потребуется перезагрузка.",
+            &[&id, &tool.name()],
+        ),
+        BackgroundTask::RollbackSnapshot(id.to_string()),
+    );
+    tr("Подтвердите откат...").into()
+}
+
+const REQUIRES_ROOT: &str = "нужны права root, выполните команду «снимки»";
+
+const NO_SNAPSHOTS: &str = "Снимки недоступны: нет snapper/timeshift, а корень не на btrfs. \
+                            Настройка: гайд backup";
+
+// ============================================================================
+// Функции выполнения (вызываются из фонового потока)
+// ============================================================================
+
+/// Список снимков по команде пользователя (при необходимости — через pkexec)
+pub fn list_snapshots() -> String {
+    format_snapshots(true)
+}
+
+/// Список снимков для инструмента ИИ: без запроса прав root
+pub fn snapshots_summary() -> String {
+    format_snapshots(false)
+}
+
+fn format_snapshots(escalate: bool) -> String {
+    let Some(tool) = SnapshotTool::detect() else {
+        return tr(NO_SNAPSHOTS).into();
+    };
+    match tool.list(escalate) {
+        Ok(list) if list.is_empty() => trf("Снимков {} пока нет", &[&tool.name()]),
+        Ok(list) => {
+            let skip = list.len().saturating_sub(SNAPSHOT_LIST_MAX);
+            let lines: Vec<String> = list[skip..]
+                .iter()
+                .map(|s| format!("  {} — {} {}", s.id, s.date, s.description))
+                .collect();
+            trf(
+                "Снимки {} (последние {}):\n{}",
+                &[&tool.name(), &lines.len(), &lines.join("\n")],
+            )
+        }
+        Err(e) => trf("[X] Не удалось получить снимки {}: {}", &[&tool.name(), &e]),
+    }
+}
+
+/// Создаёт снимок по запросу пользователя
+pub fn create_snapshot(label: &str) -> String {
+    let Some(tool) = SnapshotTool::detect() else {
+        return tr(NO_SNAPSHOTS).into();
+    };
+    match tool.create(label) {
+        Ok(id) => trf("[OK] Снимок {} создан ({})", &[&id, &tool.name()]),
+        Err(e) => trf("[X] Не удалось создать снимок: {}", &[&e]),
+    }
+}
+
+/// Снимок перед обновлением или пакетной установкой.
+/// `Ok(None)` — снимок не нужен (нет инструмента или его делает pacman-хук).
+pub fn pre_upgrade_snapshot(action: &str) -> Result<Option<String>, String> {
+    let Some(tool) = SnapshotTool::detect() else {
+        return Ok(None);
+    };
+    if tool.pacman_hook().is_some_and(is_package_installed) {
+        return Ok(None);
+    }
+    let label = trf("Альфонс: перед «{}»", &[&action]);
+    tool.create(&label).map(|id| {
+        Some(trf(
+            "[OK] Снимок {} создан ({}). Откат: откат",
+            &[&id, &tool.name()],
+        ))
+    })
+}
+
+/// Запускает откат в терминале (snapper/timeshift спрашивают подтверждения)
+pub fn rollback_snapshot(id: &str) -> String {
+    let Some(tool) = SnapshotTool::detect() else {
+        return tr(NO_SNAPSHOTS).into();
+    };
+    match tool.rollback_command(id) {
+        Some(cmd) => package::run_in_terminal(
+            &trf("{} && echo 'Перезагрузите компьютер'", &[&cmd]),
+            tr("Откат системы"),
+        ),
+        None => tool.rollback_help(),
+    }
+}
@@ -1,55 +1,577 @@
-//! Системные команды (выключение, перезагрузка)
+//! Системные команды: выключение, перезагрузка, сон, блокировка, выход и их таймеры
 
-use crate::app::chat::DialogState;
+use super::registry::{Arg, CommandContext, CommandSpec, Group, Risk};
+use crate::app::chat::BackgroundTask;
+use crate::app::constants::{CONFIG_APP_NAME, POWER_MISSED_GRACE_SECS};
+use crate::app::i18n::{tr, trf};
+use chrono::{DateTime, Duration, Local, NaiveTime, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::process::Command;
+use std::sync::{Mutex, OnceLock};
 
-/// Обработка системных команд (показывает диалог подтверждения)
-pub fn process_system_command(cmd: &str, dialog: &mut DialogState) -> Option<String> {
-    match cmd {
-        "выключить пк" | "выключить компьютер" => {
-            dialog.show_confirm(
-                "Выключение компьютера",
-                "Вы уверены, что хотите выключить компьютер?",
-                "__shutdown__",
-            );
-            Some("Подтвердите выключение...".into())
-        }
-        "перезагрузить" | "рестарт" => {
-            dialog.show_confirm(
-                "Перезагрузка",
-                "Вы уверены, что хотите перезагрузить компьютер?",
-                "__reboot__",
-            );
-            Some("Подтвердите перезагрузку...".into())
-        }
-        _ => None,
-    }
-}
-
-/// Выполнить выключение (вызывается после подтверждения)
-pub fn execute_shutdown() -> String {
-    let (prog, args) = if cfg!(windows) {
-        ("shutdown", vec!["/s", "/t", "0"])
+// ============================================================================
+// Действия питания
+// ============================================================================
+
+/// Действие питания или сеанса
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PowerAction {
+    Shutdown,
+    Reboot,
+    Suspend,
+    Hibernate,
+    Lock,
+    Logout,
+}
+
+impl PowerAction {
+    /// Название для заголовков и шапки
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Shutdown => tr("Выключение"),
+            Self::Reboot => tr("Перезагрузка"),
+            Self::Suspend => tr("Спящий режим"),
+            Self::Hibernate => tr("Гибернация"),
+            Self::Lock => tr("Блокировка"),
+            Self::Logout => tr("Выход из системы"),
+        }
+    }
+
+    /// Действие в форме вопроса ("выключить компьютер")
+    fn verb(&self) -> &'static str {
+        match self {
+            Self::Shutdown => tr("выключить компьютер"),
+            Self::Reboot => tr("перезагрузить компьютер"),
+            Self::Suspend => tr("перевести компьютер в спящий режим"),
+            Self::Hibernate => tr("перевести компьютер в гибернацию"),
+            Self::Lock => tr("заблокировать экран"),
+            Self::Logout => tr("выйти из системы"),
+        }
+    }
+
+    /// Выключение и перезагрузку планирует logind (`shutdown +N`),
+    /// они сработают и при закрытом приложении
+    fn uses_shutdown(&self) -> bool {
+        matches!(self, Self::Shutdown | Self::Reboot)
+    }
+
+    /// Программа и аргументы для немедленного выполнения
+    fn command(&self) -> (&'static str, Vec<String>) {
+        match self {
+            Self::Shutdown => ("shutdown", vec!["-h".into(), "now".into()]),
+            Self::Reboot => ("shutdown", vec!["-r".into(), "now".into()]),
+            Self::Suspend => ("systemctl", vec!["suspend".into()]),
+            Self::Hibernate => ("systemctl", vec!["hibernate".into()]),
+            Self::Lock => ("loginctl", vec!["lock-session".into()]),
+            Self::Logout => match std::env::var("XDG_SESSION_ID") {
+                Ok(id) => ("loginctl", vec!["terminate-session".into(), id]),
+                Err(_) => (
+                    "loginctl",
+                    vec![
+                        "terminate-user".into(),
+                        std::env::var("USER").unwrap_or_default(),
+                    ],
+                ),
+            },
+        }
+    }
+}
+
+// ============================================================================
+// Расписание (одно действие, сохраняется между запусками)
+// ============================================================================
+
+/// Запланированное действие
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct ScheduledPower {
+    pub action: PowerAction,
+    /// Время срабатывания (unix, секунды)
+    pub at: i64,
+}
+
+impl ScheduledPower {
+    /// Секунд до срабатывания (0, если время прошло)
+    pub fn remaining_secs(&self) -> i64 {
+        (self.at - Local::now().timestamp()).max(0)
+    }
+
+    pub fn time(&self) -> String {
+        Local
+            .timestamp_opt(self.at, 0)
+            .single()
+            .map(|t| t.format("%H:%M").to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Текущее расписание, загружается с диска при первом обращении
+fn schedule() -> &'static Mutex<Option<ScheduledPower>> {
+    static SCHEDULE: OnceLock<Mutex<Option<ScheduledPower>>> = OnceLock::new();
+    SCHEDULE.get_or_init(|| Mutex::new(load_schedule()))
+}
+
+fn schedule_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|p| p.join(CONFIG_APP_NAME).join("power_schedule.json"))
+}
+
+fn load_schedule() -> Option<ScheduledPower> {
+    let content = std::fs::read_to_string(schedule_path()?).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_schedule(scheduled: Option<ScheduledPower>) {
+    let Some(path) = schedule_path() else {
+        return;
+    };
+    match scheduled {
+        Some(s) => {
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Ok(json) = serde_json::to_string(&s) {
+                let _ = std::fs::write(path, json);
+            }
+        }
+        None => {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn set_schedule(scheduled: Option<ScheduledPower>) {
+    if let Ok(mut s) = schedule().lock() {
+        *s = scheduled;
+    }
+    save_schedule(scheduled);
+}
+
+/// Запланированное действие (для шапки)
+pub fn current_schedule() -> Option<ScheduledPower> {
+    schedule().lock().ok().and_then(|s| *s)
+}
+
+/// Снимает наступившее расписание и возвращает действие, которое должно
+/// выполнить приложение. Выключение и перезагрузку выполняет logind, а
+/// сильно просроченные действия (приложение было закрыто) пропускаются.
+pub fn take_due_schedule() -> Option<PowerAction> {
+    let due = current_schedule().filter(|s| s.remaining_secs() == 0)?;
+    set_schedule(None);
+
+    let late = Local::now().timestamp() - due.at;
+    (!due.action.uses_shutdown() && late <= POWER_MISSED_GRACE_SECS).then_some(due.action)
+}
+
+// ============================================================================
+// Разбор времени
+// ============================================================================
+
+/// Разбирает "через 30 минут", "через 2 часа", "через полчаса", "в 23:00"
+/// и английские "in 30 minutes", "in half an hour", "at 23:00"
+fn parse_when(when: &str, now: DateTime<Local>) -> Option<DateTime<Local>> {
+    if let Some(delay) = when
+        .strip_prefix("через ")
+        .or_else(|| when.strip_prefix("in "))
+    {
+        return parse_delay(delay.trim()).map(|d| now + d);
+    }
+
+    let time = when
+        .strip_prefix("в ")
+        .or_else(|| when.strip_prefix("at "))?
+        .trim();
+    let time = NaiveTime::parse_from_str(time, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(&format!("{}:00", time), "%H:%M"))
+        .ok()?;
+    let today = Local
+        .from_local_datetime(&now.date_naive().and_time(time))
+        .earliest()?;
+    // Прошедшее время — завтра
+    Some(if today <= now {
+        today + Duration::days(1)
     } else {
-        ("shutdown", vec!["-h", "now"])
+        today
+    })
+}
+
+/// Длительность: "30 минут", "2 часа", "1 ч", "90 мин", "полчаса", "час", "45",
+/// "30 minutes", "2 hours", "half an hour", "an hour"
+fn parse_delay(delay: &str) -> Option<Duration> {
+    match delay {
+        "полчаса" | "half an hour" => return Some(Duration::minutes(30)),
+        "час" | "an hour" => return Some(Duration::hours(1)),
+        _ => {}
+    }
+
+    let split = delay
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(delay.len());
+    let amount: i64 = delay[..split].parse().ok().filter(|n| *n > 0)?;
+    let unit = delay[split..].trim();
+
+    if unit.is_empty()
+        || unit.starts_with("мин")
+        || unit.starts_with("min")
+        || unit == "м"
+        || unit == "m"
+    {
+        Some(Duration::minutes(amount))
+    } else if unit.starts_with("час") || unit.starts_with("hour") || unit == "ч" || unit == "h"
+    {
+        Some(Duration::hours(amount))
+    } else {
+        None
+    }
+}
+
+/// Оставшееся время для шапки: "5:07" или "1:05:07"
+pub fn format_countdown(secs: i64) -> String {
+    let (h, m, s) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+// ============================================================================
+// Команды
+// ============================================================================
+
+/// Необязательное время таймера
+const WHEN: Arg = Arg::Optional("через 30 минут | в 23:00");
+
+/// Аргумент — время таймера. Иначе ввод вроде «выключить wifi»
+/// относится к другой команде или к ИИ
+fn is_when(arg: &str) -> bool {
+    parse_when(arg, Local::now()).is_some()
+}
+
+/// Команды питания и сеанса (с диалогом подтверждения)
+pub const COMMANDS: &[CommandSpec] = &[
+    CommandSpec::new(
+        Group::System,
+        &[
+            "выключить пк",
+            "выключить компьютер",
+            "выключить",
+            "выключение",
+            "shutdown",
+            "power off",
+        ],
+        "выключить компьютер (сейчас или по таймеру)",
+        |ctx, when| power(ctx, PowerAction::Shutdown, when),
+    )
+    .arg(WHEN)
+    .accepts(is_when)
+    .risk(Risk::Critical),
+    CommandSpec::new(
+        Group::System,
+        &["перезагрузить", "перезагрузка", "рестарт", "reboot"],
+        "перезагрузить компьютер",
+        |ctx, when| power(ctx, PowerAction::Reboot, when),
+    )
+    .arg(WHEN)
+    .accepts(is_when)
+    .risk(Risk::Critical),
+    CommandSpec::new(
+        Group::System,
+        &["спящий режим", "сон", "усыпить", "suspend"],
+        "спящий режим",
+        |ctx, when| power(ctx, PowerAction::Suspend, when),
+    )
+    .arg(WHEN)
+    .accepts(is_when)
+    .risk(Risk::Confirm),
+    CommandSpec::new(
+        Group::System,
+        &["гибернация", "hibernate"],
+        "гибернация",
+        |ctx, when| power(ctx, PowerAction::Hibernate, when),
+    )
+    .arg(WHEN)
+    .accepts(is_when)
+    .risk(Risk::Confirm),
+    CommandSpec::new(
+        Group::System,
+        &[
+            "заблокировать экран",
+            "заблокировать",
+            "блокировка экрана",
+            "lock",
+        ],
+        "заблокировать экран",
+        |ctx, when| power(ctx, PowerAction::Lock, when),
+    )
+    .arg(WHEN)
+    .accepts(is_when),
+    CommandSpec::new(
+        Group::System,
+        &[
+            "выйти из системы",
+            "выход из системы",
+            "завершить сеанс",
+            "logout",
+        ],
+        "завершить сеанс",
+        |ctx, when| power(ctx, PowerAction::Logout, when),
+    )
+    .arg(WHEN)
+    .accepts(is_when)
+    .risk(Risk::Critical),
+    CommandSpec::new(
+        Group::System,
+        &[
+            "отменить таймер",
+            "отмена таймера",
+            "отменить выключение",
+            "отменить перезагрузку",
+            "отмена выключения",
+            "cancel timer",
+        ],
+        "отменить запланированное действие",
+        |ctx, _| {
+            if current_schedule().is_none() {
+                return tr("Нет запланированных действий").into();
+            }
+            ctx.tasks.execute(BackgroundTask::CancelPowerSchedule);
+            tr("Отменяю...").into()
+        },
+    ),
+];
+
+/// Действие питания сейчас или по таймеру ("через 30 минут", "в 23:00")
+fn power(ctx: &mut CommandContext, action: PowerAction, when: &str) -> String {
+    if when.is_empty() {
+        if action == PowerAction::Lock {
+            ctx.tasks.execute(BackgroundTask::Power(action));
+            return tr("Блокирую экран...").into();
+        }
+        ctx.dialog.show_confirm_task(
+            action.label(),
+            &trf("Вы уверены, что хотите {}?", &[&action.verb()]),
+            BackgroundTask::Power(action),
+        );
+        return tr("Подтвердите действие...").into();
+    }
+
+    let now = Local::now();
+    let Some(at) = parse_when(when, now) else {
+        return tr("Не понял время. Примеры: через 30 минут, через 2 часа, в 23:00").into();
     };
 
-    match Command::new(prog).args(&args).status() {
-        Ok(_) => "Выключение...".into(),
-        Err(e) => format!("Ошибка: {}", e),
+    let mut message = format!(
+        "{} {} ({})?",
+        capitalize(action.verb()),
+        if when.starts_with("через") || when.starts_with("in ") {
+            when.to_string()
+        } else {
+            trf("в {}", &[&at.format("%H:%M")])
+        },
+        at.format("%d.%m %H:%M")
+    );
+    if let Some(old) = current_schedule() {
+        message.push_str(&trf(
+            "\nЗаменит: {} в {}",
+            &[&old.action.label().to_lowercase(), &old.time()],
+        ));
     }
+    ctx.dialog.show_confirm_task(
+        &trf("{} по таймеру", &[&action.label()]),
+        &message,
+        BackgroundTask::SchedulePower(action, at.timestamp()),
+    );
+    tr("Подтвердите таймер...").into()
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
 }
 
-/// Выполнить перезагрузку (вызывается после подтверждения)
-pub fn execute_reboot() -> String {
-    let (prog, args) = if cfg!(windows) {
-        ("shutdown", vec!["/r", "/t", "0"])
+// ============================================================================
+// Функции выполнения (вызываются из фонового потока)
+// ============================================================================
+
+/// Выполнить действие сейчас (вызывается после подтверждения)
+pub fn execute_power(action: PowerAction) -> String {
+    let (program, args) = action.command();
+    match Command::new(program).args(&args).output() {
+        Ok(out) if out.status.success() => format!("{}...", action.label()),
+        Ok(out) => format!(
+            "[X] {}: {}",
+            action.label(),
+            String::from_utf8_lossy(&out.stderr).trim()
+        ),
+        Err(e) => format!("[X] {}: {}", action.label(), e),
+    }
+}
+
+/// Планирует действие на время `at` (unix, секунды), заменяя прежнее
+pub fn schedule_power(action: PowerAction, at: i64) -> String {
+    // Новый `shutdown +N` сам заменяет прежний, иначе прежний нужно снять
+    let old_shutdown = current_schedule().is_some_and(|s| s.action.uses_shutdown());
+    if old_shutdown && !action.uses_shutdown() {
+        let _ = cancel_shutdown();
+    }
+
+    if action.uses_shutdown() {
+        // shutdown принимает минуты; при "+0" выключит сразу
+        let minutes = ((at - Local::now().timestamp()) as f64 / 60.0)
+            .ceil()
+            .max(1.0) as i64;
+        let flag = if action == PowerAction::Reboot {
+            "-r"
+        } else {
+            "-h"
+        };
+        let output = Command::new("shutdown")
+            .args([flag, &format!("+{}", minutes)])
+            .output();
+        match output {
+            Ok(out) if out.status.success() => {}
+            Ok(out) => {
+                return format!(
+                    "[X] {}: {}",
+                    action.label(),
+                    String::from_utf8_lossy(&out.stderr).trim()
+                )
+            }
+            Err(e) => return format!("[X] {}: {}", action.label(), e),
+        }
+    }
+
+    let scheduled = ScheduledPower { action, at };
+    set_schedule(Some(scheduled));
+    trf(
+        "[OK] {} запланировано на {}. Отменить: «отменить таймер» или кнопка в шапке",
+        &[&action.label(), &scheduled.time()],
+    )
+}
+
+fn cancel_shutdown() -> Result<(), String> {
+    let out = Command::new("shutdown")
+        .arg("-c")
+        .output()
+        .map_err(|e| e.to_string())?;
+    if out.status.success() {
+        Ok(())
     } else {
-        ("shutdown", vec!["-r", "now"])
+        Err(String::from_utf8_lossy(&out.stderr).trim().into())
+    }
+}
+
+/// Отменяет запланированное действие
+pub fn cancel_schedule() -> String {
+    let Some(scheduled) = current_schedule() else {
+        return tr("Нет запланированных действий").into();
     };
+    if scheduled.action.uses_shutdown() {
+        if let Err(e) = cancel_shutdown() {
+            return trf("[X] Не удалось отменить таймер: {}", &[&e]);
+        }
+    }
+    set_schedule(None);
+    trf("[OK] {} отменено", &[&scheduled.action.label()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::commands::registry;
+
+    fn now() -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(2026, 10, 19, 20, 0, 0)
+            .single()
+            .expect("valid time")
+    }
+
+    #[test]
+    fn parses_russian_times() {
+        let now = now();
+        assert_eq!(
+            parse_when("через 30 минут", now),
+            Some(now + Duration::minutes(30))
+        );
+        assert_eq!(
+            parse_when("через 2 часа", now),
+            Some(now + Duration::hours(2))
+        );
+        assert_eq!(
+            parse_when("через полчаса", now),
+            Some(now + Duration::minutes(30))
+        );
+        assert_eq!(
+            parse_when("в 23:00", now).map(|t| t.format("%d %H:%M").to_string()),
+            Some("19 23:00".into())
+        );
+        // Прошедшее время — завтра
+        assert_eq!(
+            parse_when("в 7", now).map(|t| t.format("%d %H:%M").to_string()),
+            Some("20 07:00".into())
+        );
+    }
+
+    #[test]
+    fn parses_english_times() {
+        let now = now();
+        assert_eq!(
+            parse_when("in 30 minutes", now),
+            Some(now + Duration::minutes(30))
+        );
+        assert_eq!(
+            parse_when("in 1 minute", now),
+            Some(now + Duration::minutes(1))
+        );
+        assert_eq!(
+            parse_when("in 2 hours", now),
+            Some(now + Duration::hours(2))
+        );
+        assert_eq!(parse_when("in 45m", now), Some(now + Duration::minutes(45)));
+        assert_eq!(
+            parse_when("in half an hour", now),
+            Some(now + Duration::minutes(30))
+        );
+        assert_eq!(
+            parse_when("in an hour", now),
+            Some(now + Duration::hours(1))
+        );
+        assert_eq!(
+            parse_when("at 23:00", now).map(|t| t.format("%d %H:%M").to_string()),
+            Some("19 23:00".into())
+        );
+    }
+
+    #[test]
+    fn rejects_non_times() {
+        let now = now();
+        assert_eq!(parse_when("wifi", now), None);
+        assert_eq!(parse_when("через неделю", now), None);
+        assert_eq!(parse_when("in a while", now), None);
+        assert_eq!(parse_when("at noon", now), None);
+    }
 
-    match Command::new(prog).args(&args).status() {
-        Ok(_) => "Перезагрузка...".into(),
-        Err(e) => format!("Ошибка: {}", e),
+    #[test]
+    fn power_commands_take_only_times() {
+        let phrase = |input| registry::parse(input).map(|(spec, arg)| (spec.phrases[0], arg));
+        assert_eq!(phrase("выключить"), Some(("выключить пк", "")));
+        assert_eq!(
+            phrase("выключить через 30 минут"),
+            Some(("выключить пк", "через 30 минут"))
+        );
+        assert_eq!(
+            phrase("shutdown in 30 minutes"),
+            Some(("выключить пк", "in 30 minutes"))
+        );
+        assert_eq!(
+            phrase("reboot at 23:00"),
+            Some(("перезагрузить", "at 23:00"))
+        );
+        assert_eq!(phrase("выключить звук"), Some(("выключи звук", "")));
+        assert_eq!(phrase("выключить bluetooth"), None);
     }
 }
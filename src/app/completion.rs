@@ -0,0 +1,235 @@
+//! Автодополнение команд в поле ввода
+//!
+//! Подсказки берутся из реестра команд, а аргументы — из гайдов,
+//! пакетов (репозитории и установленные) и сервисов systemd. Списки пакетов
+//! и сервисов загружаются в фоне при запуске.
+
+use super::commands::registry::{self, Arg, CommandSpec, Values};
+use super::commands::services;
+use super::constants::COMPLETION_MAX_SUGGESTIONS;
+use super::i18n::tr;
+use std::process::Command;
+use std::sync::Mutex;
+
+// ============================================================================
+// Имена пакетов и сервисов
+// ============================================================================
+
+/// Значения аргументов, загруженные из системы
+struct Names {
+    packages: Vec<String>,
+    installed: Vec<String>,
+    services: Vec<String>,
+}
+
+static NAMES: Mutex<Names> = Mutex::new(Names {
+    packages: Vec::new(),
+    installed: Vec::new(),
+    services: Vec::new(),
+});
+
+/// Строки вывода команды (пустой список, если она недоступна)
+fn output_lines(program: &str, args: &[&str]) -> Vec<String> {
+    Command::new(program)
+        .args(args)
+        .output()
+        .map(|out| {
+            String::from_utf8_lossy(&out.stdout)
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Сервисы без суффикса `.service`; шаблоны `name@` пропускаются
+fn service_names() -> Vec<String> {
+    let output = output_lines(
+        "systemctl",
+        &[
+            "list-unit-files",
+            "--type=service",
+            "--no-legend",
+            "--no-pager",
+        ],
+    )
+    .join("\n");
+    let mut names: Vec<String> = services::parse_unit_files(&output)
+        .into_iter()
+        .filter_map(|(name, _)| name.strip_suffix(".service").map(str::to_string))
+        .filter(|name| !name.ends_with('@'))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Загружает списки пакетов и сервисов в фоновом потоке
+pub fn preload() {
+    std::thread::spawn(|| {
+        let packages = output_lines("pacman", &["-Slq"]);
+        let installed = output_lines("pacman", &["-Qq"]);
+        let services = service_names();
+        if let Ok(mut names) = NAMES.lock() {
+            *names = Names {
+                packages,
+                installed,
+                services,
+            };
+        }
+    });
+}
+
+// ============================================================================
+// Подбор подсказок
+// ============================================================================
+
+/// Подсказка в списке автодополнения
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    /// Текст поля ввода после выбора подсказки
+    pub text: String,
+    /// Подпись в списке
+    pub label: String,
+    /// Аргументы и описание команды
+    pub hint: String,
+}
+
+/// Нечёткое совпадение: `query` — подпоследовательность `candidate`.
+/// Меньше — лучше: начало строки, затем подстрока, затем с пропусками
+pub fn fuzzy_score(candidate: &str, query: &str) -> Option<usize> {
+    if query.is_empty() {
+        return Some(0);
+    }
+    if candidate.starts_with(query) {
+        return Some(candidate.chars().count() - query.chars().count());
+    }
+    if let Some(pos) = candidate.find(query) {
+        return Some(1000 + pos);
+    }
+
+    let mut gaps = 0;
+    let mut chars = candidate.chars();
+    for q in query.chars() {
+        loop {
+            match chars.next() {
+                Some(c) if c == q => break,
+                Some(_) => gaps += 1,
+                None => return None,
+            }
+        }
+    }
+    Some(10_000 + gaps)
+}
+
+/// Подсказки для введённого текста: команды и значения их аргументов
+pub fn suggest<'a>(input: &str, guides: impl Iterator<Item = &'a str>) -> Vec<Suggestion> {
+    let query = input.trim_start().to_lowercase();
+    if query.trim().is_empty() {
+        return Vec::new();
+    }
+    let guides: Vec<String> = guides.map(str::to_string).collect();
+    let mut scored: Vec<(usize, Suggestion)> = Vec::new();
+
+    for command in registry::all() {
+        // Команда уже набрана — подсказываем аргумент
+        if let Some(arg) = query
+            .strip_prefix(command.phrase())
+            .and_then(|rest| rest.strip_prefix(' '))
+        {
+            scored.extend(argument_suggestions(command, arg.trim_start(), &guides));
+            continue;
+        }
+
+        let Some(score) = fuzzy_score(command.phrase(), query.trim_end()) else {
+            continue;
+        };
+        let no_arg = command.arg == Arg::None;
+        if command.phrase() == query && no_arg {
+            continue;
+        }
+        let text = if no_arg {
+            command.phrase().to_string()
+        } else {
+            format!("{} ", command.phrase())
+        };
+        scored.push((
+            score,
+            Suggestion {
+                text,
+                label: command.phrase().to_string(),
+                hint: describe(command),
+            },
+        ));
+    }
+
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.label.cmp(&b.1.label)));
+    scored
+        .into_iter()
+        .map(|(_, s)| s)
+        .take(COMPLETION_MAX_SUGGESTIONS)
+        .collect()
+}
+
+fn describe(command: &CommandSpec) -> String {
+    match command.arg {
+        Arg::None => tr(command.description).to_string(),
+        arg => format!("{} — {}", arg.hint(), tr(command.description)),
+    }
+}
+
+/// Значения аргумента команды, подходящие под набранное
+fn argument_suggestions(
+    command: &CommandSpec,
+    arg: &str,
+    guides: &[String],
+) -> Vec<(usize, Suggestion)> {
+    let names = NAMES.lock().ok();
+    let values: Vec<&str> = match command.values {
+        Values::Free => Vec::new(),
+        Values::List(values) => values.to_vec(),
+        Values::Guides => guides.iter().map(String::as_str).collect(),
+        Values::Packages => names
+            .as_ref()
+            .map(|n| n.packages.iter().map(String::as_str).collect())
+            .unwrap_or_default(),
+        Values::Installed => names
+            .as_ref()
+            .map(|n| n.installed.iter().map(String::as_str).collect())
+            .unwrap_or_default(),
+        Values::Services => names
+            .as_ref()
+            .map(|n| n.services.iter().map(String::as_str).collect())
+            .unwrap_or_default(),
+    };
+
+    // Аргумент ещё не начат — показываем, что ожидается
+    if values.is_empty() && arg.is_empty() {
+        return vec![(
+            0,
+            Suggestion {
+                text: format!("{} ", command.phrase()),
+                label: command.usage(),
+                hint: tr(command.description).to_string(),
+            },
+        )];
+    }
+
+    values
+        .into_iter()
+        .filter(|value| *value != arg)
+        .filter_map(|value| {
+            let score = fuzzy_score(&value.to_lowercase(), arg)?;
+            Some((
+                score,
+                Suggestion {
+                    text: format!("{} {}", command.phrase(), value),
+                    label: value.to_string(),
+                    hint: tr(command.description).to_string(),
+                },
+            ))
+        })
+        .collect()
+}
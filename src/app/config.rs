@@ -1,8 +1,10 @@
 //! Конфигурация пользователя
 
 use super::constants::{
-    CONFIG_APP_NAME, DEFAULT_ACCENT_COLOR, DEFAULT_ASSISTANT_NAME, OLLAMA_MODEL,
+    CONFIG_APP_NAME, DEFAULT_ACCENT_COLOR, DEFAULT_ASSISTANT_NAME, DEFAULT_MIRROR_COUNT,
+    DEFAULT_SESSION_LIMIT, OLLAMA_MODEL, SECURITY_FEED_URL,
 };
+use super::i18n::{trf, Language};
 use serde::{Deserialize, Serialize};
 
 /// Настройки приложения (сохраняются на диск)
@@ -12,18 +14,60 @@ pub struct Config {
     pub accent_color: [u8; 3],
     #[serde(default = "default_ollama_model")]
     pub ollama_model: String,
+    /// Страны для ранжирования зеркал (пусто — все)
+    #[serde(default)]
+    pub mirror_countries: Vec<String>,
+    #[serde(default = "default_true")]
+    pub mirror_https_only: bool,
+    #[serde(default = "default_mirror_count")]
+    pub mirror_count: usize,
+    /// Источник данных Arch Security Tracker: URL или путь к JSON-файлу
+    #[serde(default = "default_security_feed")]
+    pub security_feed: String,
+    /// Снимок системы перед обновлением и пакетной установкой
+    #[serde(default = "default_true")]
+    pub snapshot_before_upgrade: bool,
+    /// Сколько сессий чата хранить на диске (старые удаляются)
+    #[serde(default = "default_session_limit")]
+    pub session_limit: usize,
+    /// Язык интерфейса и ответов ИИ (по умолчанию — из LANG)
+    #[serde(default)]
+    pub language: Language,
 }
 
 fn default_ollama_model() -> String {
     OLLAMA_MODEL.to_string()
 }
 
+fn default_true() -> bool {
+    true
+}
+
+fn default_mirror_count() -> usize {
+    DEFAULT_MIRROR_COUNT
+}
+
+fn default_session_limit() -> usize {
+    DEFAULT_SESSION_LIMIT
+}
+
+fn default_security_feed() -> String {
+    SECURITY_FEED_URL.to_string()
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             assistant_name: DEFAULT_ASSISTANT_NAME.to_string(),
             accent_color: DEFAULT_ACCENT_COLOR,
             ollama_model: OLLAMA_MODEL.to_string(),
+            mirror_countries: Vec::new(),
+            mirror_https_only: true,
+            mirror_count: DEFAULT_MIRROR_COUNT,
+            security_feed: SECURITY_FEED_URL.to_string(),
+            snapshot_before_upgrade: true,
+            session_limit: DEFAULT_SESSION_LIMIT,
+            language: Language::from_env(),
         }
     }
 }
@@ -37,7 +81,7 @@ impl Config {
     /// Сохраняет на диск. Возвращает Ok(()) при успехе или сообщение об ошибке.
     pub fn save(&self) -> Result<(), String> {
         confy::store(CONFIG_APP_NAME, "config", self)
-            .map_err(|e| format!("Не удалось сохранить настройки: {}", e))
+            .map_err(|e| trf("Не удалось сохранить настройки: {}", &[&e]))
     }
 
     /// Конвертация для egui
@@ -5,6 +5,8 @@
 
 // === Приложение ===
 pub const APP_NAME: &str = "Альфонс";
+/// Отправитель сообщений пользователя в истории чата
+pub const USER_SENDER: &str = "Вы";
 pub const APP_VERSION: &str = "0.0.5";
 pub const DEFAULT_ASSISTANT_NAME: &str = "Альфонс";
 pub const DEFAULT_ACCENT_COLOR: [u8; 3] = [61, 174, 233]; // Голубой
@@ -19,27 +21,86 @@ pub const OLLAMA_INSTALL_SCRIPT: &str = "https://ollama.com/install.sh";
 // === Yay (AUR) ===
 pub const YAY_INSTALL_DIR: &str = "/tmp/yay-install";
 pub const YAY_AUR_URL: &str = "https://aur.archlinux.org/yay.git";
+pub const AUR_RPC_URL: &str = "https://aur.archlinux.org/rpc/v5/info";
+pub const AUR_PKGBUILD_URL: &str = "https://aur.archlinux.org/cgit/aur.git/plain/PKGBUILD";
+pub const AUR_TIMEOUT_SECS: u64 = 15;
 
 // === Пути ===
 pub const CONFIG_APP_NAME: &str = "alfons-assistant";
+pub const MIRRORLIST_PATH: &str = "/etc/pacman.d/mirrorlist";
+
+// === Безопасность ===
+pub const SECURITY_FEED_URL: &str = "https://security.archlinux.org/all.json";
+pub const SECURITY_CACHE_MAX_AGE_SECS: u64 = 6 * 60 * 60;
+pub const SECURITY_FEED_TIMEOUT_SECS: u64 = 30;
 
 // === Лимиты ===
 pub const MAX_CHAT_MESSAGES: usize = 100;
 
+// === Сессии чата ===
+pub const DEFAULT_SESSION_LIMIT: usize = 50;
+pub const SESSION_TITLE_MAX: usize = 40;
+pub const SEARCH_MAX_RESULTS: usize = 100;
+/// Символов контекста вокруг совпадения в результатах поиска
+pub const SEARCH_SNIPPET_CONTEXT: usize = 60;
+
+// === Автодополнение ===
+pub const COMPLETION_MAX_SUGGESTIONS: usize = 8;
+
+// === Зеркала ===
+pub const MIRROR_PROBE_TIMEOUT_MS: u64 = 1500;
+pub const MIRROR_PROBE_BATCH: usize = 16;
+pub const DEFAULT_MIRROR_COUNT: usize = 10;
+
+// === Снимки ===
+pub const SNAPSHOT_DIR: &str = "/.snapshots";
+pub const SNAPSHOT_LIST_MAX: usize = 15;
+pub const SNAPSHOT_MANUAL_LABEL: &str = "Снимок из Альфонса";
+
+// === Журнал ===
+pub const JOURNAL_MAX_ENTRIES: usize = 500;
+pub const JOURNAL_EXPLAIN_MAX: usize = 50;
+pub const JOURNAL_TOOL_ENTRIES: usize = 20;
+
+// === Питание ===
+/// Просроченный таймер (приложение было закрыто) старше этого не выполняется
+pub const POWER_MISSED_GRACE_SECS: i64 = 5 * 60;
+
+// === Мониторинг ===
+pub const MONITOR_INTERVAL_MS: u64 = 1000;
+pub const MONITOR_HISTORY_LEN: usize = 60;
+
+// === Процессы ===
+pub const PROCESS_REFRESH_MS: u64 = 2000;
+pub const TOP_PROCESSES: usize = 10;
+
+// === Обновления ===
+/// Как часто фоном проверять доступные обновления (checkupdates)
+pub const UPDATES_CHECK_INTERVAL_SECS: u64 = 60 * 60;
+
+// === IPC ===
+pub const IPC_SOCKET_NAME: &str = "alfons-assistant.sock";
+/// Сколько соединение ждёт ответа приложения
+pub const IPC_REPLY_TIMEOUT_SECS: u64 = 90;
+/// Сколько ждать записи подписчику: зависший подписчик отключается
+pub const IPC_SUBSCRIBER_WRITE_TIMEOUT_MS: u64 = 200;
+
 // === UI ===
 pub const SETTINGS_PANEL_WIDTH: f32 = 280.0;
+pub const SESSIONS_PANEL_WIDTH: f32 = 220.0;
 
 // === Сообщения ===
+// Тексты сообщений и ошибок — ключи каталога `i18n`, выводятся через `tr`
 pub mod messages {
     pub const WELCOME: &str = "Система готова. Введите команду или задайте вопрос ИИ.";
     pub const CHAT_CLEARED: &str = "История чата очищена. Чем могу помочь?";
     pub const PROCESSING: &str = "Обработка...";
+    pub const REGENERATING: &str = "Генерирую другой вариант ответа...";
     pub const MODEL_CREATING: &str =
         "Создаю кастомную модель 'alfons'... Это может занять несколько минут.";
     pub const MODEL_CREATED: &str = "[OK] Модель 'alfons' создана! Переключаю на неё.";
     pub const MODEL_EXISTS: &str = "Модель 'alfons' уже существует.";
     pub const OLLAMA_INSTALLING: &str = "Устанавливаю Ollama... Это может занять некоторое время.";
-    pub const OLLAMA_INSTALLED: &str = "[OK] Ollama успешно установлена!";
     pub const OLLAMA_ALREADY: &str = "Ollama уже установлена!";
     pub const OLLAMA_STARTING: &str = "Запускаю сервис Ollama...";
     pub const OLLAMA_STARTED: &str = "[OK] Сервис Ollama запущен!";
@@ -0,0 +1,60 @@
+//! Запуск внешних программ от имени пользователя
+
+use super::i18n::trf;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+/// Запускает программу и возвращает stdout (ошибка — stderr)
+pub fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| trf("Ошибка {}: {}", &[&program, &e]))?;
+    into_result(program, output)
+}
+
+/// Как `run`, но передаёт `input` в stdin. Так передаются пароли:
+/// аргументы любой пользователь видит в /proc/*/cmdline
+pub fn run_with_input(program: &str, args: &[&str], input: &str) -> Result<String, String> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| trf("Ошибка {}: {}", &[&program, &e]))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| trf("Ошибка {}: {}", &[&program, &e]))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| trf("Ошибка {}: {}", &[&program, &e]))?;
+    into_result(program, output)
+}
+
+/// stdout при успехе; иначе stderr или общее сообщение, если он пуст
+fn into_result(program: &str, output: Output) -> Result<String, String> {
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        let err = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(if err.is_empty() {
+            trf("{} завершился с ошибкой", &[&program])
+        } else {
+            err
+        })
+    }
+}
+
+/// Есть ли программа в PATH
+pub fn has_program(name: &str) -> bool {
+    Command::new("which")
+        .arg(name)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
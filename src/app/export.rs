@@ -0,0 +1,216 @@
+//! Экспорт переписки в Markdown, JSON и HTML
+
+use super::chat::ChatMessage;
+use super::constants::{APP_NAME, USER_SENDER};
+use super::i18n::{language, tr, trf, Language};
+use super::markup::{self, Block};
+use chrono::Local;
+use std::path::PathBuf;
+
+/// Формат экспорта
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum ExportFormat {
+    #[default]
+    Markdown,
+    Json,
+    Html,
+}
+
+impl ExportFormat {
+    pub const ALL: &'static [ExportFormat] = &[
+        ExportFormat::Markdown,
+        ExportFormat::Json,
+        ExportFormat::Html,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Html => "HTML",
+        }
+    }
+
+    /// Расширение файла, оно же идентификатор в команде «экспорт чата md»
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Json => "json",
+            ExportFormat::Html => "html",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id {
+            "md" | "markdown" => Some(ExportFormat::Markdown),
+            "json" => Some(ExportFormat::Json),
+            "html" | "htm" => Some(ExportFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// Форматирование
+// ============================================================================
+
+/// Текст экспорта
+pub fn render(messages: &[ChatMessage], title: &str, format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Markdown => to_markdown(messages, title),
+        ExportFormat::Json => to_json(messages, title),
+        ExportFormat::Html => to_html(messages, title),
+    }
+}
+
+fn exported_at() -> String {
+    Local::now().format("%d.%m.%Y %H:%M").to_string()
+}
+
+fn to_markdown(messages: &[ChatMessage], title: &str) -> String {
+    let mut out = trf(
+        "# {}\n\n_Экспорт из {}, {}_\n",
+        &[&title, &APP_NAME, &exported_at()],
+    );
+    for msg in messages {
+        out.push_str(&format!(
+            "\n---\n\n**{}** · {}\n\n",
+            tr(&msg.sender),
+            msg.timestamp.format("%d.%m.%Y %H:%M")
+        ));
+        for block in markup::blocks(&msg.text) {
+            match block {
+                // Двойной пробел — перенос строки внутри абзаца
+                Block::Text(text) => out.push_str(&format!("{}\n\n", text.replace('\n', "  \n"))),
+                Block::Code { lang, code } => {
+                    out.push_str(&format!("```{}\n{}\n```\n\n", lang, code))
+                }
+            }
+        }
+    }
+    out
+}
+
+fn to_json(messages: &[ChatMessage], title: &str) -> String {
+    let messages: Vec<serde_json::Value> = messages
+        .iter()
+        .map(|msg| {
+            serde_json::json!({
+                "sender": msg.sender,
+                "timestamp": msg.timestamp.to_rfc3339(),
+                "text": msg.text,
+                "commands": markup::commands(&msg.text),
+            })
+        })
+        .collect();
+    let export = serde_json::json!({
+        "title": title,
+        "exported": Local::now().to_rfc3339(),
+        "messages": messages,
+    });
+    serde_json::to_string_pretty(&export).unwrap_or_default()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const HTML_STYLE: &str = "body{font-family:sans-serif;background:#1e1e1e;color:#eee;\
+max-width:820px;margin:2em auto;padding:0 1em}\
+.msg{background:#2a2a2a;border-radius:12px;padding:10px 14px;margin:12px 0}\
+.user{background:#28507a}\
+.meta{color:#999;font-size:12px;margin-bottom:6px}\
+.meta b{color:#3daee9}\
+p{margin:6px 0;white-space:pre-wrap}\
+pre{background:#111;border-radius:6px;padding:8px;overflow-x:auto}";
+
+fn to_html(messages: &[ChatMessage], title: &str) -> String {
+    let mut body = String::new();
+    for msg in messages {
+        let class = if msg.sender == USER_SENDER {
+            "msg user"
+        } else {
+            "msg"
+        };
+        body.push_str(&format!(
+            "<div class=\"{}\">\n<div class=\"meta\"><b>{}</b> · {}</div>\n",
+            class,
+            escape_html(tr(&msg.sender)),
+            msg.timestamp.format("%d.%m.%Y %H:%M")
+        ));
+        for block in markup::blocks(&msg.text) {
+            match block {
+                Block::Text(text) => body.push_str(&format!("<p>{}</p>\n", escape_html(&text))),
+                Block::Code { code, .. } => {
+                    body.push_str(&format!("<pre><code>{}</code></pre>\n", escape_html(&code)))
+                }
+            }
+        }
+        body.push_str("</div>\n");
+    }
+
+    let lang = match language() {
+        Language::Ru => "ru",
+        Language::En => "en",
+    };
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{lang}\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{title}</title>\n<style>{style}</style>\n</head>\n<body>\n\
+         <h1>{title}</h1>\n<div class=\"meta\">{meta}</div>\n\
+         {body}</body>\n</html>\n",
+        lang = lang,
+        title = escape_html(title),
+        style = HTML_STYLE,
+        meta = trf("Экспорт из {}, {}", &[&APP_NAME, &exported_at()]),
+        body = body
+    )
+}
+
+// ============================================================================
+// Сохранение
+// ============================================================================
+
+/// Каталог для экспорта: «Загрузки», иначе домашний
+fn export_dir() -> Option<PathBuf> {
+    dirs::download_dir().or_else(dirs::home_dir)
+}
+
+/// Имя файла из названия сессии: только буквы, цифры и дефисы
+fn file_name(title: &str, format: ExportFormat) -> String {
+    let slug: String = title
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .take(6)
+        .collect::<Vec<_>>()
+        .join("-");
+    format!(
+        "alfons-{}-{}.{}",
+        if slug.is_empty() { "chat" } else { &slug },
+        Local::now().format("%Y%m%d-%H%M"),
+        format.extension()
+    )
+}
+
+/// Сохраняет экспорт в файл и возвращает сообщение для чата
+pub fn save(messages: &[ChatMessage], title: &str, format: ExportFormat) -> String {
+    if messages.is_empty() {
+        return tr("[X] Нечего экспортировать: сообщений нет").into();
+    }
+    let Some(dir) = export_dir() else {
+        return tr("[X] Не найден каталог для сохранения").into();
+    };
+    let path = dir.join(file_name(title, format));
+    match std::fs::write(&path, render(messages, title, format)) {
+        Ok(()) => trf(
+            "[OK] Экспортировано сообщений: {} ({})\n{}",
+            &[&messages.len(), &format.label(), &path.display()],
+        ),
+        Err(e) => trf("[X] Не удалось сохранить {}: {}", &[&path.display(), &e]),
+    }
+}
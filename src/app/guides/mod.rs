@@ -1,3 +1,4 @@
+use super::i18n::tr;
 use std::collections::HashMap;
 
 /// Один шаг гайда
@@ -59,19 +60,19 @@ impl Guide {
         self
     }
 
-    /// Форматирует гайд для вывода в чат
+    /// Форматирует гайд для вывода в чат на языке интерфейса
     pub fn format(&self) -> String {
-        let mut output = format!(" {}\n{}\n\n", self.title, self.description);
+        let mut output = format!(" {}\n{}\n\n", tr(&self.title), tr(&self.description));
 
         for (i, step) in self.steps.iter().enumerate() {
-            output.push_str(&format!("{}. {}\n", i + 1, step.instruction));
+            output.push_str(&format!("{}. {}\n", i + 1, tr(&step.instruction)));
 
             if let Some(cmd) = &step.command {
-                output.push_str(&format!("   $ {}\n", cmd));
+                output.push_str(&format!("   $ {}\n", tr(cmd)));
             }
 
             if let Some(note) = &step.note {
-                output.push_str(&format!("   ℹ {}\n", note));
+                output.push_str(&format!("   ℹ {}\n", tr(note)));
             }
             output.push('\n');
         }
@@ -104,6 +105,11 @@ impl GuideRegistry {
         self.guides.get(id)
     }
 
+    /// Идентификаторы всех гайдов
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.guides.keys().map(String::as_str)
+    }
+
     /// Поиск гайдов по ключевому слову (в названии, описании, тегах)
     pub fn search(&self, query: &str) -> Vec<&Guide> {
         let query_lower = query.to_lowercase();
@@ -111,6 +117,7 @@ impl GuideRegistry {
             .values()
             .filter(|g| {
                 g.title.to_lowercase().contains(&query_lower)
+                    || tr(&g.title).to_lowercase().contains(&query_lower)
                     || g.description.to_lowercase().contains(&query_lower)
                     || g.tags
                         .iter()
@@ -122,16 +129,16 @@ impl GuideRegistry {
 
     /// Форматирует список гайдов для вывода
     pub fn format_list(&self) -> String {
-        let mut output = String::from("📚 Доступные гайды:\n\n");
+        let mut output = String::from(tr("📚 Доступные гайды:\n\n"));
 
         let mut guides: Vec<_> = self.guides.values().collect();
         guides.sort_by(|a, b| a.title.cmp(&b.title));
 
         for guide in guides {
-            output.push_str(&format!("• {} — {}\n", guide.id, guide.title));
+            output.push_str(&format!("• {} — {}\n", guide.id, tr(&guide.title)));
         }
 
-        output.push_str("\nИспользуйте: гайд <название>");
+        output.push_str(tr("\nИспользуйте: гайд <название>"));
         output
     }
 
@@ -184,10 +191,41 @@ impl GuideRegistry {
                     .with_command("yay -Ps"))
         );
 
+        // Зеркала
+        self.register(
+            Guide::new("mirrors", "Зеркала pacman", "Выбор быстрых зеркал для загрузки пакетов")
+                .add_tags(&["зеркала", "mirrorlist", "скорость", "reflector", "медленно"])
+                .add_step(
+                    GuideStep::new("Посмотреть текущий список")
+                        .with_command("cat /etc/pacman.d/mirrorlist"),
+                )
+                .add_step(
+                    GuideStep::new("Ранжировать зеркала в Альфонсе")
+                        .with_command("зеркала ранжировать Germany")
+                        .with_note("Страны можно не указывать или задать в настройках"),
+                )
+                .add_step(
+                    GuideStep::new("Или через reflector")
+                        .with_command("sudo reflector --country Germany --protocol https --sort rate --save /etc/pacman.d/mirrorlist"),
+                )
+                .add_step(
+                    GuideStep::new("Обновить базы пакетов")
+                        .with_command("sudo pacman -Syy")
+                        .with_note("Нужно после смены зеркал"),
+                ),
+        );
+
         // WiFi
         self.register(
             Guide::new("wifi", "Настройка WiFi", "Подключение к беспроводной сети")
                 .add_tags(&["сеть", "интернет", "wireless", "network", "вайфай"])
+                .add_step(
+                    GuideStep::new("Быстрый способ — панель WiFi ассистента")
+                        .with_command("wifi")
+                        .with_note(
+                            "Сканирование, подключение с паролем и режим полёта без терминала",
+                        ),
+                )
                 .add_step(
                     GuideStep::new("Проверить сетевые интерфейсы")
                         .with_command("ip link")
@@ -266,6 +304,11 @@ impl GuideRegistry {
                 .add_step(
                     GuideStep::new("Определить видеокарту").with_command("lspci -v | grep -i vga"),
                 )
+                .add_step(
+                    GuideStep::new("Или определить автоматически")
+                        .with_command("видеокарта")
+                        .with_note("Альфонс подберёт пакеты; установка — «установить драйверы»"),
+                )
                 .add_step(
                     GuideStep::new("Для Intel")
                         .with_command("sudo pacman -S mesa intel-media-driver"),
@@ -275,9 +318,14 @@ impl GuideRegistry {
                         .with_command("sudo pacman -S mesa xf86-video-amdgpu vulkan-radeon"),
                 )
                 .add_step(
-                    GuideStep::new("Для NVIDIA (проприетарный)")
-                        .with_command("sudo pacman -S nvidia nvidia-utils nvidia-settings")
-                        .with_note("После установки нужна перезагрузка"),
+                    GuideStep::new("Для NVIDIA (GTX 16xx, RTX и новее)")
+                        .with_command("sudo pacman -S nvidia-open nvidia-utils nvidia-settings")
+                        .with_note("Для ядер кроме linux — nvidia-open-dkms и заголовки ядра. После установки нужна перезагрузка"),
+                )
+                .add_step(
+                    GuideStep::new("Для NVIDIA (GTX 9xx/10xx)")
+                        .with_command("yay -S nvidia-580xx-dkms nvidia-580xx-utils")
+                        .with_note("Старые карты не поддерживаются открытым модулем"),
                 )
                 .add_step(
                     GuideStep::new("Для NVIDIA (открытый)")
@@ -314,6 +362,11 @@ impl GuideRegistry {
                     GuideStep::new("Переключить устройство по умолчанию")
                         .with_command("wpctl set-default <ID>")
                         .with_note("ID можно узнать из wpctl status"),
+                )
+                .add_step(
+                    GuideStep::new("Или попросите ассистента")
+                        .with_command("переключи звук на наушники")
+                        .with_note("Также: громкость 50, выключи звук, что играет"),
                 ),
         );
 
@@ -368,6 +421,11 @@ impl GuideRegistry {
                 GuideStep::new("Настроить автоматические снимки")
                     .with_command("sudo timeshift-gtk")
                     .with_note("Графический интерфейс для настройки"),
+            )
+            .add_step(
+                GuideStep::new("Снимки из ассистента")
+                    .with_command("снимки")
+                    .with_note("Перед обновлением снимок создаётся сам; откат — команда «откат»"),
             ),
         );
     }
@@ -0,0 +1,1399 @@
+//! Английский каталог: русская строка → перевод
+//!
+//! Ключ должен совпадать с русским текстом в коде символ в символ, включая
+//! `{}` и переводы строк. Длинные промпты ИИ берутся по имени константы.
+
+use crate::app::ai::{local_provider, tools};
+use crate::app::cli;
+use crate::app::commands::{aur, journal};
+
+pub const STRINGS: &[(&str, &str)] = &[
+    // ========================================================================
+    // Промпты ИИ
+    // ========================================================================
+    (tools::SYSTEM_PROMPT, SYSTEM_PROMPT),
+    (local_provider::MODELFILE, MODELFILE),
+    (aur::PKGBUILD_SUMMARY_PROMPT, PKGBUILD_SUMMARY_PROMPT),
+    (journal::EXPLAIN_PROMPT, EXPLAIN_PROMPT),
+    (cli::USAGE, USAGE),
+    // ========================================================================
+    // Сообщения и ошибки (constants)
+    // ========================================================================
+    (
+        "Система готова. Введите команду или задайте вопрос ИИ.",
+        "System ready. Enter a command or ask the AI a question.",
+    ),
+    (
+        "История чата очищена. Чем могу помочь?",
+        "Chat history cleared. How can I help?",
+    ),
+    ("Обработка...", "Processing..."),
+    ("Генерирую другой вариант ответа...", "Generating another answer..."),
+    (
+        "Создаю кастомную модель 'alfons'... Это может занять несколько минут.",
+        "Creating custom model 'alfons'... This may take a few minutes.",
+    ),
+    (
+        "[OK] Модель 'alfons' создана! Переключаю на неё.",
+        "[OK] Model 'alfons' created! Switching to it.",
+    ),
+    ("Модель 'alfons' уже существует.", "Model 'alfons' already exists."),
+    (
+        "Устанавливаю Ollama... Это может занять некоторое время.",
+        "Installing Ollama... This may take a while.",
+    ),
+    ("Ollama уже установлена!", "Ollama is already installed!"),
+    ("Запускаю сервис Ollama...", "Starting the Ollama service..."),
+    ("[OK] Сервис Ollama запущен!", "[OK] Ollama service started!"),
+    (
+        "Устанавливаю yay... Это может занять некоторое время.",
+        "Installing yay... This may take a while.",
+    ),
+    ("[OK] yay успешно установлен!", "[OK] yay installed successfully!"),
+    ("yay уже установлен!", "yay is already installed!"),
+    (
+        "Ошибка связи с Ollama. Убедитесь, что сервис запущен.",
+        "Cannot reach Ollama. Make sure the service is running.",
+    ),
+    ("Ошибка обработки ответа от Ollama.", "Failed to process the Ollama response."),
+    ("Ничего не найдено.", "Nothing found."),
+    (
+        "[X] Не удалось создать модель. Проверьте, что Ollama запущена и llama3 загружена.",
+        "[X] Failed to create the model. Check that Ollama is running and llama3 is pulled.",
+    ),
+    (
+        "[X] Базовая модель llama3 не найдена. Выполните: ollama pull llama3",
+        "[X] Base model llama3 not found. Run: ollama pull llama3",
+    ),
+    ("[X] Не удалось установить Ollama.", "[X] Failed to install Ollama."),
+    ("[X] Не удалось запустить сервис Ollama.", "[X] Failed to start the Ollama service."),
+    (
+        "[X] Не удалось установить зависимости для yay.",
+        "[X] Failed to install yay dependencies.",
+    ),
+    (
+        "[X] Не удалось склонировать репозиторий yay.",
+        "[X] Failed to clone the yay repository.",
+    ),
+    ("[X] Не удалось собрать yay.", "[X] Failed to build yay."),
+    ("Снимок из Альфонса", "Snapshot from Alfons"),
+    // ========================================================================
+    // Чат и отправители
+    // ========================================================================
+    ("Вы", "You"),
+    ("Система", "System"),
+    ("Ассистент", "Assistant"),
+    ("Новый чат", "New chat"),
+    ("Ошибка ИИ: {}", "AI error: {}"),
+    ("[!] команда '{}' не распознана", "[!] command '{}' not recognized"),
+    ("Поиск пакетов", "Package search"),
+    ("Введите название пакета:", "Enter a package name:"),
+    ("Не удалось сохранить настройки: {}", "Failed to save settings: {}"),
+    // ========================================================================
+    // ИИ: Ollama и инструменты
+    // ========================================================================
+    ("Установка Ollama", "Ollama installation"),
+    ("[OK] {} запущено в {}", "[OK] {} started in {}"),
+    (
+        "[X] Не найден терминал для {}. Установите {} или другой терминал.",
+        "[X] No terminal found for {}. Install {} or another terminal.",
+    ),
+    (
+        "{}; echo 'Нажмите Enter...'; read",
+        "{}; echo 'Press Enter...'; read",
+    ),
+    (
+        "sh -c '{}; echo Нажмите Enter...; read'",
+        "sh -c '{}; echo Press Enter...; read'",
+    ),
+    ("получить текущее время", "get the current time"),
+    ("получить текущую дату", "get the current date"),
+    ("получить дату и время", "get the date and time"),
+    ("показать доступные обучающие гайды", "show the available guides"),
+    ("показать использование RAM и swap", "show RAM and swap usage"),
+    ("показать заполненность разделов", "show partition usage"),
+    ("показать процессор и его загрузку", "show the CPU and its load"),
+    ("температура датчиков (CPU, GPU, диски)", "sensor temperatures (CPU, GPU, disks)"),
+    ("заряд и состояние батареи", "battery charge and status"),
+    ("текущая скорость сети", "current network speed"),
+    (
+        "уязвимые установленные пакеты (CVE, важность, исправленная версия)",
+        "vulnerable installed packages (CVE, severity, fixed version)",
+    ),
+    (
+        "упавшие сервисы systemd (systemctl --failed)",
+        "failed systemd services (systemctl --failed)",
+    ),
+    (
+        "текущее сетевое подключение, IP и DNS",
+        "current network connection, IP and DNS",
+    ),
+    (
+        "устройство вывода, громкость и что сейчас играет",
+        "output device, volume and what is playing",
+    ),
+    ("все устройства вывода и ввода звука", "all audio output and input devices"),
+    (
+        "видеокарты, загруженный драйвер и рекомендуемые пакеты",
+        "graphics cards, loaded driver and recommended packages",
+    ),
+    (
+        "работающее и установленные ядра, нужна ли перезагрузка, записи загрузчика",
+        "running and installed kernels, whether a reboot is needed, bootloader entries",
+    ),
+    (
+        "последние снимки системы (snapper, timeshift или btrfs)",
+        "latest system snapshots (snapper, timeshift or btrfs)",
+    ),
+    (
+        "самые загруженные процессы (CPU и память)",
+        "the busiest processes (CPU and memory)",
+    ),
+    ("сервис", "service"),
+    (
+        "последние ошибки сервиса из журнала за текущую загрузку",
+        "recent service errors from the journal for the current boot",
+    ),
+    ("показать общую информацию о системе", "show general system information"),
+    ("Память: {}\nCPU: {}\nДиск: {}", "Memory: {}\nCPU: {}\nDisk: {}"),
+    // ========================================================================
+    // Реестр команд и справка
+    // ========================================================================
+    ("Базовые", "Basic"),
+    ("Пакеты (через yay)", "Packages (via yay)"),
+    ("Зеркала", "Mirrors"),
+    ("Безопасность", "Security"),
+    ("Видеокарта", "Graphics card"),
+    ("Ядра", "Kernels"),
+    ("Снимки", "Snapshots"),
+    ("Сервисы systemd", "systemd services"),
+    ("Сеть", "Network"),
+    ("Звук", "Audio"),
+    ("Процессы", "Processes"),
+    ("Журнал", "Journal"),
+    ("Гайды", "Guides"),
+    ("Чаты", "Chats"),
+    ("Прочее", "Other"),
+    ("Не хватает аргумента. Формат: {}", "Missing argument. Usage: {}"),
+    ("📋 Доступные команды:\n", "📋 Available commands:\n"),
+    ("\n⚠ — с подтверждением\n", "\n⚠ — asks for confirmation\n"),
+    (
+        "💡 Или просто задайте вопрос — ИИ постарается помочь!",
+        "💡 Or just ask a question — the AI will try to help!",
+    ),
+    (
+        " (откроется диалог подтверждения!)",
+        " (opens a confirmation dialog!)",
+    ),
+    (
+        "Вы имели в виду «{}»? Повторите команду в этой форме.",
+        "Did you mean “{}”? Repeat the command in this form.",
+    ),
+    ("гайд показан", "guide shown"),
+    // ========================================================================
+    // Базовые команды
+    // ========================================================================
+    ("текущее время", "current time"),
+    ("Текущее время: {}", "Current time: {}"),
+    ("сегодняшняя дата", "today's date"),
+    ("Сегодня: {}", "Today: {}"),
+    ("дата и время", "date and time"),
+    ("Сейчас: {}", "Now: {}"),
+    ("начать новую сессию (Ctrl+N)", "start a new session (Ctrl+N)"),
+    ("список сохранённых сессий", "list saved sessions"),
+    ("поиск по всем чатам (Ctrl+F)", "search all chats (Ctrl+F)"),
+    ("запрос", "query"),
+    (
+        "сохранить переписку в файл (без формата — окно, Ctrl+E)",
+        "save the conversation to a file (no format — dialog, Ctrl+E)",
+    ),
+    ("очистить текущий чат", "clear the current chat"),
+    ("поздороваться", "say hello"),
+    (
+        "Привет! Я {}, твой помощник для Arch Linux.",
+        "Hi! I'm {}, your Arch Linux assistant.",
+    ),
+    ("повторить фразу", "repeat a phrase"),
+    ("фраза", "phrase"),
+    ("эта справка", "this help"),
+    // ========================================================================
+    // Пакеты и AUR
+    // ========================================================================
+    ("окно поиска пакетов", "package search dialog"),
+    ("Открываю поиск пакетов...", "Opening package search..."),
+    ("найти пакеты", "search packages"),
+    ("Ищу пакеты '{}'...", "Searching packages '{}'..."),
+    ("установить пакет", "install a package"),
+    ("Установка пакета", "Package installation"),
+    ("Установить '{}' через yay?", "Install '{}' via yay?"),
+    ("Подготовка к установке '{}'...", "Preparing to install '{}'..."),
+    ("пакет", "package"),
+    ("удалить пакет", "remove a package"),
+    ("Удаление пакета", "Package removal"),
+    ("Удалить '{}' из системы?", "Remove '{}' from the system?"),
+    ("Подготовка к удалению '{}'...", "Preparing to remove '{}'..."),
+    ("полное обновление (yay -Syu)", "full upgrade (yay -Syu)"),
+    ("Обновление системы", "System update"),
+    (
+        "Выполнить полное обновление (yay -Syu)?",
+        "Run a full upgrade (yay -Syu)?",
+    ),
+    ("Подготовка к обновлению...", "Preparing to update..."),
+    ("Ошибка yay: {}", "yay error: {}"),
+    ("Установка {}", "Installing {}"),
+    ("Удаление {}", "Removing {}"),
+    ("Выполнение команды", "Running a command"),
+    (
+        "[X] Не удалось создать снимок: {}\n{} отменено. Повторите или отключите снимки в настройках",
+        "[X] Failed to create a snapshot: {}\n{} cancelled. Try again or disable snapshots in settings",
+    ),
+    ("скачивание и запуск скрипта (curl | sh)", "downloads and runs a script (curl | sh)"),
+    ("использование sudo при сборке", "uses sudo during the build"),
+    ("запись в систему вне $pkgdir", "writes to the system outside $pkgdir"),
+    ("обфусцированный код (base64/eval)", "obfuscated code (base64/eval)"),
+    ("AUR недоступен: {}", "AUR is unavailable: {}"),
+    (
+        "Пакет '{}' не найден ни в репозиториях, ни в AUR",
+        "Package '{}' not found in the repositories or the AUR",
+    ),
+    ("Не удалось загрузить PKGBUILD: {}", "Failed to download PKGBUILD: {}"),
+    // ========================================================================
+    // Зеркала
+    // ========================================================================
+    (
+        "##\n## Arch Linux repository mirrorlist\n## Ранжировано Альфонсом: {}\n##\n",
+        "##\n## Arch Linux repository mirrorlist\n## Ranked by Alfons: {}\n##\n",
+    ),
+    ("Изменения: +{} / -{} строк\n\n", "Changes: +{} / -{} lines\n\n"),
+    ("... и ещё {} строк\n", "... and {} more lines\n"),
+    ("текущий список", "current list"),
+    ("найти самые быстрые зеркала", "find the fastest mirrors"),
+    (
+        "Замеряю задержку зеркал... Это может занять до минуты.",
+        "Measuring mirror latency... This may take up to a minute.",
+    ),
+    ("страна ...", "country ..."),
+    ("записать подготовленный список", "write the prepared list"),
+    ("Подтвердите запись списка зеркал...", "Confirm writing the mirror list..."),
+    ("Сначала выполните: зеркала ранжировать", "First run: rank mirrors"),
+    ("[X] Не удалось прочитать {}: {}", "[X] Failed to read {}: {}"),
+    ("Зеркала ({}): активно {} из {}\n\n", "Mirrors ({}): {} of {} active\n\n"),
+    (
+        "\nРанжировать: зеркала ранжировать [страна ...]",
+        "\nRank: rank mirrors [country ...]",
+    ),
+    ("Обновление зеркал", "Mirror update"),
+    (
+        "Записать {} зеркал в {}?\nСтарый файл будет сохранён как резервная копия.",
+        "Write {} mirrors to {}?\nThe old file will be kept as a backup.",
+    ),
+    (
+        "[X] Ни одно зеркало не ответило. Проверьте фильтр стран и сеть.",
+        "[X] No mirror responded. Check the country filter and the network.",
+    ),
+    ("[OK] Самые быстрые зеркала:\n\n", "[OK] Fastest mirrors:\n\n"),
+    ("• {} — {} мс\n", "• {} — {} ms\n"),
+    ("\nЗаписать: зеркала применить", "\nWrite: apply mirrors"),
+    (
+        "[OK] Список зеркал обновлён. Резервная копия: {}\nОбновите базы: yay -Syy",
+        "[OK] Mirror list updated. Backup: {}\nRefresh the databases: yay -Syy",
+    ),
+    ("[X] Не удалось записать зеркала: {}", "[X] Failed to write mirrors: {}"),
+    // ========================================================================
+    // Безопасность
+    // ========================================================================
+    ("Не удалось прочитать {}: {}", "Failed to read {}: {}"),
+    ("Не удалось определить каталог кэша", "Failed to determine the cache directory"),
+    ("Не удалось загрузить {}: {}", "Failed to download {}: {}"),
+    ("поток загрузки завершился аварийно", "download thread panicked"),
+    ("Ошибка чтения кэша: {}", "Cache read error: {}"),
+    ("Ошибка разбора JSON трекера: {}", "Failed to parse tracker JSON: {}"),
+    ("Ошибка pacman: {}", "pacman error: {}"),
+    ("[OK] Уязвимых пакетов не найдено.", "[OK] No vulnerable packages found."),
+    ("[!] Уязвимых пакетов: {}\n\n", "[!] Vulnerable packages: {}\n\n"),
+    (
+        "• {} {} — {} ({})\n  {}\n  Исправлено в: {}\n",
+        "• {} {} — {} ({})\n  {}\n  Fixed in: {}\n",
+    ),
+    ("исправления пока нет", "no fix yet"),
+    (
+        "\nУстановить исправления: безопасность исправить",
+        "\nInstall fixes: fix vulnerabilities",
+    ),
+    (
+        "проверить пакеты по Arch Security Tracker",
+        "check packages against the Arch Security Tracker",
+    ),
+    (
+        "Проверяю пакеты по Arch Security Tracker...",
+        "Checking packages against the Arch Security Tracker...",
+    ),
+    ("установить исправления", "install fixes"),
+    (
+        "Нет исправлений для установки. Сначала: безопасность",
+        "No fixes to install. First run: security",
+    ),
+    (
+        "Исправления для: {}\nВыполнить полное обновление (yay -Syu)?",
+        "Fixes for: {}\nRun a full upgrade (yay -Syu)?",
+    ),
+    ("Подтвердите установку исправлений...", "Confirm installing the fixes..."),
+    ("уязвимых пакетов нет", "no vulnerable packages"),
+    ("{} {} ({}, {}, исправлено в {})", "{} {} ({}, {}, fixed in {})"),
+    // ========================================================================
+    // Видеокарта и ядра
+    // ========================================================================
+    ("устройство {}", "device {}"),
+    (
+        "{} работает на старом драйвере radeon. Для GCN 1/2 можно включить amdgpu: radeon.si_support=0 amdgpu.si_support=1 (или cik_support) в параметрах ядра",
+        "{} runs on the old radeon driver. For GCN 1/2 you can enable amdgpu: radeon.si_support=0 amdgpu.si_support=1 (or cik_support) in the kernel parameters",
+    ),
+    (
+        "Сейчас загружен nouveau: после установки nvidia-open перезагрузитесь",
+        "nouveau is loaded now: reboot after installing nvidia-open",
+    ),
+    (
+        "{} (Maxwell/Pascal/Volta) не поддерживается открытым модулем — ветка 580xx из AUR",
+        "{} (Maxwell/Pascal/Volta) is not supported by the open module — use the 580xx branch from the AUR",
+    ),
+    (
+        "{} слишком старая для текущих драйверов NVIDIA — используется nouveau (mesa)",
+        "{} is too old for current NVIDIA drivers — nouveau (mesa) is used",
+    ),
+    (
+        "Гибридная графика: игры на дискретной — prime-run <программа>",
+        "Hybrid graphics: run games on the discrete GPU with prime-run <program>",
+    ),
+    (
+        "Для Steam и Wine включите [multilib] в /etc/pacman.conf — добавятся lib32-пакеты",
+        "For Steam and Wine enable [multilib] in /etc/pacman.conf — lib32 packages will be added",
+    ),
+    ("Видеокарты не найдены в /sys/bus/pci", "No graphics cards found in /sys/bus/pci"),
+    ("Видеокарты:", "Graphics cards:"),
+    ("  {} {} — драйвер: {}", "  {} {} — driver: {}"),
+    ("не загружен", "not loaded"),
+    ("Рекомендуемые пакеты: {}", "Recommended packages: {}"),
+    (
+        "[OK] Все рекомендуемые пакеты установлены",
+        "[OK] All recommended packages are installed",
+    ),
+    (
+        "Не установлены: {}. Установить: установить драйверы",
+        "Not installed: {}. Install: install drivers",
+    ),
+    ("модель, драйвер, рекомендуемые пакеты", "model, driver, recommended packages"),
+    ("Определяю видеокарту...", "Detecting the graphics card..."),
+    ("недостающие драйверы", "missing drivers"),
+    ("Видеокарты не найдены", "No graphics cards found"),
+    (
+        "[OK] Рекомендуемые драйверы уже установлены",
+        "[OK] Recommended drivers are already installed",
+    ),
+    ("Установить через yay: {}?", "Install via yay: {}?"),
+    ("Установка драйверов", "Driver installation"),
+    ("Подтвердите установку драйверов...", "Confirm the driver installation..."),
+    (
+        "ядра, загрузчик, нужна ли перезагрузка",
+        "kernels, bootloader, whether a reboot is needed",
+    ),
+    ("Проверяю ядра и загрузчик...", "Checking kernels and the bootloader..."),
+    ("резервное ядро linux-lts", "linux-lts fallback kernel"),
+    ("[OK] Ядро linux-lts уже установлено", "[OK] linux-lts is already installed"),
+    ("Установка ядра LTS", "LTS kernel installation"),
+    (
+        "Установить {} как резервное ядро?\n{}",
+        "Install {} as a fallback kernel?\n{}",
+    ),
+    ("Подтвердите установку ядра...", "Confirm the kernel installation..."),
+    (
+        "После установки меню GRUB будет обновлено (grub-mkconfig)",
+        "The GRUB menu will be regenerated after installation (grub-mkconfig)",
+    ),
+    (
+        "Для systemd-boot может понадобиться запись в loader/entries (если не используются UKI)",
+        "systemd-boot may need an entry in loader/entries (unless UKIs are used)",
+    ),
+    (
+        "Не забудьте добавить ядро в меню загрузчика",
+        "Don't forget to add the kernel to the bootloader menu",
+    ),
+    ("Работающее ядро: {}", "Running kernel: {}"),
+    (
+        "Пакеты ядер не найдены (pacman недоступен?)",
+        "No kernel packages found (is pacman available?)",
+    ),
+    ("Установленные ядра:", "Installed kernels:"),
+    (
+        "[!] Ядро обновлено, нужна перезагрузка: модулей {} больше нет в {}",
+        "[!] The kernel was updated, reboot needed: modules for {} are gone from {}",
+    ),
+    ("Загрузчик: {}", "Bootloader: {}"),
+    (" (по умолчанию)", " (default)"),
+    (
+        "Загрузчик: нет доступа к меню ({})",
+        "Bootloader: no access to the menu ({})",
+    ),
+    ("Загрузчик не определён", "Bootloader not detected"),
+    (
+        "Резервного ядра нет. Установить: установить lts ядро",
+        "No fallback kernel. Install: install lts kernel",
+    ),
+    // ========================================================================
+    // Снимки
+    // ========================================================================
+    (
+        "Откат через snapper:\n1. Посмотрите номера: снимки\n2. Откатитесь: откатить снимок <номер> (sudo snapper rollback <номер>)\n3. Перезагрузите компьютер\nЕсли система не загружается — выберите снимок в меню загрузчика (grub-btrfs)",
+        "Rollback with snapper:\n1. Find the number: snapshots\n2. Roll back: restore snapshot <number> (sudo snapper rollback <number>)\n3. Reboot the computer\nIf the system does not boot, pick the snapshot in the bootloader menu (grub-btrfs)",
+    ),
+    (
+        "Откат через timeshift:\n1. Посмотрите снимки: снимки\n2. Откатитесь: откатить снимок <имя> (sudo timeshift --restore --snapshot <имя>)\n3. Перезагрузите компьютер\nЕсли система не загружается — загрузитесь с live-USB и запустите timeshift --restore",
+        "Rollback with timeshift:\n1. Find the snapshot: snapshots\n2. Roll back: restore snapshot <name> (sudo timeshift --restore --snapshot <name>)\n3. Reboot the computer\nIf the system does not boot, start from a live USB and run timeshift --restore",
+    ),
+    (
+        "Снимки btrfs лежат в {}. Для отката загрузитесь с live-USB, смонтируйте корневой btrfs и замените подтом корня снимком:\nbtrfs subvolume snapshot {}/<снимок> <новый корень>\nзатем укажите его в rootflags=subvol=... загрузчика или через btrfs subvolume set-default",
+        "btrfs snapshots are in {}. To roll back, boot from a live USB, mount the root btrfs and replace the root subvolume with a snapshot:\nbtrfs subvolume snapshot {}/<snapshot> <new root>\nthen set it in the bootloader's rootflags=subvol=... or with btrfs subvolume set-default",
+    ),
+    ("список снимков", "list snapshots"),
+    ("Получаю список снимков...", "Fetching snapshots..."),
+    ("снимок системы", "system snapshot"),
+    ("описание", "description"),
+    ("как откатить систему", "how to roll back the system"),
+    ("откатить систему к снимку", "roll the system back to a snapshot"),
+    ("номер|имя", "number|name"),
+    (
+        "Укажите номер или имя снимка из списка: снимки",
+        "Give a snapshot number or name from the list: snapshots",
+    ),
+    ("Откат системы", "System rollback"),
+    ("Создание снимка", "Create snapshot"),
+    ("Создать снимок системы «{}»?", "Create system snapshot “{}”?"),
+    (
+        "Подтвердите создание снимка...",
+        "Confirm the snapshot creation...",
+    ),
+    (
+        "Откатить систему к снимку {} через {}?\nПосле отката потребуется перезагрузка.",
+        "Roll the system back to snapshot {} with {}?\nA reboot will be required afterwards.",
+    ),
+    ("Подтвердите откат...", "Confirm the rollback..."),
+    (
+        "Снимки недоступны: нет snapper/timeshift, а корень не на btrfs. Настройка: гайд backup",
+        "Snapshots unavailable: no snapper/timeshift and the root is not on btrfs. Setup: guide backup",
+    ),
+    ("Снимков {} пока нет", "No {} snapshots yet"),
+    ("Снимки {} (последние {}):\n{}", "{} snapshots (latest {}):\n{}"),
+    ("[X] Не удалось получить снимки {}: {}", "[X] Failed to list {} snapshots: {}"),
+    (
+        "нужны права root, выполните команду «снимки»",
+        "root is required, run the “snapshots” command",
+    ),
+    ("[OK] Снимок {} создан ({})", "[OK] Snapshot {} created ({})"),
+    ("[X] Не удалось создать снимок: {}", "[X] Failed to create a snapshot: {}"),
+    ("Альфонс: перед «{}»", "Alfons: before “{}”"),
+    (
+        "[OK] Снимок {} создан ({}). Откат: откат",
+        "[OK] Snapshot {} created ({}). Roll back: rollback",
+    ),
+    (
+        "{} && echo 'Перезагрузите компьютер'",
+        "{} && echo 'Reboot the computer'",
+    ),
+    // ========================================================================
+    // Питание
+    // ========================================================================
+    ("Выключение", "Shutdown"),
+    ("Перезагрузка", "Reboot"),
+    ("Спящий режим", "Suspend"),
+    ("Гибернация", "Hibernate"),
+    ("Блокировка", "Lock"),
+    ("Выход из системы", "Log out"),
+    ("выключить компьютер", "shut down the computer"),
+    ("перезагрузить компьютер", "reboot the computer"),
+    ("перевести компьютер в спящий режим", "suspend the computer"),
+    ("перевести компьютер в гибернацию", "hibernate the computer"),
+    ("заблокировать экран", "lock the screen"),
+    ("выйти из системы", "log out"),
+    ("через 30 минут | в 23:00", "in 30 minutes | at 23:00"),
+    (
+        "выключить компьютер (сейчас или по таймеру)",
+        "shut down the computer (now or on a timer)",
+    ),
+    ("спящий режим", "suspend"),
+    ("гибернация", "hibernate"),
+    ("завершить сеанс", "end the session"),
+    ("отменить запланированное действие", "cancel the scheduled action"),
+    ("Нет запланированных действий", "No scheduled actions"),
+    ("Отменяю...", "Cancelling..."),
+    ("Блокирую экран...", "Locking the screen..."),
+    ("Вы уверены, что хотите {}?", "Are you sure you want to {}?"),
+    (
+        "Не понял время. Примеры: через 30 минут, через 2 часа, в 23:00",
+        "Could not parse the time. Examples: in 30 minutes, in 2 hours, at 23:00",
+    ),
+    ("в {}", "at {}"),
+    ("\nЗаменит: {} в {}", "\nReplaces: {} at {}"),
+    ("{} по таймеру", "{} on a timer"),
+    ("Подтвердите таймер...", "Confirm the timer..."),
+    (
+        "[OK] {} запланировано на {}. Отменить: «отменить таймер» или кнопка в шапке",
+        "[OK] {} scheduled for {}. Cancel: “cancel timer” or the button in the header",
+    ),
+    ("[X] Не удалось отменить таймер: {}", "[X] Failed to cancel the timer: {}"),
+    ("[OK] {} отменено", "[OK] {} cancelled"),
+    // ========================================================================
+    // Сервисы и журнал
+    // ========================================================================
+    ("Запуск сервиса", "Start service"),
+    ("Остановка сервиса", "Stop service"),
+    ("Перезапуск сервиса", "Restart service"),
+    ("Включение автозапуска", "Enable autostart"),
+    ("Отключение автозапуска", "Disable autostart"),
+    ("Ошибка systemctl: {}", "systemctl error: {}"),
+    ("панель сервисов", "services panel"),
+    ("упавшие сервисы", "failed services"),
+    ("Проверяю сервисы...", "Checking services..."),
+    ("статус сервиса", "service status"),
+    ("Получаю статус {}...", "Fetching the status of {}..."),
+    (
+        "Укажите корректное имя сервиса. Пример: статус sshd",
+        "Give a valid service name. Example: status sshd",
+    ),
+    ("запустить", "start"),
+    ("имя", "name"),
+    ("остановить", "stop"),
+    ("перезапустить", "restart"),
+    ("включить автозапуск", "enable autostart"),
+    ("отключить автозапуск", "disable autostart"),
+    (
+        "Укажите имя сервиса. Пример: запустить сервис sshd",
+        "Give a service name. Example: start service sshd",
+    ),
+    ("Подтвердите действие для {}...", "Confirm the action for {}..."),
+    ("Выполнить systemctl {} {}?", "Run systemctl {} {}?"),
+    ("упавших сервисов нет", "no failed services"),
+    ("Некорректное имя юнита: {}", "Invalid unit name: {}"),
+    ("Ошибка journalctl: {}", "journalctl error: {}"),
+    ("просмотр журнала с фильтрами", "view the journal with filters"),
+    ("последние ошибки в чат", "recent errors into the chat"),
+    ("Читаю журнал...", "Reading the journal..."),
+    ("разбор ошибок ИИ", "AI explanation of errors"),
+    ("ошибок в журнале нет", "no errors in the journal"),
+    ("ошибок для {} нет", "no errors for {}"),
+    // ========================================================================
+    // Сеть
+    // ========================================================================
+    ("Ошибка {}: {}", "{} error: {}"),
+    ("{} завершился с ошибкой", "{} failed"),
+    ("[OK] Подключено к {}", "[OK] Connected to {}"),
+    ("Нет активного подключения", "No active connection"),
+    ("[OK] {} отключено", "[OK] {} disconnected"),
+    ("[OK] Режим полёта включён", "[OK] Airplane mode on"),
+    ("[OK] Режим полёта выключен", "[OK] Airplane mode off"),
+    ("Подключение: {} ({})", "Connection: {} ({})"),
+    ("Шлюз: {}", "Gateway: {}"),
+    ("Режим полёта включён", "Airplane mode is on"),
+    ("Сети не найдены", "No networks found"),
+    ("открытая", "open"),
+    (
+        "Не найден NetworkManager (nmcli) или iwd (iwctl)",
+        "Neither NetworkManager (nmcli) nor iwd (iwctl) found",
+    ),
+    ("сеть", "network"),
+    ("панель сетей", "networks panel"),
+    ("найти сети", "scan for networks"),
+    ("Ищу сети...", "Scanning for networks..."),
+    ("подключение, IP и DNS", "connection, IP and DNS"),
+    ("Проверяю подключение...", "Checking the connection..."),
+    ("отключиться от сети", "disconnect from the network"),
+    ("Отключаюсь...", "Disconnecting..."),
+    (
+        "подключиться (пароль вводится в панели)",
+        "connect (the password is entered in the panel)",
+    ),
+    ("все радиомодули", "all radios"),
+    (
+        "Укажите: режим полёта вкл / выкл",
+        "Specify: airplane mode on / off",
+    ),
+    ("Переключаю радиомодули...", "Switching radios..."),
+    ("вкл|выкл", "on|off"),
+    // ========================================================================
+    // Звук
+    // ========================================================================
+    ("вывод, громкость, что играет", "output, volume, what is playing"),
+    ("Проверяю звук...", "Checking audio..."),
+    ("все устройства", "all devices"),
+    ("Ищу аудиоустройства...", "Looking for audio devices..."),
+    ("приложения, воспроизводящие звук", "applications playing audio"),
+    ("Смотрю, что играет...", "Checking what is playing..."),
+    (
+        "сменить вывод (наушники, динамики, hdmi или название)",
+        "change the output (headphones, speakers, hdmi or a name)",
+    ),
+    ("Переключаю вывод на «{}»...", "Switching output to “{}”..."),
+    ("устройство", "device"),
+    ("громкость", "volume"),
+    ("Меняю громкость...", "Changing the volume..."),
+    (
+        "Укажите громкость: громкость 50, громкость +10, громкость тише",
+        "Specify the volume: volume 50, volume +10, volume down",
+    ),
+    ("громкость +10", "volume +10"),
+    ("громкость -10", "volume -10"),
+    ("выключить звук", "mute"),
+    ("Выключаю звук...", "Muting..."),
+    ("включить звук", "unmute"),
+    ("Включаю звук...", "Unmuting..."),
+    (" (без звука)", " (muted)"),
+    (" (пауза)", " (paused)"),
+    ("Вывод: {} — {}%{}", "Output: {} — {}%{}"),
+    (
+        "Устройство вывода по умолчанию не найдено",
+        "No default output device found",
+    ),
+    ("Сейчас ничего не играет", "Nothing is playing"),
+    ("Играет:", "Playing:"),
+    ("Вывод", "Output"),
+    ("Ввод", "Input"),
+    (
+        "[X] Устройство «{}» не найдено. Доступные: {}",
+        "[X] Device “{}” not found. Available: {}",
+    ),
+    ("{} уже используется", "{} is already in use"),
+    ("[OK] Звук переключён на {}", "[OK] Audio switched to {}"),
+    ("[OK] Громкость: {}% (без звука)", "[OK] Volume: {}% (muted)"),
+    ("[OK] Громкость: {}%", "[OK] Volume: {}%"),
+    ("[OK] Готово", "[OK] Done"),
+    // ========================================================================
+    // Процессы
+    // ========================================================================
+    ("Память", "Memory"),
+    ("Имя", "Name"),
+    ("Завершение процесса", "Terminate process"),
+    ("Принудительное завершение", "Force kill"),
+    ("Изменение приоритета", "Change priority"),
+    ("Завершить (SIGTERM)", "Terminate (SIGTERM)"),
+    ("Принудительно завершить (SIGKILL)", "Force kill (SIGKILL)"),
+    ("Установить приоритет nice {}", "Set nice priority {}"),
+    ("диспетчер процессов", "process manager"),
+    ("поиск", "search"),
+    ("самые прожорливые процессы", "the most resource-hungry processes"),
+    ("Смотрю процессы...", "Looking at processes..."),
+    ("завершить (SIGTERM)", "terminate (SIGTERM)"),
+    ("pid|имя", "pid|name"),
+    ("принудительно (SIGKILL)", "force (SIGKILL)"),
+    ("изменить приоритет", "change priority"),
+    (
+        "Формат: приоритет процесса <pid|имя> <nice>",
+        "Usage: renice <pid|name> <nice>",
+    ),
+    (
+        "Приоритет nice — число от -20 до 19",
+        "nice priority is a number from -20 to 19",
+    ),
+    ("pid|имя nice", "pid|name nice"),
+    ("Процесс «{}» не найден", "Process “{}” not found"),
+    ("Подтвердите действие...", "Confirm the action..."),
+    (
+        "{} (PID {}, {}) — CPU {}%, память {}",
+        "{} (PID {}, {}) — CPU {}%, memory {}",
+    ),
+    ("Не удалось прочитать процессы", "Failed to read processes"),
+    // ========================================================================
+    // Гайды
+    // ========================================================================
+    ("список всех гайдов", "list all guides"),
+    ("показать гайд", "show a guide"),
+    ("тема", "topic"),
+    ("поиск гайдов по слову", "search guides by keyword"),
+    ("Гайды по запросу '{}' не найдены.", "No guides found for '{}'."),
+    ("Найдено {} гайдов:\n\n", "Found {} guides:\n\n"),
+    ("\nИспользуйте: гайд <название>", "\nUse: guide <name>"),
+    (
+        "Гайд '{}' не найден.\n\nИспользуйте 'гайды' для списка доступных.",
+        "Guide '{}' not found.\n\nUse 'guides' to list the available ones.",
+    ),
+    (
+        "Найдено {} гайдов по запросу '{}':\n\n",
+        "Found {} guides for '{}':\n\n",
+    ),
+    ("\nУточните запрос: гайд <название>", "\nNarrow it down: guide <name>"),
+    ("Ваша система:\n", "Your system:\n"),
+    ("📚 Доступные гайды:\n\n", "📚 Available guides:\n\n"),
+    ("Основы Pacman", "Pacman basics"),
+    (
+        "Базовые команды пакетного менеджера Arch Linux",
+        "Basic commands of the Arch Linux package manager",
+    ),
+    (
+        "Обновить список пакетов и систему",
+        "Refresh the package list and upgrade the system",
+    ),
+    (
+        "Рекомендуется делать перед установкой новых пакетов",
+        "Recommended before installing new packages",
+    ),
+    ("Установить пакет", "Install a package"),
+    ("sudo pacman -S <пакет>", "sudo pacman -S <package>"),
+    ("Удалить пакет", "Remove a package"),
+    ("sudo pacman -R <пакет>", "sudo pacman -R <package>"),
+    ("Удалить пакет с зависимостями", "Remove a package with its dependencies"),
+    ("sudo pacman -Rns <пакет>", "sudo pacman -Rns <package>"),
+    (
+        "Удаляет также неиспользуемые зависимости и конфиги",
+        "Also removes unused dependencies and configs",
+    ),
+    ("Поиск пакета", "Search for a package"),
+    ("pacman -Ss <запрос>", "pacman -Ss <query>"),
+    ("Информация о пакете", "Package information"),
+    ("pacman -Si <пакет>", "pacman -Si <package>"),
+    ("Список установленных пакетов", "List installed packages"),
+    ("Очистить кэш пакетов", "Clean the package cache"),
+    (
+        "Удаляет старые версии из /var/cache/pacman/pkg",
+        "Removes old versions from /var/cache/pacman/pkg",
+    ),
+    ("Работа с AUR", "Working with the AUR"),
+    (
+        "Arch User Repository — пользовательские пакеты",
+        "Arch User Repository — community packages",
+    ),
+    ("Установить yay (AUR helper)", "Install yay (AUR helper)"),
+    ("Поиск в AUR", "Search the AUR"),
+    ("yay -Ss <запрос>", "yay -Ss <query>"),
+    (
+        "Ищет и в официальных репозиториях, и в AUR",
+        "Searches both the official repositories and the AUR",
+    ),
+    ("Установить пакет из AUR", "Install a package from the AUR"),
+    ("yay -S <пакет>", "yay -S <package>"),
+    (
+        "Обновить все пакеты (включая AUR)",
+        "Upgrade all packages (including the AUR)",
+    ),
+    ("Показать статистику AUR пакетов", "Show AUR package statistics"),
+    ("Зеркала pacman", "Pacman mirrors"),
+    (
+        "Выбор быстрых зеркал для загрузки пакетов",
+        "Choosing fast mirrors for package downloads",
+    ),
+    ("Посмотреть текущий список", "View the current list"),
+    ("Ранжировать зеркала в Альфонсе", "Rank mirrors in Alfons"),
+    ("зеркала ранжировать Germany", "rank mirrors Germany"),
+    (
+        "Страны можно не указывать или задать в настройках",
+        "Countries are optional or can be set in settings",
+    ),
+    ("Или через reflector", "Or with reflector"),
+    ("Обновить базы пакетов", "Refresh the package databases"),
+    ("Нужно после смены зеркал", "Needed after changing mirrors"),
+    ("Настройка WiFi", "WiFi setup"),
+    ("Подключение к беспроводной сети", "Connecting to a wireless network"),
+    (
+        "Быстрый способ — панель WiFi ассистента",
+        "Quick way — the assistant's WiFi panel",
+    ),
+    (
+        "Сканирование, подключение с паролем и режим полёта без терминала",
+        "Scanning, connecting with a password and airplane mode without a terminal",
+    ),
+    ("Проверить сетевые интерфейсы", "Check the network interfaces"),
+    ("Найдите интерфейс wlan0 или похожий", "Look for wlan0 or a similar interface"),
+    ("Включить интерфейс", "Bring the interface up"),
+    ("Сканировать доступные сети", "Scan for available networks"),
+    ("Подключиться через iwctl (iwd)", "Connect with iwctl (iwd)"),
+    (
+        "iwctl station wlan0 connect <имя_сети>",
+        "iwctl station wlan0 connect <network_name>",
+    ),
+    ("Для WPA сетей запросит пароль", "Asks for a password on WPA networks"),
+    ("Или через NetworkManager", "Or with NetworkManager"),
+    (
+        "nmcli device wifi connect <имя_сети> password <пароль>",
+        "nmcli device wifi connect <network_name> password <password>",
+    ),
+    ("Проверить подключение", "Check the connection"),
+    ("Управление сервисами", "Managing services"),
+    ("Основы работы с systemd", "systemd basics"),
+    ("Статус сервиса", "Service status"),
+    ("systemctl status <сервис>", "systemctl status <service>"),
+    ("Запустить сервис", "Start a service"),
+    ("sudo systemctl start <сервис>", "sudo systemctl start <service>"),
+    ("Остановить сервис", "Stop a service"),
+    ("sudo systemctl stop <сервис>", "sudo systemctl stop <service>"),
+    ("Перезапустить сервис", "Restart a service"),
+    ("sudo systemctl restart <сервис>", "sudo systemctl restart <service>"),
+    ("Включить автозапуск", "Enable autostart"),
+    ("sudo systemctl enable <сервис>", "sudo systemctl enable <service>"),
+    ("Отключить автозапуск", "Disable autostart"),
+    ("sudo systemctl disable <сервис>", "sudo systemctl disable <service>"),
+    ("Список всех сервисов", "List all services"),
+    ("Просмотр логов сервиса", "View service logs"),
+    ("journalctl -u <сервис> -f", "journalctl -u <service> -f"),
+    ("-f для отслеживания в реальном времени", "-f follows the log in real time"),
+    ("Драйверы видеокарты", "Graphics drivers"),
+    ("Установка драйверов для GPU", "Installing GPU drivers"),
+    ("Определить видеокарту", "Identify the graphics card"),
+    ("Или определить автоматически", "Or detect it automatically"),
+    ("видеокарта", "gpu"),
+    (
+        "Альфонс подберёт пакеты; установка — «установить драйверы»",
+        "Alfons will pick the packages; install them with “install drivers”",
+    ),
+    ("Для Intel", "For Intel"),
+    ("Для AMD", "For AMD"),
+    ("Для NVIDIA (GTX 16xx, RTX и новее)", "For NVIDIA (GTX 16xx, RTX and newer)"),
+    (
+        "Для ядер кроме linux — nvidia-open-dkms и заголовки ядра. После установки нужна перезагрузка",
+        "For kernels other than linux use nvidia-open-dkms and the kernel headers. Reboot after installation",
+    ),
+    ("Для NVIDIA (GTX 9xx/10xx)", "For NVIDIA (GTX 9xx/10xx)"),
+    (
+        "Старые карты не поддерживаются открытым модулем",
+        "Older cards are not supported by the open module",
+    ),
+    ("Для NVIDIA (открытый)", "For NVIDIA (open source)"),
+    ("Проверить драйвер", "Check the driver"),
+    ("Настройка звука", "Audio setup"),
+    ("Pipewire и управление аудио", "Pipewire and audio control"),
+    ("Установить Pipewire", "Install Pipewire"),
+    ("Включить сервис", "Enable the service"),
+    ("Установить графический микшер", "Install a graphical mixer"),
+    ("Проверить устройства вывода", "Check the output devices"),
+    ("Установить громкость", "Set the volume"),
+    (
+        "Переключить устройство по умолчанию",
+        "Switch the default device",
+    ),
+    ("ID можно узнать из wpctl status", "Get the ID from wpctl status"),
+    ("Или попросите ассистента", "Or ask the assistant"),
+    ("переключи звук на наушники", "switch audio to headphones"),
+    (
+        "Также: громкость 50, выключи звук, что играет",
+        "Also: volume 50, mute, now playing",
+    ),
+    ("Локализация системы", "System localization"),
+    ("Настройка языка и раскладки", "Language and keyboard layout setup"),
+    ("Раскомментировать нужные локали", "Uncomment the locales you need"),
+    (
+        "Раскомментируйте en_US.UTF-8 и ru_RU.UTF-8",
+        "Uncomment en_US.UTF-8 and ru_RU.UTF-8",
+    ),
+    ("Сгенерировать локали", "Generate the locales"),
+    ("Установить системную локаль", "Set the system locale"),
+    ("Настроить раскладку клавиатуры", "Configure the keyboard layout"),
+    ("Переключение по Alt+Shift", "Switch with Alt+Shift"),
+    ("Проверить настройки", "Check the settings"),
+    ("Резервное копирование", "Backups"),
+    ("Создание бэкапов системы", "Creating system backups"),
+    ("Установить Timeshift", "Install Timeshift"),
+    ("Создать снимок системы", "Create a system snapshot"),
+    (
+        "sudo timeshift --create --comments 'Мой бэкап'",
+        "sudo timeshift --create --comments 'My backup'",
+    ),
+    ("Список снимков", "List snapshots"),
+    ("Восстановить из снимка", "Restore from a snapshot"),
+    ("Выберите снимок интерактивно", "Pick the snapshot interactively"),
+    ("Настроить автоматические снимки", "Configure automatic snapshots"),
+    ("Графический интерфейс для настройки", "Graphical configuration tool"),
+    ("Снимки из ассистента", "Snapshots from the assistant"),
+    (
+        "Перед обновлением снимок создаётся сам; откат — команда «откат»",
+        "A snapshot is taken before every update; roll back with “rollback”",
+    ),
+    // ========================================================================
+    // Экспорт, установка, привилегии, мониторинг
+    // ========================================================================
+    ("# {}\n\n_Экспорт из {}, {}_\n", "# {}\n\n_Exported from {}, {}_\n"),
+    ("Экспорт из {}, {}", "Exported from {}, {}"),
+    (
+        "[X] Нечего экспортировать: сообщений нет",
+        "[X] Nothing to export: no messages",
+    ),
+    (
+        "[X] Не найден каталог для сохранения",
+        "[X] No directory found to save into",
+    ),
+    (
+        "[OK] Экспортировано сообщений: {} ({})\n{}",
+        "[OK] Messages exported: {} ({})\n{}",
+    ),
+    ("[X] Не удалось сохранить {}: {}", "[X] Failed to save {}: {}"),
+    (
+        "[X] Не удалось определить домашнюю директорию",
+        "[X] Could not determine the home directory",
+    ),
+    (
+        "[X] Не удалось найти исполняемый файл: {}",
+        "[X] Could not find the executable: {}",
+    ),
+    (
+        "[X] Не удалось создать директорию: {}",
+        "[X] Failed to create the directory: {}",
+    ),
+    (
+        "[X] Не удалось скопировать бинарник: {}",
+        "[X] Failed to copy the binary: {}",
+    ),
+    (
+        "[X] Не удалось установить права: {}",
+        "[X] Failed to set permissions: {}",
+    ),
+    (
+        "[X] Не удалось создать .desktop файл: {}",
+        "[X] Failed to create the .desktop file: {}",
+    ),
+    (
+        "[OK] Альфонс установлен!\nБинарник: {}\nЯрлык добавлен в меню приложений.\nПерезапустите меню или выполните: update-desktop-database",
+        "[OK] Alfons installed!\nBinary: {}\nA shortcut was added to the application menu.\nRestart the menu or run: update-desktop-database",
+    ),
+    ("бинарник: {}", "binary: {}"),
+    ("иконка: {}", "icon: {}"),
+    ("[OK] Альфонс удалён из системы", "[OK] Alfons removed from the system"),
+    ("[X] Ошибки при удалении: {}", "[X] Errors during removal: {}"),
+    ("Ошибка pkexec: {}", "pkexec error: {}"),
+    (
+        "Действие отменено или завершилось с ошибкой",
+        "The action was cancelled or failed",
+    ),
+    ("Ошибка записи: {}", "Write error: {}"),
+    ("Запись отменена", "Write cancelled"),
+    ("Б", "B"),
+    ("КБ", "KB"),
+    ("МБ", "MB"),
+    ("ГБ", "GB"),
+    ("ТБ", "TB"),
+    ("{}/с", "{}/s"),
+    ("{} / {} (использовано)", "{} / {} (used)"),
+    ("Не удалось получить", "Unavailable"),
+    ("Неизвестно", "Unknown"),
+    (
+        "{} — {}% ({} ядер, load average: {})",
+        "{} — {}% ({} cores, load average: {})",
+    ),
+    ("датчики температуры не найдены", "no temperature sensors found"),
+    ("батарея не найдена", "no battery found"),
+    ("приём {}, передача {}", "down {}, up {}"),
+    // ========================================================================
+    // Интерфейс: шапка и настройки
+    // ========================================================================
+    ("Ollama подключена", "Ollama connected"),
+    ("Ollama недоступна", "Ollama unavailable"),
+    (
+        "Уязвимых пакетов: {}. Нажмите для отчёта",
+        "Vulnerable packages: {}. Click for the report",
+    ),
+    ("Запланировано на {}", "Scheduled for {}"),
+    ("Отменить таймер", "Cancel timer"),
+    ("Панели", "Panels"),
+    ("Настройки", "Settings"),
+    ("Мониторинг системы", "System monitor"),
+    ("Персонализация", "Personalization"),
+    ("Цвет темы:", "Theme color:"),
+    ("Язык:", "Language:"),
+    ("ИИ (Ollama)", "AI (Ollama)"),
+    ("[OK] Ollama установлена", "[OK] Ollama installed"),
+    ("[X] Ollama не установлена", "[X] Ollama not installed"),
+    ("Установить Ollama", "Install Ollama"),
+    ("[OK] Сервис запущен", "[OK] Service running"),
+    ("[X] Сервис не запущен", "[X] Service not running"),
+    ("Запустить Ollama", "Start Ollama"),
+    ("Модель:", "Model:"),
+    ("Проверить соединение", "Test connection"),
+    ("Кастомная модель", "Custom model"),
+    ("[OK] Модель 'alfons' готова", "[OK] Model 'alfons' is ready"),
+    ("Использовать alfons", "Use alfons"),
+    ("Модель 'alfons' не создана", "Model 'alfons' not created"),
+    (
+        "Создаёт модель с оптимизированным промптом",
+        "Creates a model with an optimized prompt",
+    ),
+    ("Создать модель alfons", "Create the alfons model"),
+    ("Чат", "Chat"),
+    ("X Очистить чат", "X Clear chat"),
+    ("Пакетный менеджер", "Package manager"),
+    ("[OK] yay установлен", "[OK] yay installed"),
+    ("[X] yay не найден", "[X] yay not found"),
+    ("Проверить", "Check"),
+    ("Установить yay", "Install yay"),
+    ("драйвер: {}", "driver: {}"),
+    ("[OK] Драйверы установлены", "[OK] Drivers installed"),
+    ("Установить драйверы", "Install drivers"),
+    ("Определяется...", "Detecting..."),
+    ("Подробнее", "Details"),
+    ("Сессии чата", "Chat sessions"),
+    ("Хранить чатов:", "Chats to keep:"),
+    (
+        "Более старые сессии удаляются при создании новой",
+        "Older sessions are deleted when a new one is created",
+    ),
+    ("Снимки системы", "System snapshots"),
+    ("Снимок перед обновлением", "Snapshot before updating"),
+    (
+        "snapper, timeshift или btrfs; пропускается, если снимки делает snap-pac или timeshift-autosnap",
+        "snapper, timeshift or btrfs; skipped if snap-pac or timeshift-autosnap already takes snapshots",
+    ),
+    ("Создать снимок", "Create snapshot"),
+    ("Страны (через запятую):", "Countries (comma-separated):"),
+    ("Только HTTPS", "HTTPS only"),
+    ("Количество:", "Count:"),
+    ("Ранжировать зеркала", "Rank mirrors"),
+    ("Замеряю задержку зеркал...", "Measuring mirror latency..."),
+    ("Новый список: {} зеркал", "New list: {} mirrors"),
+    ("Предпросмотр изменений", "Preview changes"),
+    ("Записать mirrorlist", "Write mirrorlist"),
+    ("Горячие клавиши", "Keyboard shortcuts"),
+    ("Ctrl+L — очистить чат", "Ctrl+L — clear chat"),
+    ("Ctrl+N — новый чат", "Ctrl+N — new chat"),
+    ("Ctrl+F — поиск по истории", "Ctrl+F — search history"),
+    ("Ctrl+E — экспорт чата", "Ctrl+E — export chat"),
+    ("Esc — закрыть панель", "Esc — close panel"),
+    ("↑/↓ — история команд", "↑/↓ — command history"),
+    ("О программе", "About"),
+    ("{} — помощник для Arch Linux", "{} — an Arch Linux assistant"),
+    ("Установка", "Installation"),
+    ("[OK] Установлено в систему", "[OK] Installed in the system"),
+    ("Удалить из системы", "Remove from the system"),
+    ("Не установлено", "Not installed"),
+    ("Добавит ярлык в меню приложений", "Adds a shortcut to the application menu"),
+    ("Установить в систему", "Install in the system"),
+    ("~/.local/bin не в PATH", "~/.local/bin is not in PATH"),
+    ("Добавьте в .bashrc/.zshrc:", "Add to .bashrc/.zshrc:"),
+    ("Выполнить команды", "Run commands"),
+    ("Запустить в терминале?\n\n{}", "Run in a terminal?\n\n{}"),
+    (
+        "Изменение сообщения: Enter — новый вариант, Esc — отмена",
+        "Editing message: Enter — new answer, Esc — cancel",
+    ),
+    ("Введите команду...", "Enter a command..."),
+    ("ОТПРАВИТЬ", "SEND"),
+    // ========================================================================
+    // Интерфейс: диалоги, экспорт, поиск, сессии, сообщения
+    // ========================================================================
+    (
+        "Tab — подставить, ↑↓ — выбор, Esc — скрыть",
+        "Tab — insert, ↑↓ — select, Esc — hide",
+    ),
+    ("Пакет:", "Package:"),
+    ("название...", "name..."),
+    ("Отмена", "Cancel"),
+    ("Найти", "Search"),
+    ("Подтвердить", "Confirm"),
+    ("Проверяю пакет в AUR...", "Checking the package in the AUR..."),
+    (
+        "Пакет из AUR — проверьте PKGBUILD перед установкой",
+        "AUR package — review the PKGBUILD before installing",
+    ),
+    ("Сопровождающий:", "Maintainer:"),
+    ("нет (пакет-сирота)", "none (orphaned package)"),
+    ("Голоса:", "Votes:"),
+    ("{} (популярность {})", "{} (popularity {})"),
+    ("Обновлён:", "Updated:"),
+    ("Устарел:", "Out of date:"),
+    ("помечен с {}", "flagged since {}"),
+    (
+        "[OK] Опасных конструкций не найдено",
+        "[OK] No dangerous constructs found",
+    ),
+    ("[!] строка {}: {}", "[!] line {}: {}"),
+    (
+        "[!] Проверка пакета в AUR не завершилась",
+        "[!] The AUR package check did not finish",
+    ),
+    (
+        "[!] --yes не действует для пакетов из AUR",
+        "[!] --yes does not apply to AUR packages",
+    ),
+    (
+        "[!] --yes не действует для критических команд из ответа ИИ",
+        "[!] --yes does not apply to critical commands from an AI answer",
+    ),
+    ("ИИ читает PKGBUILD...", "The AI is reading the PKGBUILD..."),
+    ("Мнение ИИ:", "AI opinion:"),
+    ("Описать PKGBUILD с помощью ИИ", "Describe the PKGBUILD with AI"),
+    ("Экспорт чата", "Export chat"),
+    ("Формат:", "Format:"),
+    ("Весь чат ({} сообщ.)", "Whole chat ({} msgs)"),
+    ("Диапазон сообщений", "Message range"),
+    ("с", "from"),
+    ("по", "to"),
+    ("Первое:", "First:"),
+    ("Последнее:", "Last:"),
+    ("Сохранить в файл", "Save to file"),
+    ("Копировать", "Copy"),
+    ("Скопировать в буфер обмена", "Copy to clipboard"),
+    ("[OK] Скопировано сообщений: {} ({})", "[OK] Messages copied: {} ({})"),
+    ("Все", "All"),
+    ("За всё время", "All time"),
+    ("Сегодня", "Today"),
+    ("За неделю", "Past week"),
+    ("За месяц", "Past month"),
+    ("Поиск по истории", "Search history"),
+    ("Что найти? Например: bluetooth", "What to find? For example: bluetooth"),
+    ("Введите запрос", "Enter a query"),
+    ("Ничего не найдено", "Nothing found"),
+    ("Индексация истории…", "Indexing history…"),
+    ("Найдено: {}", "Found: {}"),
+    ("Открыть в чате", "Open in chat"),
+    ("+ Новый", "+ New"),
+    ("Новый чат (Ctrl+N)", "New chat (Ctrl+N)"),
+    (
+        "Двойной щелчок — переименовать, ПКМ — меню",
+        "Double-click — rename, right-click — menu",
+    ),
+    ("Переименовать", "Rename"),
+    ("Экспорт...", "Export..."),
+    ("Удалить", "Delete"),
+    ("изменить", "edit"),
+    (
+        "Изменить вопрос и получить новый ответ",
+        "Edit the question and get a new answer",
+    ),
+    ("заново", "retry"),
+    ("Сгенерировать другой вариант ответа", "Generate another answer"),
+    ("Нажмите чтобы скопировать", "Click to copy"),
+    ("код", "code"),
+    ("Выполнить", "Run"),
+    (
+        "Запустить в терминале после подтверждения",
+        "Run in a terminal after confirmation",
+    ),
+    // ========================================================================
+    // Интерфейс: мониторинг и панели
+    // ========================================================================
+    ("Ядро {}: {}%", "Core {}: {}%"),
+    ("Память: {} / {}", "Memory: {} / {}"),
+    ("Сеть: ↓ {}  ↑ {}", "Network: ↓ {}  ↑ {}"),
+    ("Диски:", "Disks:"),
+    ("Температура:", "Temperature:"),
+    ("Системный журнал", "System journal"),
+    ("Сеть и WiFi", "Network and WiFi"),
+    ("Текущая загрузка", "Current boot"),
+    ("Предыдущая", "Previous"),
+    ("Все загрузки", "All boots"),
+    ("Записей не найдено", "No entries found"),
+    ("Сервис:", "Service:"),
+    ("все", "all"),
+    ("Важность:", "Priority:"),
+    ("до {}", "up to {}"),
+    ("С:", "From:"),
+    ("По:", "To:"),
+    ("сейчас", "now"),
+    ("Обновить", "Refresh"),
+    ("Записей: {}", "Entries: {}"),
+    ("Ошибок: {}", "Errors: {}"),
+    ("Выбрать ошибки", "Select errors"),
+    ("Снять выбор", "Clear selection"),
+    ("Объясни ошибки ({})", "Explain errors ({})"),
+    (
+        "Отправить выбранные записи ИИ (без выбора — все ошибки)",
+        "Send the selected entries to the AI (no selection — all errors)",
+    ),
+    ("ИИ разбирает ошибки...", "The AI is analysing the errors..."),
+    ("Объяснение", "Explanation"),
+    ("Получаю состояние сети...", "Fetching network status..."),
+    ("Управление через {}", "Managed by {}"),
+    ("Доступные сети", "Available networks"),
+    ("Сканировать", "Scan"),
+    ("Подключение:", "Connection:"),
+    ("нет", "none"),
+    ("Шлюз:", "Gateway:"),
+    ("Режим полёта", "Airplane mode"),
+    ("Отключиться", "Disconnect"),
+    ("Пароль:", "Password:"),
+    ("Подключиться", "Connect"),
+    ("Поиск:", "Search:"),
+    ("имя, PID, пользователь...", "name, PID, user..."),
+    ("Сортировка:", "Sort by:"),
+    ("Показано: {} из {}", "Shown: {} of {}"),
+    ("Пользователь", "User"),
+    ("Команда", "Command"),
+    (
+        "Выберите процесс по PID для действий",
+        "Select a process by PID to act on it",
+    ),
+    ("Завершить", "Terminate"),
+    ("Убить", "Kill"),
+    ("Применить", "Apply"),
+    ("Фильтр:", "Filter:"),
+    ("Только с ошибками", "Failed only"),
+    ("Всего: {}", "Total: {}"),
+    ("С ошибками: {}", "Failed: {}"),
+    ("Сервис", "Service"),
+    ("Состояние", "State"),
+    ("Автозапуск", "Autostart"),
+    ("Действия", "Actions"),
+    ("автозапуск −", "autostart −"),
+    ("автозапуск +", "autostart +"),
+    ("Загрузка списка...", "Loading the list..."),
+    // ========================================================================
+    // Макросы
+    // ========================================================================
+    ("Не найден каталог настроек", "Config directory not found"),
+    ("Не удалось сохранить макросы: {}", "Failed to save macros: {}"),
+    (
+        "[X] Укажите имя без «=» и «;» и хотя бы одну команду",
+        "[X] Enter a name without “=” and “;” and at least one command",
+    ),
+    ("Запускаю макрос «{}», шагов: {}", "Running macro “{}”, steps: {}"),
+    ("Шаг {}/{}: {}", "Step {}/{}: {}"),
+    ("Макрос «{}» завершён:", "Macro “{}” finished:"),
+    ("[X] {} — отменено", "[X] {} — cancelled"),
+    ("[!] {} — команда не распознана", "[!] {} — command not recognized"),
+    ("[X] {} — ошибка", "[X] {} — failed"),
+    ("Макросы", "Macros"),
+    ("Удалить макрос", "Delete macro"),
+    ("Изменить", "Edit"),
+    ("Макросов пока нет", "No macros yet"),
+    ("Имя:", "Name:"),
+    ("утро", "morning"),
+    ("Команды (через ;):", "Commands (separated by ;):"),
+    (
+        "обновить систему; очистить кэш",
+        "update system; clear cache",
+    ),
+    ("Сохранить макрос", "Save macro"),
+    (
+        "Введите имя макроса в чат, чтобы выполнить его шаги",
+        "Type a macro name in the chat to run its steps",
+    ),
+    // ========================================================================
+    // Командная строка
+    // ========================================================================
+    (
+        "{} в терминале. Команды — как в окне, остальное — вопрос ИИ. Выход: exit или Ctrl+D",
+        "{} in the terminal. Commands work as in the window, anything else goes to the AI. Quit: exit or Ctrl+D",
+    ),
+    ("Отменено", "Cancelled"),
+    ("Подтвердить? [y/N]", "Confirm? [y/N]"),
+    (
+        "[X] Эта команда доступна только в окне приложения",
+        "[X] This command is only available in the application window",
+    ),
+    ("работает", "running"),
+    ("не запущена", "not running"),
+    ("неизвестно", "unknown"),
+    ("Ollama: {}", "Ollama: {}"),
+    ("Обновления: {}", "Updates: {}"),
+    ("Уязвимые пакеты: {}", "Vulnerable packages: {}"),
+    ("Выполняется задача", "A task is running"),
+    ("[X] Окно Альфонса не открыто", "[X] The Alfons window is not open"),
+    // ========================================================================
+    // IPC
+    // ========================================================================
+    ("Ожидает подтверждения в окне", "Waiting for confirmation in the window"),
+    ("[X] Приложение не ответило", "[X] The application did not respond"),
+];
+
+// ============================================================================
+// Промпты ИИ на английском
+// ============================================================================
+
+const SYSTEM_PROMPT: &str = r#"You are Alfons, an Arch Linux assistant. Answer briefly and to the point in English.
+
+AVAILABLE TOOLS:
+{}
+Usage: [TOOL:name] or [TOOL:name:argument]. Tool names are identifiers — write them exactly as listed.
+
+AVAILABLE COMMANDS (you can run them for the user):
+Format: [CMD:command]; <...> is a required argument, [...] is optional
+{}
+  Guide topics: pacman, aur, mirrors, wifi, systemd, gpu, audio, locale, backup
+  A snapshot is taken automatically before an update
+
+IMPORTANT RULES:
+1. Use [TOOL:...] to get information (time, date)
+2. Use [CMD:...] to run commands for the user
+3. Install/remove/update COMMANDS ONLY open a dialog! Do NOT say "installed" or "updated" right away!
+4. After an install command say "a confirmation dialog will open" or just use the command
+5. If asked "how do I install" — explain or suggest [CMD:guide pacman]
+6. Run DANGEROUS COMMANDS ({}) ONLY if the user EXPLICITLY asked for it!
+7. For "what can you do?" or "which commands are there?" — ANSWER IN TEXT, do NOT run commands!
+8. Put terminal commands in ```bash ... ``` blocks — the user can copy or run them. You may use **bold**, `code`, lists and headings
+
+EXAMPLES:
+- "What time is it?" -> "It is [TOOL:время]"
+- "Install firefox" -> "[CMD:install firefox]" (do NOT say "installed"!)
+- "Find the vim package" -> "[CMD:search vim]"
+- "How do I set up wifi?" -> "[CMD:guide wifi]"
+- "Connect to wifi" -> "[CMD:wifi]"
+- "What is my IP?" -> "[TOOL:сеть]"
+- "Is my laptop overheating?" -> "[TOOL:температура]"
+- "Switch audio to headphones" -> "[CMD:switch audio to headphones]"
+- "Make it louder" -> "[CMD:volume +10]"
+- "What is eating my CPU?" -> "[TOOL:топ_процессы]"
+- "Firefox is frozen" -> "[CMD:terminate firefox]"
+- "Packages download slowly" -> "[CMD:rank mirrors]"
+- "Are there any vulnerabilities?" -> "[CMD:security]"
+- "Everything broke after the update" -> "[CMD:rollback]"
+- "The graphics card stopped working after an update" -> "[TOOL:ядро]"
+- "Which drivers do I need?" -> "[TOOL:видеокарта]"
+- "How are the services doing?" -> "[TOOL:сервисы_с_ошибками]"
+- "Why is there no sound?" -> "[TOOL:ошибки_сервиса:pipewire]"
+- "Start bluetooth" -> "[CMD:start service bluetooth]"
+- "Show the guides" -> "[CMD:guides]"
+- "Update the system" -> "[CMD:update]" (a dialog will open)
+- "What can you do?" -> List your abilities IN TEXT, do NOT run commands!
+- "Turn off the computer" -> "[CMD:shutdown]" (only when explicitly asked!)
+- "Turn off the PC in an hour" -> "[CMD:shutdown in 1 hour]"
+
+Be brief. Do NOT write text after install/remove/update commands."#;
+
+const MODELFILE: &str = r#"FROM llama3
+
+SYSTEM """
+You are Alfons, a smart Arch Linux assistant. Answer briefly and to the point in English.
+
+AVAILABLE TOOLS:
+- [TOOL:время] - current time
+- [TOOL:дата] - current date
+- [TOOL:память] - RAM usage
+- [TOOL:диск] - disk usage
+- [TOOL:cpu] - CPU information
+
+AVAILABLE COMMANDS (format: [CMD:command]):
+{}
+RULES:
+1. Install/remove commands ONLY open a dialog - do NOT say "installed"!
+2. Dangerous commands ({}) - ONLY when explicitly asked!
+"""
+
+PARAMETER temperature 0.7
+PARAMETER top_p 0.9
+PARAMETER num_ctx 4096
+"#;
+
+const PKGBUILD_SUMMARY_PROMPT: &str = "\
+You are reviewing a PKGBUILD from the AUR before installation. Briefly (3-6 points), in English, describe: \
+where the sources are downloaded from, what happens during build and install, \
+and whether there are suspicious actions (downloading scripts, sudo, writing outside $pkgdir, obfuscation). \
+Do not invent anything that is not in the file.";
+
+const EXPLAIN_PROMPT: &str = "\
+You are analysing Arch Linux system journal entries (journalctl). In English, briefly: \
+1) what went wrong, in plain words; 2) the likely cause; \
+3) concrete steps or commands to fix it. \
+Rely only on the given entries, do not invent anything. If the entries do not look like an error, say so.";
+
+const USAGE: &str = "Usage: alfons [command] [--json] [--yes]
+
+Without arguments the application window opens.
+
+Commands:
+  ask \"question\"   AI answer
+  run \"command\"    assistant command, e.g. \"search vim\"
+  guide [topic]    a guide or the list of guides
+  repl             terminal dialog: commands and AI questions
+  show [text]      show the window, text goes to the input field
+  status           Ollama, updates and vulnerabilities
+  watch            task completion signals from the open window
+  help             this help
+
+Options:
+  --json           JSON output: {\"input\", \"status\", \"output\"}
+  --yes            confirm dangerous commands without asking
+                   (except AUR packages and critical commands from AI answers)
+
+Exit codes: 0 — success, 1 — error or cancelled, 2 — command not recognized or invalid arguments,
+3 — the command is waiting for confirmation in the window";
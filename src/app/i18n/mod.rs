@@ -0,0 +1,108 @@
+//! Локализация
+//!
+//! Русские строки в коде служат ключами каталога (как msgid в gettext):
+//! `tr` возвращает перевод для выбранного языка, а если перевода нет —
+//! исходный русский текст. Язык хранится в настройках и меняется на лету:
+//! интерфейс перерисовывается каждый кадр и сразу берёт новые строки.
+
+mod en;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+// ============================================================================
+// Язык
+// ============================================================================
+
+/// Язык интерфейса и ответов ИИ
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    Ru,
+    En,
+}
+
+impl Language {
+    pub const ALL: &'static [Language] = &[Language::Ru, Language::En];
+
+    /// Название языка на нём самом (для выбора в настройках)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::Ru => "Русский",
+            Language::En => "English",
+        }
+    }
+
+    /// Язык системы из LC_ALL / LC_MESSAGES / LANG: русский для `ru_*`,
+    /// английский для остальных локалей, русский без локали
+    pub fn from_env() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty());
+        match locale {
+            Some(value) if value == "C" || value == "POSIX" || value.starts_with("ru") => {
+                Language::Ru
+            }
+            Some(_) => Language::En,
+            None => Language::Ru,
+        }
+    }
+}
+
+impl Default for Language {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+/// Переключает язык (вызывается при запуске и из настроек)
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => Language::En,
+        _ => Language::Ru,
+    }
+}
+
+// ============================================================================
+// Перевод строк
+// ============================================================================
+
+fn english() -> &'static HashMap<&'static str, &'static str> {
+    static CATALOG: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    CATALOG.get_or_init(|| en::STRINGS.iter().copied().collect())
+}
+
+/// Перевод строки на текущий язык
+pub fn tr(text: &str) -> &str {
+    match language() {
+        Language::Ru => text,
+        Language::En => english().get(text).copied().unwrap_or(text),
+    }
+}
+
+/// Перевод шаблона с подстановкой аргументов вместо `{}` по порядку
+pub fn trf(template: &str, args: &[&dyn Display]) -> String {
+    let mut output = String::new();
+    let mut args = args.iter();
+    let mut parts = tr(template).split("{}");
+    if let Some(first) = parts.next() {
+        output.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            output.push_str(&arg.to_string());
+        }
+        output.push_str(part);
+    }
+    output
+}
@@ -1,5 +1,6 @@
 //! Установка приложения в систему
 
+use super::i18n::{tr, trf};
 use std::fs;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
@@ -39,7 +40,7 @@ pub fn install() -> InstallResult {
         Some(h) => h,
         None => {
             return InstallResult {
-                message: "[X] Не удалось определить домашнюю директорию".into(),
+                message: tr("[X] Не удалось определить домашнюю директорию").into(),
             }
         }
     };
@@ -49,7 +50,7 @@ pub fn install() -> InstallResult {
         Ok(p) => p,
         Err(e) => {
             return InstallResult {
-                message: format!("[X] Не удалось найти исполняемый файл: {}", e),
+                message: trf("[X] Не удалось найти исполняемый файл: {}", &[&e]),
             }
         }
     };
@@ -62,7 +63,7 @@ pub fn install() -> InstallResult {
     for dir in [&bin_dir, &desktop_dir, &icon_dir] {
         if let Err(e) = fs::create_dir_all(dir) {
             return InstallResult {
-                message: format!("[X] Не удалось создать директорию: {}", e),
+                message: trf("[X] Не удалось создать директорию: {}", &[&e]),
             };
         }
     }
@@ -71,14 +72,14 @@ pub fn install() -> InstallResult {
     let bin_path = home.join(INSTALL_BIN_PATH);
     if let Err(e) = fs::copy(&current_exe, &bin_path) {
         return InstallResult {
-            message: format!("[X] Не удалось скопировать бинарник: {}", e),
+            message: trf("[X] Не удалось скопировать бинарник: {}", &[&e]),
         };
     }
 
     // Устанавливаем права на исполнение
     if let Err(e) = fs::set_permissions(&bin_path, fs::Permissions::from_mode(0o755)) {
         return InstallResult {
-            message: format!("[X] Не удалось установить права: {}", e),
+            message: trf("[X] Не удалось установить права: {}", &[&e]),
         };
     }
 
@@ -103,7 +104,7 @@ pub fn install() -> InstallResult {
     let desktop_content = generate_desktop_file(&bin_path, &icon_path);
     if let Err(e) = fs::write(&desktop_path, desktop_content) {
         return InstallResult {
-            message: format!("[X] Не удалось создать .desktop файл: {}", e),
+            message: trf("[X] Не удалось создать .desktop файл: {}", &[&e]),
         };
     }
 
@@ -113,12 +114,12 @@ pub fn install() -> InstallResult {
         .output();
 
     InstallResult {
-        message: format!(
+        message: trf(
             "[OK] Альфонс установлен!\n\
              Бинарник: {}\n\
              Ярлык добавлен в меню приложений.\n\
              Перезапустите меню или выполните: update-desktop-database",
-            bin_path.display()
+            &[&bin_path.display()],
         ),
     }
 }
@@ -129,7 +130,7 @@ pub fn uninstall() -> InstallResult {
         Some(h) => h,
         None => {
             return InstallResult {
-                message: "[X] Не удалось определить домашнюю директорию".into(),
+                message: tr("[X] Не удалось определить домашнюю директорию").into(),
             }
         }
     };
@@ -143,7 +144,7 @@ pub fn uninstall() -> InstallResult {
     // Удаляем файлы
     if bin_path.exists() {
         if let Err(e) = fs::remove_file(&bin_path) {
-            errors.push(format!("бинарник: {}", e));
+            errors.push(trf("бинарник: {}", &[&e]));
         }
     }
 
@@ -155,17 +156,17 @@ pub fn uninstall() -> InstallResult {
 
     if icon_path.exists() {
         if let Err(e) = fs::remove_file(&icon_path) {
-            errors.push(format!("иконка: {}", e));
+            errors.push(trf("иконка: {}", &[&e]));
         }
     }
 
     if errors.is_empty() {
         InstallResult {
-            message: "[OK] Альфонс удалён из системы".into(),
+            message: tr("[OK] Альфонс удалён из системы").into(),
         }
     } else {
         InstallResult {
-            message: format!("[X] Ошибки при удалении: {}", errors.join(", ")),
+            message: trf("[X] Ошибки при удалении: {}", &[&errors.join(", ")]),
         }
     }
 }
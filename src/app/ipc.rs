@@ -0,0 +1,273 @@
+//! Локальный IPC: Unix-сокет запущенного приложения
+//!
+//! Сокет `$XDG_RUNTIME_DIR/alfons-assistant.sock`, запрос и ответ — по строке
+//! JSON: `{"method": "RunCommand", "text": "обновить систему"}` →
+//! `{"status": "ok", "output": "..."}`. После `Subscribe` соединение остаётся
+//! открытым и получает сигналы `{"signal": "TaskCompleted", "output": "..."}`.
+//! Через сокет же работает единственный экземпляр: второй запуск поднимает
+//! уже открытое окно.
+
+use super::cli::Status;
+use super::constants::{IPC_REPLY_TIMEOUT_SECS, IPC_SOCKET_NAME, IPC_SUBSCRIBER_WRITE_TIMEOUT_MS};
+use super::i18n::tr;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// ============================================================================
+// Протокол
+// ============================================================================
+
+/// Запрос к запущенному приложению
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method")]
+pub enum Request {
+    /// Вопрос ИИ; ответ приходит, когда ИИ договорит
+    Ask { text: String },
+    /// Команда ассистента, как ввод в чат
+    RunCommand { text: String },
+    /// Показать окно, при необходимости — с текстом в поле ввода
+    ShowWindow {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        text: Option<String>,
+    },
+    /// Состояние: Ollama, занятость, обновления
+    GetStatus,
+    /// Подписка на сигналы о завершении задач
+    Subscribe,
+}
+
+/// Ответ на запрос
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Response {
+    pub status: Status,
+    #[serde(default)]
+    pub output: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<AppState>,
+}
+
+impl Response {
+    /// Ответ команды; строка с `[X]` означает ошибку
+    pub fn new(output: impl Into<String>) -> Self {
+        let output = output.into();
+        Self {
+            status: Status::from_output(&output),
+            output,
+            state: None,
+        }
+    }
+
+    pub fn with_status(output: impl Into<String>, status: Status) -> Self {
+        Self {
+            status,
+            output: output.into(),
+            state: None,
+        }
+    }
+}
+
+/// Состояние приложения для строки состояния
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppState {
+    pub ollama_online: bool,
+    /// Выполняется фоновая задача
+    pub busy: bool,
+    /// Доступные обновления; null — checkupdates недоступен
+    pub updates_pending: Option<usize>,
+    /// Уязвимые пакеты по последней проверке
+    pub vulnerable: Option<usize>,
+}
+
+/// Сигнал подписчикам
+#[derive(Serialize)]
+struct Signal<'a> {
+    signal: &'a str,
+    output: &'a str,
+}
+
+/// Путь к сокету: каталог сеанса пользователя, иначе /tmp с uid в имени
+pub fn socket_path() -> PathBuf {
+    match dirs::runtime_dir() {
+        Some(dir) => dir.join(IPC_SOCKET_NAME),
+        None => {
+            // SAFETY: getuid не имеет побочных эффектов и всегда успешен
+            let uid = unsafe { libc::getuid() };
+            std::env::temp_dir().join(format!("{}-{}", uid, IPC_SOCKET_NAME))
+        }
+    }
+}
+
+// ============================================================================
+// Сервер (в окне приложения)
+// ============================================================================
+
+/// Запрос, ожидающий обработки в потоке интерфейса
+pub struct Incoming {
+    pub request: Request,
+    pub reply: Sender<Response>,
+}
+
+/// Сервер сокета. Запросы читаются из `requests` в `AssistantApp::update`
+pub struct IpcServer {
+    requests: Receiver<Incoming>,
+    subscribers: Arc<Mutex<Vec<UnixStream>>>,
+    path: PathBuf,
+}
+
+impl IpcServer {
+    /// Открывает сокет. None — сокет занят другим экземпляром или недоступен
+    pub fn start(ctx: egui::Context) -> Option<Self> {
+        let path = socket_path();
+        if UnixStream::connect(&path).is_ok() {
+            return None;
+        }
+        // Файл остался от упавшего экземпляра
+        let _ = std::fs::remove_file(&path);
+
+        let listener = UnixListener::bind(&path).ok()?;
+        let _ = std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600));
+
+        let (sender, requests) = mpsc::channel();
+        let subscribers = Arc::new(Mutex::new(Vec::new()));
+        let subs = subscribers.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let sender = sender.clone();
+                let subs = subs.clone();
+                let ctx = ctx.clone();
+                thread::spawn(move || handle_connection(stream, sender, subs, ctx));
+            }
+        });
+
+        Some(Self {
+            requests,
+            subscribers,
+            path,
+        })
+    }
+
+    /// Следующий необработанный запрос
+    pub fn try_recv(&self) -> Option<Incoming> {
+        self.requests.try_recv().ok()
+    }
+
+    /// Рассылает сигнал подписчикам; отключившиеся и зависшие удаляются.
+    /// Запись ограничена таймаутом, поэтому поток интерфейса не блокируется
+    pub fn broadcast(&self, signal: &str, output: &str) {
+        let Ok(line) = serde_json::to_string(&Signal { signal, output }) else {
+            return;
+        };
+        if let Ok(mut subs) = self.subscribers.lock() {
+            subs.retain_mut(|stream| writeln!(stream, "{}", line).is_ok());
+        }
+    }
+}
+
+impl Drop for IpcServer {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Читает запрос, передаёт его окну и пишет ответ
+fn handle_connection(
+    stream: UnixStream,
+    sender: Sender<Incoming>,
+    subscribers: Arc<Mutex<Vec<UnixStream>>>,
+    ctx: egui::Context,
+) {
+    // Пустое соединение — проверка, запущено ли приложение
+    let mut line = String::new();
+    if !matches!(BufReader::new(&stream).read_line(&mut line), Ok(n) if n > 0) {
+        return;
+    }
+
+    let response = match serde_json::from_str::<Request>(&line) {
+        Ok(Request::Subscribe) => {
+            // Сигналы пишутся из потока интерфейса — не ждём подписчика дольше таймаута
+            let timeout = Duration::from_millis(IPC_SUBSCRIBER_WRITE_TIMEOUT_MS);
+            if stream.set_write_timeout(Some(timeout)).is_err() {
+                return;
+            }
+            let ok = Response::with_status(String::new(), Status::Ok);
+            if write_response(&stream, &ok) {
+                if let Ok(mut subs) = subscribers.lock() {
+                    subs.push(stream);
+                }
+            }
+            return;
+        }
+        Ok(request) => {
+            let (reply, answer) = mpsc::channel();
+            if sender.send(Incoming { request, reply }).is_err() {
+                return;
+            }
+            // Окно обрабатывает запросы в update — будим его
+            ctx.request_repaint();
+            answer
+                .recv_timeout(Duration::from_secs(IPC_REPLY_TIMEOUT_SECS))
+                .unwrap_or_else(|_| {
+                    Response::with_status(tr("[X] Приложение не ответило"), Status::Failed)
+                })
+        }
+        Err(e) => Response::with_status(format!("[X] {}", e), Status::Unknown),
+    };
+    write_response(&stream, &response);
+}
+
+fn write_response(mut stream: &UnixStream, response: &Response) -> bool {
+    serde_json::to_string(response)
+        .map(|line| writeln!(stream, "{}", line).is_ok())
+        .unwrap_or(false)
+}
+
+// ============================================================================
+// Клиент (командная строка)
+// ============================================================================
+
+/// Отправляет запрос запущенному приложению. None — приложение не запущено
+pub fn request(request: &Request) -> Option<Response> {
+    let mut stream = UnixStream::connect(socket_path()).ok()?;
+    let failed = |e: String| Response::with_status(format!("[X] {}", e), Status::Failed);
+
+    let line = serde_json::to_string(request).ok()?;
+    if let Err(e) = writeln!(stream, "{}", line) {
+        return Some(failed(e.to_string()));
+    }
+    let mut answer = String::new();
+    if let Err(e) = BufReader::new(&stream).read_line(&mut answer) {
+        return Some(failed(e.to_string()));
+    }
+    Some(serde_json::from_str(&answer).unwrap_or_else(|e| failed(e.to_string())))
+}
+
+/// Подписка на сигналы: `on_signal` получает каждую строку JSON.
+/// Возвращает false, если приложение не запущено
+pub fn subscribe(mut on_signal: impl FnMut(&str)) -> bool {
+    let Ok(mut stream) = UnixStream::connect(socket_path()) else {
+        return false;
+    };
+    let Ok(line) = serde_json::to_string(&Request::Subscribe) else {
+        return false;
+    };
+    if writeln!(stream, "{}", line).is_err() {
+        return false;
+    }
+
+    // Первая строка — подтверждение подписки
+    for line in BufReader::new(stream).lines().skip(1) {
+        match line {
+            Ok(line) => on_signal(&line),
+            Err(_) => break,
+        }
+    }
+    true
+}
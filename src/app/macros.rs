@@ -0,0 +1,197 @@
+//! Пользовательские псевдонимы и макросы
+//!
+//! Хранятся в `~/.config/alfons-assistant/macros.conf`, по одному на строку:
+//! `утро = обновить систему; очистить кэш`. Псевдоним — макрос из одного шага.
+//! Шаги выполняются по очереди как обычные команды, опасные шаги
+//! подтверждаются своими диалогами.
+
+use super::constants::CONFIG_APP_NAME;
+use super::i18n::{tr, trf};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+
+/// Имя файла макросов в каталоге настроек
+const MACROS_FILE: &str = "macros.conf";
+
+/// Заголовок файла макросов
+const FILE_HEADER: &str = "# Макросы Альфонса: имя = команда; команда; ...";
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Именованная последовательность команд
+#[derive(Debug, Clone, PartialEq)]
+pub struct Macro {
+    pub name: String,
+    pub steps: Vec<String>,
+}
+
+impl Macro {
+    /// Разбор строки `имя = шаг; шаг`. Комментарии и пустые строки — None
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (name, steps) = line.split_once('=')?;
+        let name = name.trim();
+        let steps: Vec<String> = steps
+            .split(';')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+        if name.is_empty() || name.contains(';') || steps.is_empty() {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            steps,
+        })
+    }
+
+    /// Строка для файла макросов
+    pub fn to_line(&self) -> String {
+        format!("{} = {}", self.name, self.steps.join("; "))
+    }
+
+    /// Совпадает ли ввод с именем макроса (без учёта регистра)
+    pub fn matches(&self, input: &str) -> bool {
+        self.name.to_lowercase() == input.trim().to_lowercase()
+    }
+}
+
+// ============================================================================
+// Хранение
+// ============================================================================
+
+/// Путь к файлу макросов
+fn file_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|p| p.join(CONFIG_APP_NAME).join(MACROS_FILE))
+}
+
+/// Загружает макросы; нет файла — пустой список
+pub fn load() -> Vec<Macro> {
+    file_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|text| text.lines().filter_map(Macro::parse).collect())
+        .unwrap_or_default()
+}
+
+/// Сохраняет макросы на диск
+pub fn save(macros: &[Macro]) -> Result<(), String> {
+    let path = file_path().ok_or_else(|| tr("Не найден каталог настроек").to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| trf("Не удалось сохранить макросы: {}", &[&e]))?;
+    }
+
+    let mut text = String::from(FILE_HEADER);
+    text.push('\n');
+    for m in macros {
+        text.push_str(&m.to_line());
+        text.push('\n');
+    }
+    std::fs::write(&path, text).map_err(|e| trf("Не удалось сохранить макросы: {}", &[&e]))
+}
+
+/// Добавляет макрос или заменяет одноимённый. `steps` — команды через `;`
+pub fn upsert(macros: &mut Vec<Macro>, name: &str, steps: &str) -> Result<(), String> {
+    let Some(new) = Macro::parse(&format!("{} = {}", name, steps)) else {
+        return Err(tr("[X] Укажите имя без «=» и «;» и хотя бы одну команду").to_string());
+    };
+    match macros.iter_mut().find(|m| m.matches(&new.name)) {
+        Some(existing) => *existing = new,
+        None => macros.push(new),
+    }
+    save(macros)
+}
+
+/// Поиск макроса по введённому тексту
+pub fn find<'a>(macros: &'a [Macro], input: &str) -> Option<&'a Macro> {
+    macros.iter().find(|m| m.matches(input))
+}
+
+// ============================================================================
+// Выполнение
+// ============================================================================
+
+/// Итог шага макроса
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StepOutcome {
+    Done,
+    Cancelled,
+    /// Команда выполнена с ошибкой
+    Failed,
+    NotRecognized,
+}
+
+impl StepOutcome {
+    /// Итог по тексту ответа: строка с `[X]` означает ошибку
+    pub fn from_output(output: &str) -> Self {
+        if output.lines().any(|l| l.trim_start().starts_with("[X]")) {
+            StepOutcome::Failed
+        } else {
+            StepOutcome::Done
+        }
+    }
+}
+
+/// Выполняемый макрос: оставшиеся шаги и итоги пройденных
+pub struct MacroRun {
+    name: String,
+    total: usize,
+    queue: VecDeque<String>,
+    /// Шаг, ожидающий ответа в диалоге подтверждения,
+    /// и число запущенных к тому времени фоновых задач
+    pub awaiting: Option<(String, usize)>,
+    /// Шаг, запустивший фоновую задачу, и её номер: итог шага — её результат
+    pub running: Option<(String, usize)>,
+    results: Vec<(String, StepOutcome)>,
+}
+
+impl MacroRun {
+    pub fn new(m: &Macro) -> Self {
+        Self {
+            name: m.name.clone(),
+            total: m.steps.len(),
+            queue: m.steps.iter().cloned().collect(),
+            awaiting: None,
+            running: None,
+            results: Vec::new(),
+        }
+    }
+
+    /// Следующий шаг и его номер (с единицы)
+    pub fn next_step(&mut self) -> Option<(usize, String)> {
+        let step = self.queue.pop_front()?;
+        Some((self.total - self.queue.len(), step))
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn record(&mut self, step: String, outcome: StepOutcome) {
+        self.results.push((step, outcome));
+    }
+
+    /// Сводка по шагам после завершения
+    pub fn summary(&self) -> String {
+        let mut output = trf("Макрос «{}» завершён:", &[&self.name]);
+        for (step, outcome) in &self.results {
+            output.push('\n');
+            output.push_str(&match outcome {
+                StepOutcome::Done => format!("[OK] {}", step),
+                StepOutcome::Cancelled => trf("[X] {} — отменено", &[step]),
+                StepOutcome::Failed => trf("[X] {} — ошибка", &[step]),
+                StepOutcome::NotRecognized => trf("[!] {} — команда не распознана", &[step]),
+            });
+        }
+        output
+    }
+}
@@ -0,0 +1,204 @@
+//! Разбор текста сообщений: упрощённый Markdown и блоки команд
+//!
+//! Команды встречаются в двух видах: строки `$ команда` из гайдов
+//! и блоки в тройных обратных кавычках из ответов ИИ.
+
+/// Языки блоков кода, которые можно выполнить в терминале
+const SHELL_LANGS: &[&str] = &["", "bash", "sh", "shell", "zsh", "console"];
+
+/// Часть сообщения
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    Text(String),
+    /// Блок кода; строки `$ команда` из гайдов получают язык `bash`
+    Code {
+        lang: String,
+        code: String,
+    },
+}
+
+impl Block {
+    /// Блок — команды оболочки (можно выполнить)
+    pub fn is_shell(&self) -> bool {
+        matches!(self, Block::Code { lang, .. } if SHELL_LANGS.contains(&lang.as_str()))
+    }
+}
+
+/// Делит текст на блоки; соседние строки одного вида объединяются
+pub fn blocks(text: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut text_lines: Vec<&str> = Vec::new();
+    let mut code_lines: Vec<&str> = Vec::new();
+    // Язык открытого блока ``` (None — вне блока)
+    let mut fence: Option<String> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if let Some(lang) = trimmed.strip_prefix("```") {
+            flush_text(&mut blocks, &mut text_lines);
+            match fence.take() {
+                Some(lang) => flush_code(&mut blocks, &mut code_lines, &lang),
+                None => {
+                    // Строки `$ ...` прямо перед блоком — отдельный блок
+                    flush_code(&mut blocks, &mut code_lines, "bash");
+                    fence = Some(lang.trim().to_lowercase());
+                }
+            }
+            continue;
+        }
+
+        if fence.is_some() {
+            code_lines.push(line);
+        } else if let Some(cmd) = trimmed.strip_prefix("$ ") {
+            flush_text(&mut blocks, &mut text_lines);
+            code_lines.push(cmd);
+        } else {
+            flush_code(&mut blocks, &mut code_lines, "bash");
+            text_lines.push(line);
+        }
+    }
+    // Незакрытый блок кода считаем кодом до конца сообщения
+    flush_text(&mut blocks, &mut text_lines);
+    let lang = fence.unwrap_or_else(|| "bash".to_string());
+    flush_code(&mut blocks, &mut code_lines, &lang);
+    blocks
+}
+
+/// Все команды оболочки в сообщении, по одной на строку блока
+pub fn commands(text: &str) -> Vec<String> {
+    blocks(text)
+        .into_iter()
+        .filter(Block::is_shell)
+        .flat_map(|block| match block {
+            Block::Code { code, .. } => code
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty() && !l.starts_with('#'))
+                .map(str::to_string)
+                .collect(),
+            Block::Text(_) => Vec::new(),
+        })
+        .collect()
+}
+
+fn flush_text(blocks: &mut Vec<Block>, lines: &mut Vec<&str>) {
+    let text = lines.join("\n");
+    lines.clear();
+    // Пустые строки между блоками не нужны
+    let text = text.trim_matches('\n');
+    if !text.trim().is_empty() {
+        blocks.push(Block::Text(text.to_string()));
+    }
+}
+
+fn flush_code(blocks: &mut Vec<Block>, lines: &mut Vec<&str>, lang: &str) {
+    if !lines.is_empty() {
+        blocks.push(Block::Code {
+            lang: lang.to_string(),
+            code: lines.join("\n"),
+        });
+        lines.clear();
+    }
+}
+
+// ============================================================================
+// Строки текста
+// ============================================================================
+
+/// Вид строки текстового блока
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Line<'a> {
+    /// Заголовок `#`..`######`: уровень и текст
+    Heading(usize, &'a str),
+    /// Пункт `-`, `*` или `•`
+    Bullet(&'a str),
+    /// Нумерованный пункт: номер с точкой и текст
+    Numbered(&'a str, &'a str),
+    Plain(&'a str),
+    Blank,
+}
+
+/// Определяет вид строки
+pub fn line(line: &str) -> Line<'_> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return Line::Blank;
+    }
+
+    let hashes = trimmed.chars().take_while(|c| *c == '#').count();
+    if (1..=6).contains(&hashes) {
+        if let Some(text) = trimmed[hashes..].strip_prefix(' ') {
+            return Line::Heading(hashes, text.trim());
+        }
+    }
+
+    for bullet in ["- ", "* ", "• "] {
+        if let Some(text) = trimmed.strip_prefix(bullet) {
+            return Line::Bullet(text.trim());
+        }
+    }
+
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 && digits <= 3 {
+        let rest = &trimmed[digits..];
+        if rest.starts_with(". ") || rest.starts_with(") ") {
+            return Line::Numbered(&trimmed[..digits + 1], rest[2..].trim());
+        }
+    }
+
+    Line::Plain(trimmed)
+}
+
+// ============================================================================
+// Оформление внутри строки
+// ============================================================================
+
+/// Оформление фрагмента строки
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpanStyle {
+    Normal,
+    /// `**жирный**`
+    Bold,
+    /// `` `код` ``
+    Code,
+}
+
+/// Делит строку на фрагменты; непарные `**` и `` ` `` остаются текстом
+pub fn spans(text: &str) -> Vec<(&str, SpanStyle)> {
+    let mut spans = Vec::new();
+    let mut rest = text;
+    let mut plain_start = 0;
+    let offset = |s: &str| s.as_ptr() as usize - text.as_ptr() as usize;
+
+    while !rest.is_empty() {
+        let marker = if rest.starts_with("**") {
+            Some(("**", SpanStyle::Bold))
+        } else if rest.starts_with('`') {
+            Some(("`", SpanStyle::Code))
+        } else {
+            None
+        };
+
+        if let Some((marker, style)) = marker {
+            let inner = &rest[marker.len()..];
+            if let Some(end) = inner.find(marker).filter(|end| *end > 0) {
+                let start = offset(rest);
+                if start > plain_start {
+                    spans.push((&text[plain_start..start], SpanStyle::Normal));
+                }
+                spans.push((&inner[..end], style));
+                rest = &inner[end + marker.len()..];
+                plain_start = offset(rest);
+                continue;
+            }
+        }
+
+        let len = rest.chars().next().map_or(1, char::len_utf8);
+        rest = &rest[len..];
+    }
+
+    if plain_start < text.len() {
+        spans.push((&text[plain_start..], SpanStyle::Normal));
+    }
+    spans
+}
@@ -3,24 +3,48 @@
 //! Структура:
 //! - `constants` — все константы и строки
 //! - `config` — настройки пользователя
+//! - `i18n` — локализация (русский и английский)
 //! - `chat` — история сообщений и фоновые задачи
+//! - `sessions` — сессии чата на диске
+//! - `search` — полнотекстовый поиск по сессиям
+//! - `export` — экспорт переписки в Markdown, JSON и HTML
+//! - `markup` — разбор Markdown и блоков команд в сообщениях
+//! - `completion` — автодополнение команд в поле ввода
+//! - `macros` — пользовательские псевдонимы и макросы
 //! - `commands` — обработка команд
 //! - `guides` — обучающие гайды
 //! - `ai` — интеграция с Ollama
 //! - `ui` — графический интерфейс
 //! - `assistant_app` — главная структура приложения
+//! - `cli` — режим командной строки без окна
+//! - `ipc` — Unix-сокет для других программ и единственного экземпляра
 //! - `installer` — установка в систему
+//! - `exec` — запуск внешних программ от имени пользователя
+//! - `privileged` — действия с правами root (pkexec)
+//! - `monitor` — мониторинг системы через /proc и /sys
 
 pub mod ai; // Локальный ИИ (Ollama)
 pub mod assistant_app; // Главная структура
 pub mod chat; // История и фоновые задачи
+pub mod cli; // Режим командной строки
 pub mod command_log; // Логирование команд
 pub mod commands; // Обработка команд
+pub mod completion; // Автодополнение команд
 pub mod config; // Настройки пользователя
 pub mod constants; // Константы и строки
 pub mod desktop;
+pub mod exec; // Запуск внешних программ
+pub mod export; // Экспорт переписки
 pub mod guides; // Обучающие гайды
+pub mod i18n; // Локализация
 pub mod installer; // Установка в систему
+pub mod ipc; // Сокет для других программ
+pub mod macros; // Псевдонимы и макросы
+pub mod markup; // Markdown и блоки команд в сообщениях
+pub mod monitor; // Мониторинг /proc и /sys
+pub mod privileged; // Действия с правами root
+pub mod search; // Поиск по истории чатов
+pub mod sessions; // Сессии чата на диске
 pub mod ui; // Графический интерфейс // Определение DE и стили
 
 pub use assistant_app::AssistantApp;
@@ -0,0 +1,963 @@
+//! Мониторинг системы: чтение /proc и /sys без внешних утилит
+//!
+//! `SystemReader` читает файлы относительно корня (в тестах — каталог
+//! с копией нужных файлов), `Sampler` снимает замеры, а `Monitor` делает это
+//! в фоновом потоке и хранит историю для графиков.
+
+use crate::app::constants::{MONITOR_HISTORY_LEN, MONITOR_INTERVAL_MS};
+use crate::app::i18n::{tr, trf};
+use std::collections::{HashMap, VecDeque};
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Счётчики времени CPU из /proc/stat (в тиках)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CpuTimes {
+    pub busy: u64,
+    pub total: u64,
+}
+
+impl CpuTimes {
+    /// Загрузка в процентах между двумя замерами
+    pub fn usage_since(&self, prev: &CpuTimes) -> f32 {
+        let total = self.total.saturating_sub(prev.total);
+        if total == 0 {
+            return 0.0;
+        }
+        self.busy.saturating_sub(prev.busy) as f32 * 100.0 / total as f32
+    }
+}
+
+/// Память и swap (в байтах)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MemoryInfo {
+    pub total: u64,
+    pub available: u64,
+    pub swap_total: u64,
+    pub swap_free: u64,
+}
+
+impl MemoryInfo {
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.available)
+    }
+
+    pub fn swap_used(&self) -> u64 {
+        self.swap_total.saturating_sub(self.swap_free)
+    }
+}
+
+/// Смонтированная файловая система
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mount {
+    pub device: String,
+    pub path: String,
+    pub fs_type: String,
+}
+
+/// Заполненность раздела (в байтах)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiskUsage {
+    pub mount: Mount,
+    pub total: u64,
+    pub available: u64,
+}
+
+impl DiskUsage {
+    pub fn used(&self) -> u64 {
+        self.total.saturating_sub(self.available)
+    }
+
+    pub fn percent(&self) -> f32 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.used() as f32 * 100.0 / self.total as f32
+        }
+    }
+}
+
+/// Датчик температуры hwmon
+#[derive(Debug, Clone, PartialEq)]
+pub struct Temperature {
+    /// Имя устройства и метка датчика ("coretemp: Package id 0")
+    pub label: String,
+    pub celsius: f32,
+}
+
+/// Состояние батареи
+#[derive(Debug, Clone, PartialEq)]
+pub struct Battery {
+    pub name: String,
+    pub capacity: u8,
+    /// "Charging", "Discharging", "Full"...
+    pub status: String,
+}
+
+/// Процесс из /proc/<pid> (без расчёта загрузки CPU)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessStat {
+    pub pid: u32,
+    pub name: String,
+    pub uid: u32,
+    /// utime + stime в тиках
+    pub cpu_ticks: u64,
+    /// Резидентная память в байтах
+    pub rss: u64,
+    pub nice: i32,
+    /// Аргументы через пробел (пусто у потоков ядра)
+    pub cmdline: String,
+}
+
+/// Счётчики трафика из /proc/net/dev (в байтах, без loopback)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NetCounters {
+    pub rx: u64,
+    pub tx: u64,
+}
+
+// ============================================================================
+// Чтение /proc и /sys
+// ============================================================================
+
+/// Файловые системы, которые не показываются как диски
+const VIRTUAL_FS: &[&str] = &[
+    "proc",
+    "sysfs",
+    "devtmpfs",
+    "devpts",
+    "tmpfs",
+    "cgroup",
+    "cgroup2",
+    "securityfs",
+    "pstore",
+    "efivarfs",
+    "bpf",
+    "debugfs",
+    "tracefs",
+    "configfs",
+    "fusectl",
+    "mqueue",
+    "hugetlbfs",
+    "autofs",
+    "binfmt_misc",
+    "ramfs",
+    "overlay",
+    "squashfs",
+    "nsfs",
+    "fuse.portal",
+    "fuse.gvfsd-fuse",
+];
+
+/// Читает системные файлы относительно корня
+#[derive(Debug, Clone)]
+pub struct SystemReader {
+    root: PathBuf,
+}
+
+impl Default for SystemReader {
+    fn default() -> Self {
+        Self::new("/")
+    }
+}
+
+impl SystemReader {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    fn path(&self, rel: &str) -> PathBuf {
+        self.root.join(rel.trim_start_matches('/'))
+    }
+
+    fn read(&self, rel: &str) -> Option<String> {
+        std::fs::read_to_string(self.path(rel)).ok()
+    }
+
+    /// Подкаталоги с заданным префиксом имени (hwmon0, BAT0...), по порядку
+    fn entries(&self, rel: &str, prefix: &str) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = std::fs::read_dir(self.path(rel))
+            .map(|rd| {
+                rd.flatten()
+                    .filter(|e| e.file_name().to_string_lossy().starts_with(prefix))
+                    .map(|e| e.path())
+                    .collect()
+            })
+            .unwrap_or_default();
+        dirs.sort();
+        dirs
+    }
+
+    /// Общие счётчики CPU и по ядрам: `(всего, [ядра])`
+    pub fn cpu_times(&self) -> (CpuTimes, Vec<CpuTimes>) {
+        let mut total = CpuTimes::default();
+        let mut cores = Vec::new();
+
+        for line in self.read("/proc/stat").unwrap_or_default().lines() {
+            let mut parts = line.split_whitespace();
+            let Some(name) = parts.next().filter(|n| n.starts_with("cpu")) else {
+                continue;
+            };
+            let values: Vec<u64> = parts.filter_map(|v| v.parse().ok()).collect();
+            if values.len() < 4 {
+                continue;
+            }
+            // user nice system idle iowait irq softirq steal (guest уже входит в user)
+            let sum: u64 = values.iter().take(8).sum();
+            let idle = values[3] + values.get(4).copied().unwrap_or(0);
+            let times = CpuTimes {
+                busy: sum.saturating_sub(idle),
+                total: sum,
+            };
+
+            if name == "cpu" {
+                total = times;
+            } else {
+                cores.push(times);
+            }
+        }
+
+        (total, cores)
+    }
+
+    /// Модель процессора
+    pub fn cpu_model(&self) -> Option<String> {
+        self.read("/proc/cpuinfo")?
+            .lines()
+            .find(|l| l.starts_with("model name"))
+            .and_then(|l| l.split(':').nth(1))
+            .map(|s| s.trim().to_string())
+    }
+
+    /// Средняя загрузка за минуту
+    pub fn load_average(&self) -> Option<f32> {
+        self.read("/proc/loadavg")?
+            .split_whitespace()
+            .next()?
+            .parse()
+            .ok()
+    }
+
+    pub fn memory(&self) -> Option<MemoryInfo> {
+        let text = self.read("/proc/meminfo")?;
+        let get = |key: &str| {
+            text.lines()
+                .find(|l| l.starts_with(key) && l[key.len()..].starts_with(':'))
+                .and_then(|l| l.split_whitespace().nth(1))
+                .and_then(|v| v.parse::<u64>().ok())
+                .map(|kb| kb * 1024)
+        };
+
+        Some(MemoryInfo {
+            total: get("MemTotal")?,
+            available: get("MemAvailable").or_else(|| get("MemFree"))?,
+            swap_total: get("SwapTotal").unwrap_or(0),
+            swap_free: get("SwapFree").unwrap_or(0),
+        })
+    }
+
+    /// Реальные файловые системы из /proc/mounts (без дублей устройства)
+    pub fn mounts(&self) -> Vec<Mount> {
+        let mut mounts: Vec<Mount> = Vec::new();
+
+        for line in self.read("/proc/mounts").unwrap_or_default().lines() {
+            let mut parts = line.split_whitespace();
+            let (Some(device), Some(path), Some(fs_type)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                continue;
+            };
+            if VIRTUAL_FS.contains(&fs_type) || !device.starts_with('/') {
+                continue;
+            }
+            // btrfs-подтома одного устройства показываем один раз
+            if mounts.iter().any(|m| m.device == device) {
+                continue;
+            }
+            mounts.push(Mount {
+                device: device.to_string(),
+                // Пробелы в путях экранированы как \040
+                path: path.replace("\\040", " "),
+                fs_type: fs_type.to_string(),
+            });
+        }
+
+        mounts
+    }
+
+    /// Датчики температуры из /sys/class/hwmon
+    pub fn temperatures(&self) -> Vec<Temperature> {
+        let mut temps = Vec::new();
+
+        for dir in self.entries("/sys/class/hwmon", "hwmon") {
+            let device = read_trimmed(&dir.join("name")).unwrap_or_else(|| "?".into());
+
+            let mut inputs: Vec<PathBuf> = std::fs::read_dir(&dir)
+                .map(|rd| {
+                    rd.flatten()
+                        .map(|e| e.path())
+                        .filter(|p| {
+                            let name = p.file_name().unwrap_or_default().to_string_lossy();
+                            name.starts_with("temp") && name.ends_with("_input")
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            inputs.sort();
+
+            for input in inputs {
+                let Some(millis) = read_trimmed(&input).and_then(|v| v.parse::<i64>().ok()) else {
+                    continue;
+                };
+                let label_path = input.to_string_lossy().replace("_input", "_label");
+                let label = match read_trimmed(Path::new(&label_path)) {
+                    Some(label) => format!("{}: {}", device, label),
+                    None => device.clone(),
+                };
+                temps.push(Temperature {
+                    label,
+                    celsius: millis as f32 / 1000.0,
+                });
+            }
+        }
+
+        temps
+    }
+
+    /// Батареи из /sys/class/power_supply
+    pub fn batteries(&self) -> Vec<Battery> {
+        self.entries("/sys/class/power_supply", "BAT")
+            .into_iter()
+            .filter_map(|dir| {
+                Some(Battery {
+                    name: dir.file_name()?.to_string_lossy().into_owned(),
+                    capacity: read_trimmed(&dir.join("capacity"))?.parse().ok()?,
+                    status: read_trimmed(&dir.join("status")).unwrap_or_default(),
+                })
+            })
+            .collect()
+    }
+
+    /// Все процессы из /proc (исчезнувшие во время чтения пропускаются)
+    pub fn processes(&self) -> Vec<ProcessStat> {
+        let Ok(dir) = std::fs::read_dir(self.path("/proc")) else {
+            return Vec::new();
+        };
+
+        let mut processes: Vec<ProcessStat> = dir
+            .flatten()
+            .filter_map(|e| e.file_name().to_str()?.parse::<u32>().ok())
+            .filter_map(|pid| self.process(pid))
+            .collect();
+        processes.sort_by_key(|p| p.pid);
+        processes
+    }
+
+    /// Один процесс: /proc/<pid>/stat, status и cmdline
+    pub fn process(&self, pid: u32) -> Option<ProcessStat> {
+        let stat = self.read(&format!("/proc/{}/stat", pid))?;
+
+        // Имя в скобках может содержать пробелы и скобки — берём до последней ")"
+        let open = stat.find('(')?;
+        let close = stat.rfind(')')?;
+        let name = stat.get(open + 1..close)?.to_string();
+        let fields: Vec<&str> = stat.get(close + 1..)?.split_whitespace().collect();
+        // После имени: state(0) ... utime(11) stime(12) ... nice(16)
+        let ticks = |i: usize| fields.get(i).and_then(|v| v.parse::<u64>().ok());
+        let cpu_ticks = ticks(11)? + ticks(12)?;
+        let nice = fields.get(16)?.parse().ok()?;
+
+        let status = self
+            .read(&format!("/proc/{}/status", pid))
+            .unwrap_or_default();
+        let status_field = |key: &str| {
+            status
+                .lines()
+                .find(|l| l.starts_with(key))
+                .and_then(|l| l.split_whitespace().nth(1))
+                .and_then(|v| v.parse::<u64>().ok())
+        };
+
+        let cmdline = std::fs::read(self.path(&format!("/proc/{}/cmdline", pid)))
+            .map(|raw| {
+                raw.split(|b| *b == 0)
+                    .filter(|a| !a.is_empty())
+                    .map(|a| String::from_utf8_lossy(a).into_owned())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .unwrap_or_default();
+
+        Some(ProcessStat {
+            pid,
+            name,
+            uid: status_field("Uid:").unwrap_or(0) as u32,
+            cpu_ticks,
+            rss: status_field("VmRSS:").unwrap_or(0) * 1024,
+            nice,
+            cmdline,
+        })
+    }
+
+    /// Имена пользователей из /etc/passwd: uid → имя
+    pub fn users(&self) -> HashMap<u32, String> {
+        self.read("/etc/passwd")
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split(':');
+                let name = parts.next()?;
+                let uid = parts.nth(1)?.parse().ok()?;
+                Some((uid, name.to_string()))
+            })
+            .collect()
+    }
+
+    /// Суммарный трафик по всем интерфейсам, кроме loopback
+    pub fn net_counters(&self) -> NetCounters {
+        let mut counters = NetCounters::default();
+
+        // Первые две строки — заголовок
+        for line in self
+            .read("/proc/net/dev")
+            .unwrap_or_default()
+            .lines()
+            .skip(2)
+        {
+            let Some((iface, data)) = line.split_once(':') else {
+                continue;
+            };
+            if iface.trim() == "lo" {
+                continue;
+            }
+            let values: Vec<u64> = data
+                .split_whitespace()
+                .filter_map(|v| v.parse().ok())
+                .collect();
+            if values.len() >= 9 {
+                counters.rx += values[0];
+                counters.tx += values[8];
+            }
+        }
+
+        counters
+    }
+
+    /// Размер и свободное место раздела через statvfs (путь — от корня).
+    /// Может зависнуть на недоступном сетевом разделе
+    pub fn disk_usage(&self, mount: &Mount) -> Option<DiskUsage> {
+        let path = CString::new(self.path(&mount.path).as_os_str().as_bytes()).ok()?;
+        let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+        // SAFETY: path — корректная C-строка, stat — выделенная структура нужного типа
+        if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+            return None;
+        }
+
+        let block = stat.f_frsize as u64;
+        Some(DiskUsage {
+            mount: mount.clone(),
+            total: stat.f_blocks as u64 * block,
+            available: stat.f_bavail as u64 * block,
+        })
+    }
+
+    /// Заполненность всех реальных разделов
+    pub fn disks(&self) -> Vec<DiskUsage> {
+        self.mounts()
+            .iter()
+            .filter_map(|m| self.disk_usage(m))
+            .collect()
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+// ============================================================================
+// Сбор замеров и истории
+// ============================================================================
+
+/// Последний замер
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    pub cpu: f32,
+    pub cores: Vec<f32>,
+    pub memory: Option<MemoryInfo>,
+    pub disks: Vec<DiskUsage>,
+    pub temperatures: Vec<Temperature>,
+    pub batteries: Vec<Battery>,
+    /// Скорость сети, байт/с
+    pub rx_rate: f64,
+    pub tx_rate: f64,
+}
+
+/// Замеры показателей; загрузка CPU и сеть считаются от прошлого замера
+pub struct Sampler {
+    reader: SystemReader,
+    prev_cpu: Option<(CpuTimes, Vec<CpuTimes>)>,
+    prev_net: Option<(Instant, NetCounters)>,
+    snapshot: Snapshot,
+}
+
+impl Default for Sampler {
+    fn default() -> Self {
+        Self::new(SystemReader::default())
+    }
+}
+
+impl Sampler {
+    pub fn new(reader: SystemReader) -> Self {
+        Self {
+            reader,
+            prev_cpu: None,
+            prev_net: None,
+            snapshot: Snapshot::default(),
+        }
+    }
+
+    /// Снимает все показатели
+    pub fn sample(&mut self) -> Snapshot {
+        let now = Instant::now();
+
+        let (total, cores) = self.reader.cpu_times();
+        if let Some((prev_total, prev_cores)) = &self.prev_cpu {
+            self.snapshot.cpu = total.usage_since(prev_total);
+            self.snapshot.cores = cores
+                .iter()
+                .zip(prev_cores)
+                .map(|(c, p)| c.usage_since(p))
+                .collect();
+        }
+        self.prev_cpu = Some((total, cores));
+
+        let net = self.reader.net_counters();
+        if let Some((at, prev)) = self.prev_net {
+            let secs = now.duration_since(at).as_secs_f64().max(0.001);
+            self.snapshot.rx_rate = net.rx.saturating_sub(prev.rx) as f64 / secs;
+            self.snapshot.tx_rate = net.tx.saturating_sub(prev.tx) as f64 / secs;
+        }
+        self.prev_net = Some((now, net));
+
+        self.snapshot.memory = self.reader.memory();
+        self.snapshot.disks = self.reader.disks();
+        self.snapshot.temperatures = self.reader.temperatures();
+        self.snapshot.batteries = self.reader.batteries();
+        self.snapshot.clone()
+    }
+}
+
+/// Последний замер и история для графиков
+#[derive(Debug, Default)]
+pub struct MonitorData {
+    pub snapshot: Snapshot,
+    pub cpu_history: VecDeque<f32>,
+    pub memory_history: VecDeque<f32>,
+    pub rx_history: VecDeque<f32>,
+    pub tx_history: VecDeque<f32>,
+}
+
+impl MonitorData {
+    fn push(&mut self, snapshot: Snapshot) {
+        let memory = snapshot
+            .memory
+            .map(|m| m.used() as f32 * 100.0 / m.total.max(1) as f32)
+            .unwrap_or(0.0);
+        push_history(&mut self.cpu_history, snapshot.cpu);
+        push_history(&mut self.memory_history, memory);
+        push_history(&mut self.rx_history, snapshot.rx_rate as f32);
+        push_history(&mut self.tx_history, snapshot.tx_rate as f32);
+        self.snapshot = snapshot;
+    }
+}
+
+/// Периодические замеры в фоновом потоке, пока мониторинг включён.
+/// Зависший раздел останавливает только этот поток, а не окно
+pub struct Monitor {
+    data: Arc<Mutex<MonitorData>>,
+    active: Arc<AtomicBool>,
+}
+
+impl Default for Monitor {
+    fn default() -> Self {
+        Self::start(SystemReader::default())
+    }
+}
+
+impl Monitor {
+    pub fn start(reader: SystemReader) -> Self {
+        let data = Arc::new(Mutex::new(MonitorData::default()));
+        let active = Arc::new(AtomicBool::new(false));
+
+        // Поток завершается вместе с монитором
+        let weak = Arc::downgrade(&data);
+        let flag = active.clone();
+        std::thread::spawn(move || {
+            let mut sampler = Sampler::new(reader);
+            loop {
+                if flag.load(Ordering::Relaxed) {
+                    let snapshot = sampler.sample();
+                    let Some(shared) = weak.upgrade() else { break };
+                    if let Ok(mut data) = shared.lock() {
+                        data.push(snapshot);
+                    };
+                } else if weak.strong_count() == 0 {
+                    break;
+                }
+                std::thread::sleep(Duration::from_millis(MONITOR_INTERVAL_MS));
+            }
+        });
+
+        Self { data, active }
+    }
+
+    /// Включает или приостанавливает замеры
+    pub fn set_active(&self, active: bool) {
+        self.active.store(active, Ordering::Relaxed);
+    }
+
+    /// Текущие данные для отрисовки
+    pub fn data(&self) -> Option<MutexGuard<'_, MonitorData>> {
+        self.data.lock().ok()
+    }
+}
+
+fn push_history(history: &mut VecDeque<f32>, value: f32) {
+    if history.len() >= MONITOR_HISTORY_LEN {
+        history.pop_front();
+    }
+    history.push_back(value);
+}
+
+// ============================================================================
+// Форматирование (для UI и инструментов ИИ)
+// ============================================================================
+
+/// Размер в человекочитаемом виде
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["Б", "КБ", "МБ", "ГБ", "ТБ"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, tr(UNITS[0]))
+    } else {
+        format!("{:.1} {}", value, tr(UNITS[unit]))
+    }
+}
+
+/// Скорость в человекочитаемом виде
+pub fn format_rate(bytes_per_sec: f64) -> String {
+    trf("{}/с", &[&format_bytes(bytes_per_sec as u64)])
+}
+
+/// Замер с паузой — для разовых запросов, где нет истории
+fn quick_snapshot() -> Snapshot {
+    let mut sampler = Sampler::default();
+    sampler.sample();
+    std::thread::sleep(Duration::from_millis(250));
+    sampler.sample()
+}
+
+pub fn memory_summary() -> String {
+    match SystemReader::default().memory() {
+        Some(m) => {
+            let mut text = trf(
+                "{} / {} (использовано)",
+                &[&format_bytes(m.used()), &format_bytes(m.total)],
+            );
+            if m.swap_total > 0 {
+                text.push_str(&format!(
+                    ", swap {} / {}",
+                    format_bytes(m.swap_used()),
+                    format_bytes(m.swap_total)
+                ));
+            }
+            text
+        }
+        None => tr("Не удалось получить").into(),
+    }
+}
+
+pub fn disk_summary() -> String {
+    let disks: Vec<String> = SystemReader::default()
+        .disks()
+        .iter()
+        .map(|d| {
+            format!(
+                "{}: {} / {} ({:.0}%)",
+                d.mount.path,
+                format_bytes(d.used()),
+                format_bytes(d.total),
+                d.percent()
+            )
+        })
+        .collect();
+
+    if disks.is_empty() {
+        tr("Не удалось получить").into()
+    } else {
+        disks.join("; ")
+    }
+}
+
+pub fn cpu_summary() -> String {
+    let reader = SystemReader::default();
+    let name = reader
+        .cpu_model()
+        .unwrap_or_else(|| tr("Неизвестно").into());
+    let snapshot = quick_snapshot();
+    let load = reader
+        .load_average()
+        .map(|l| format!("{:.2}", l))
+        .unwrap_or_else(|| "?".into());
+
+    trf(
+        "{} — {}% ({} ядер, load average: {})",
+        &[
+            &name,
+            &format!("{:.0}", snapshot.cpu),
+            &snapshot.cores.len(),
+            &load,
+        ],
+    )
+}
+
+pub fn temperature_summary() -> String {
+    let temps = SystemReader::default().temperatures();
+    if temps.is_empty() {
+        return tr("датчики температуры не найдены").into();
+    }
+    temps
+        .iter()
+        .map(|t| format!("{}: {:.0}°C", t.label, t.celsius))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn battery_summary() -> String {
+    let batteries = SystemReader::default().batteries();
+    if batteries.is_empty() {
+        return tr("батарея не найдена").into();
+    }
+    batteries
+        .iter()
+        .map(|b| format!("{}: {}% ({})", b.name, b.capacity, b.status))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn network_rate_summary() -> String {
+    let snapshot = quick_snapshot();
+    trf(
+        "приём {}, передача {}",
+        &[
+            &format_rate(snapshot.rx_rate),
+            &format_rate(snapshot.tx_rate),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// Временный корень с копией нужных файлов /proc и /sys
+    struct Fixture {
+        root: PathBuf,
+    }
+
+    impl Fixture {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!(
+                "alfons-monitor-{}-{}",
+                name,
+                std::process::id()
+            ));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(&root).unwrap();
+            Self { root }
+        }
+
+        fn write(&self, rel: &str, content: &str) -> &Self {
+            let path = self.root.join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+            self
+        }
+
+        fn reader(&self) -> SystemReader {
+            SystemReader::new(&self.root)
+        }
+    }
+
+    impl Drop for Fixture {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+
+    #[test]
+    fn reads_cpu_times() {
+        let fixture = Fixture::new("cpu");
+        fixture.write(
+            "proc/stat",
+            "cpu  100 0 50 800 50 0 0 0 0 0\n\
+             cpu0 60 0 30 400 10 0 0 0 0 0\n\
+             cpu1 40 0 20 400 40 0 0 0 0 0\n\
+             intr 12345\n",
+        );
+
+        let (total, cores) = fixture.reader().cpu_times();
+        assert_eq!(
+            total,
+            CpuTimes {
+                busy: 150,
+                total: 1000
+            }
+        );
+        assert_eq!(cores.len(), 2);
+        assert_eq!(
+            cores[1],
+            CpuTimes {
+                busy: 60,
+                total: 500
+            }
+        );
+
+        let later = CpuTimes {
+            busy: 200,
+            total: 1100,
+        };
+        assert_eq!(later.usage_since(&total), 50.0);
+    }
+
+    #[test]
+    fn reads_memory() {
+        let fixture = Fixture::new("mem");
+        fixture.write(
+            "proc/meminfo",
+            "MemTotal:       16000000 kB\n\
+             MemFree:         1000000 kB\n\
+             MemAvailable:    8000000 kB\n\
+             SwapTotal:       2000000 kB\n\
+             SwapFree:        1500000 kB\n",
+        );
+
+        let memory = fixture.reader().memory().unwrap();
+        assert_eq!(memory.total, 16_000_000 * 1024);
+        assert_eq!(memory.used(), 8_000_000 * 1024);
+        assert_eq!(memory.swap_used(), 500_000 * 1024);
+    }
+
+    #[test]
+    fn reads_real_mounts_and_their_usage() {
+        let fixture = Fixture::new("mounts");
+        fixture.write(
+            "proc/mounts",
+            "proc /proc proc rw 0 0\n\
+             /dev/sda2 / btrfs rw,subvol=/@ 0 0\n\
+             /dev/sda2 /home btrfs rw,subvol=/@home 0 0\n\
+             tmpfs /tmp tmpfs rw 0 0\n\
+             /dev/sda1 /boot\\040efi vfat rw 0 0\n",
+        );
+
+        let reader = fixture.reader();
+        let mounts = reader.mounts();
+        let paths: Vec<&str> = mounts.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, ["/", "/boot efi"]);
+        assert_eq!(mounts[0].fs_type, "btrfs");
+
+        // statvfs выполняется внутри корня фикстуры: "/" есть, "/boot efi" нет
+        let disks = reader.disks();
+        assert_eq!(disks.len(), 1);
+        assert_eq!(disks[0].mount.path, "/");
+        assert!(disks[0].total > 0);
+    }
+
+    #[test]
+    fn reads_network_counters_without_loopback() {
+        let fixture = Fixture::new("net");
+        fixture.write(
+            "proc/net/dev",
+            "Inter-|   Receive                            |  Transmit\n \
+             face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets\n    \
+             lo:  5000 10 0 0 0 0 0 0  5000 10 0 0 0 0 0 0\n  \
+             eth0:  1000 10 0 0 0 0 0 0  200 5 0 0 0 0 0 0\n \
+             wlan0:   300 3 0 0 0 0 0 0   40 1 0 0 0 0 0 0\n",
+        );
+
+        let counters = fixture.reader().net_counters();
+        assert_eq!(counters, NetCounters { rx: 1300, tx: 240 });
+    }
+
+    #[test]
+    fn reads_temperatures() {
+        let fixture = Fixture::new("hwmon");
+        fixture
+            .write("sys/class/hwmon/hwmon0/name", "coretemp\n")
+            .write("sys/class/hwmon/hwmon0/temp1_input", "45000\n")
+            .write("sys/class/hwmon/hwmon0/temp1_label", "Package id 0\n")
+            .write("sys/class/hwmon/hwmon1/name", "nvme\n")
+            .write("sys/class/hwmon/hwmon1/temp1_input", "38500\n");
+
+        let temps = fixture.reader().temperatures();
+        assert_eq!(
+            temps,
+            [
+                Temperature {
+                    label: "coretemp: Package id 0".into(),
+                    celsius: 45.0,
+                },
+                Temperature {
+                    label: "nvme".into(),
+                    celsius: 38.5,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_batteries() {
+        let fixture = Fixture::new("power");
+        fixture
+            .write("sys/class/power_supply/AC/online", "1\n")
+            .write("sys/class/power_supply/BAT0/capacity", "87\n")
+            .write("sys/class/power_supply/BAT0/status", "Charging\n");
+
+        let batteries = fixture.reader().batteries();
+        assert_eq!(
+            batteries,
+            [Battery {
+                name: "BAT0".into(),
+                capacity: 87,
+                status: "Charging".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_files_give_empty_results() {
+        let fixture = Fixture::new("empty");
+        let reader = fixture.reader();
+        assert!(reader.memory().is_none());
+        assert!(reader.mounts().is_empty());
+        assert!(reader.temperatures().is_empty());
+        assert!(reader.batteries().is_empty());
+        assert_eq!(reader.net_counters(), NetCounters::default());
+    }
+}
@@ -0,0 +1,69 @@
+//! Выполнение действий с правами root (через pkexec)
+
+use super::i18n::{tr, trf};
+use chrono::Local;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Запускает программу через pkexec и возвращает stdout
+pub fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("pkexec")
+        .arg(program)
+        .args(args)
+        .output()
+        .map_err(|e| trf("Ошибка pkexec: {}", &[&e]))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(if stderr.trim().is_empty() {
+            tr("Действие отменено или завершилось с ошибкой").into()
+        } else {
+            stderr.trim().into()
+        })
+    }
+}
+
+/// Записывает системный файл, предварительно сохранив резервную копию.
+/// Возвращает путь к резервной копии.
+pub fn write_file_with_backup(path: &str, content: &str) -> Result<String, String> {
+    let backup = format!("{}.{}.bak", path, Local::now().format("%Y%m%d-%H%M%S"));
+
+    // Один вызов pkexec — пароль запрашивается один раз
+    let mut child = Command::new("pkexec")
+        .args([
+            "sh",
+            "-c",
+            r#"cp -a -- "$1" "$2" && cat > "$1""#,
+            "sh",
+            path,
+            &backup,
+        ])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| trf("Ошибка pkexec: {}", &[&e]))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(content.as_bytes())
+            .map_err(|e| trf("Ошибка записи: {}", &[&e]))?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| trf("Ошибка pkexec: {}", &[&e]))?;
+
+    if output.status.success() {
+        Ok(backup)
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(if stderr.trim().is_empty() {
+            tr("Запись отменена").into()
+        } else {
+            stderr.trim().into()
+        })
+    }
+}
@@ -0,0 +1,365 @@
+//! Полнотекстовый поиск по сохранённым сессиям
+//!
+//! Индекс слов строится в фоновом потоке при первом поиске и дополняется
+//! новыми сообщениями; после перезаписи или удаления сессии он строится заново.
+
+use super::chat::ChatMessage;
+use super::constants::{SEARCH_MAX_RESULTS, SEARCH_SNIPPET_CONTEXT, USER_SENDER};
+use super::sessions;
+use chrono::{DateTime, Duration, Local, TimeZone};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::{Bound, Range};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+
+// ============================================================================
+// Фильтры
+// ============================================================================
+
+/// Фильтр по отправителю
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SenderFilter {
+    #[default]
+    All,
+    User,
+    Assistant,
+    System,
+}
+
+impl SenderFilter {
+    pub const ALL: &'static [SenderFilter] = &[
+        SenderFilter::All,
+        SenderFilter::User,
+        SenderFilter::Assistant,
+        SenderFilter::System,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SenderFilter::All => "Все",
+            SenderFilter::User => USER_SENDER,
+            SenderFilter::Assistant => "Ассистент",
+            SenderFilter::System => "Система",
+        }
+    }
+
+    fn matches(&self, sender: &str) -> bool {
+        match self {
+            SenderFilter::All => true,
+            SenderFilter::User => sender == USER_SENDER,
+            SenderFilter::System => sender == "Система",
+            SenderFilter::Assistant => sender != USER_SENDER && sender != "Система",
+        }
+    }
+}
+
+/// Фильтр по дате сообщения
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Period {
+    #[default]
+    All,
+    Today,
+    Week,
+    Month,
+}
+
+impl Period {
+    pub const ALL: &'static [Period] = &[Period::All, Period::Today, Period::Week, Period::Month];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Period::All => "За всё время",
+            Period::Today => "Сегодня",
+            Period::Week => "За неделю",
+            Period::Month => "За месяц",
+        }
+    }
+
+    /// Начало периода (unix, секунды)
+    fn since(&self) -> Option<i64> {
+        let now = Local::now();
+        match self {
+            Period::All => None,
+            Period::Today => now
+                .date_naive()
+                .and_hms_opt(0, 0, 0)
+                .and_then(|t| Local.from_local_datetime(&t).earliest())
+                .map(|t| t.timestamp()),
+            Period::Week => Some((now - Duration::days(7)).timestamp()),
+            Period::Month => Some((now - Duration::days(30)).timestamp()),
+        }
+    }
+}
+
+// ============================================================================
+// Индекс
+// ============================================================================
+
+/// Найденное сообщение
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub session: String,
+    pub session_title: String,
+    pub sender: String,
+    pub text: String,
+    pub timestamp: DateTime<Local>,
+}
+
+struct Document {
+    session: String,
+    message: ChatMessage,
+}
+
+/// Обратный индекс: слово → номера сообщений.
+/// Слова упорядочены, поэтому все слова с префиксом идут подряд
+#[derive(Default)]
+struct SearchIndex {
+    docs: Vec<Document>,
+    terms: BTreeMap<String, Vec<usize>>,
+}
+
+impl SearchIndex {
+    /// Читает все сессии с диска
+    fn build() -> Self {
+        let mut index = Self::default();
+        for info in sessions::list() {
+            for msg in sessions::messages(&info.id) {
+                index.add(&info.id, msg);
+            }
+        }
+        index
+    }
+
+    fn add(&mut self, session: &str, message: ChatMessage) {
+        let doc = self.docs.len();
+        let words: HashSet<String> = tokenize(&message.text).collect();
+        for word in words {
+            self.terms.entry(word).or_default().push(doc);
+        }
+        self.docs.push(Document {
+            session: session.to_string(),
+            message,
+        });
+    }
+
+    /// Сообщения, где каждое слово запроса — начало какого-то слова текста
+    fn lookup(&self, words: &[String]) -> HashSet<usize> {
+        let mut result: Option<HashSet<usize>> = None;
+        for word in words {
+            let docs: HashSet<usize> = self
+                .terms
+                .range::<str, _>((Bound::Included(word.as_str()), Bound::Unbounded))
+                .take_while(|(term, _)| term.starts_with(word.as_str()))
+                .flat_map(|(_, docs)| docs.iter().copied())
+                .collect();
+            result = Some(match result {
+                Some(prev) => prev.intersection(&docs).copied().collect(),
+                None => docs,
+            });
+        }
+        result.unwrap_or_default()
+    }
+}
+
+/// Индекс в памяти; None — ещё не построен или устарел
+static INDEX: Mutex<Option<SearchIndex>> = Mutex::new(None);
+/// Меняется при каждом изменении сессий, пока индекса нет:
+/// построенный к этому моменту индекс уже устарел
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+/// Индекс строится в фоновом потоке
+static BUILDING: AtomicBool = AtomicBool::new(false);
+
+/// Готов ли индекс; если нет — запускает его построение в фоне
+fn ensure_index() -> bool {
+    if INDEX.lock().unwrap_or_else(|e| e.into_inner()).is_some() {
+        return true;
+    }
+    if !BUILDING.swap(true, Ordering::SeqCst) {
+        std::thread::spawn(|| {
+            loop {
+                let generation = GENERATION.load(Ordering::SeqCst);
+                let built = SearchIndex::build();
+                let mut index = INDEX.lock().unwrap_or_else(|e| e.into_inner());
+                // Сессии изменились во время чтения — строим заново
+                if GENERATION.load(Ordering::SeqCst) == generation {
+                    *index = Some(built);
+                    break;
+                }
+            }
+            BUILDING.store(false, Ordering::SeqCst);
+        });
+    }
+    false
+}
+
+/// Слова текста в нижнем регистре (буквы и цифры, от двух символов)
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| w.chars().count() >= 2)
+        .map(str::to_lowercase)
+}
+
+/// Добавляет новое сообщение в уже построенный индекс
+pub fn add_message(session: &str, msg: &ChatMessage) {
+    let mut index = INDEX.lock().unwrap_or_else(|e| e.into_inner());
+    match index.as_mut() {
+        Some(index) => index.add(session, msg.clone()),
+        None => {
+            GENERATION.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Сбрасывает индекс (пересоберётся при следующем поиске)
+pub fn invalidate() {
+    let mut index = INDEX.lock().unwrap_or_else(|e| e.into_inner());
+    *index = None;
+    GENERATION.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Поиск по всем сессиям, новые сообщения сверху.
+/// None — индекс ещё строится, поиск нужно повторить позже
+pub fn search(query: &str, sender: SenderFilter, period: Period) -> Option<Vec<SearchHit>> {
+    let words: Vec<String> = tokenize(query).collect();
+    if words.is_empty() {
+        return Some(Vec::new());
+    }
+    if !ensure_index() {
+        return None;
+    }
+    let since = period.since();
+    let titles: HashMap<String, String> = sessions::list()
+        .into_iter()
+        .map(|s| (s.id.clone(), s.display_title().to_string()))
+        .collect();
+
+    let guard = INDEX.lock().unwrap_or_else(|e| e.into_inner());
+    // Индекс могли сбросить сразу после проверки
+    let index = guard.as_ref()?;
+
+    let mut hits: Vec<usize> = index
+        .lookup(&words)
+        .into_iter()
+        .filter(|i| sender.matches(&index.docs[*i].message.sender))
+        .filter(|i| since.is_none_or(|s| index.docs[*i].message.timestamp.timestamp() >= s))
+        .collect();
+    // На диске время хранится с точностью до секунды — при равенстве решает порядок
+    hits.sort_by_key(|i| std::cmp::Reverse((index.docs[*i].message.timestamp.timestamp(), *i)));
+
+    let hits = hits
+        .into_iter()
+        .take(SEARCH_MAX_RESULTS)
+        .map(|i| &index.docs[i])
+        .map(|d| SearchHit {
+            session: d.session.clone(),
+            session_title: titles.get(&d.session).cloned().unwrap_or_default(),
+            sender: d.message.sender.clone(),
+            text: d.message.text.clone(),
+            timestamp: d.message.timestamp,
+        })
+        .collect();
+    Some(hits)
+}
+
+// ============================================================================
+// Подсветка
+// ============================================================================
+
+/// Байтовые диапазоны слов текста, начинающихся со слов запроса
+pub fn highlight_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    let words: Vec<String> = tokenize(query).collect();
+    let mut ranges = Vec::new();
+    let mut start = None;
+
+    // Проходим по словам текста, сохраняя их байтовые границы
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                let word = text[s..i].to_lowercase();
+                if let Some(w) = words.iter().find(|w| word.starts_with(w.as_str())) {
+                    // Подсвечиваем только совпавшее начало слова
+                    let len = text[s..i]
+                        .char_indices()
+                        .nth(w.chars().count())
+                        .map_or(i - s, |(n, _)| n);
+                    ranges.push(s..s + len);
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    ranges
+}
+
+/// Фрагмент текста вокруг первого совпадения (в одну строку)
+pub fn snippet(text: &str, query: &str) -> String {
+    let text = text.replace('\n', " ");
+    let Some(first) = highlight_ranges(&text, query).first().cloned() else {
+        return text.chars().take(SEARCH_SNIPPET_CONTEXT * 2).collect();
+    };
+
+    let before: Vec<char> = text[..first.start].chars().collect();
+    let from = before.len().saturating_sub(SEARCH_SNIPPET_CONTEXT);
+    let prefix: String = before[from..].iter().collect();
+    let rest: String = text[first.start..]
+        .chars()
+        .take(SEARCH_SNIPPET_CONTEXT * 2)
+        .collect();
+
+    format!(
+        "{}{}{}{}",
+        if from > 0 { "…" } else { "" },
+        prefix,
+        rest,
+        if text[first.start..].chars().count() > SEARCH_SNIPPET_CONTEXT * 2 {
+            "…"
+        } else {
+            ""
+        }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(texts: &[&str]) -> SearchIndex {
+        let mut index = SearchIndex::default();
+        for text in texts {
+            index.add("s", ChatMessage::new(USER_SENDER, *text));
+        }
+        index
+    }
+
+    fn words(query: &str) -> Vec<String> {
+        tokenize(query).collect()
+    }
+
+    #[test]
+    fn lookup_matches_word_prefixes() {
+        let index = index(&["Установи firefox", "Обнови систему", "Установка драйверов"]);
+        let mut found: Vec<usize> = index.lookup(&words("устан")).into_iter().collect();
+        found.sort();
+        assert_eq!(found, vec![0, 2]);
+        assert!(index.lookup(&words("станови")).is_empty());
+    }
+
+    #[test]
+    fn lookup_intersects_words() {
+        let index = index(&["Установи firefox", "Установи chromium"]);
+        let found: Vec<usize> = index.lookup(&words("fire уст")).into_iter().collect();
+        assert_eq!(found, vec![0]);
+    }
+
+    #[test]
+    fn highlight_marks_prefix_only() {
+        let text = "Установи Firefox";
+        let ranges = highlight_ranges(text, "fire");
+        assert_eq!(&text[ranges[0].clone()], "Fire");
+    }
+}
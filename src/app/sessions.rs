@@ -0,0 +1,387 @@
+//! Сессии чата на диске
+//!
+//! Каждая сессия — файл `sessions/<id>.jsonl` (одно сообщение на строку),
+//! названия и порядок хранятся в `sessions/index.json`.
+
+use super::chat::{ChatMessage, Variant};
+use super::constants::{CONFIG_APP_NAME, SESSION_TITLE_MAX, USER_SENDER};
+use super::search;
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+/// Название сессии без сообщений пользователя
+pub const UNTITLED: &str = "Новый чат";
+
+// ============================================================================
+// Модель данных
+// ============================================================================
+
+/// Описание сессии в индексе
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub id: String,
+    /// Пусто — название ещё не сгенерировано
+    #[serde(default)]
+    pub title: String,
+    /// Последнее сообщение (unix, секунды)
+    pub updated: i64,
+}
+
+impl SessionInfo {
+    /// Название для списка
+    pub fn display_title(&self) -> &str {
+        if self.title.is_empty() {
+            UNTITLED
+        } else {
+            &self.title
+        }
+    }
+
+    /// Дата последнего сообщения: время сегодня, иначе число
+    pub fn updated_label(&self) -> String {
+        let Some(time) = Local.timestamp_opt(self.updated, 0).single() else {
+            return String::new();
+        };
+        if time.date_naive() == Local::now().date_naive() {
+            time.format("%H:%M").to_string()
+        } else {
+            time.format("%d.%m.%Y").to_string()
+        }
+    }
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct Index {
+    /// Сессия, открытая при выходе
+    #[serde(default)]
+    active: Option<String>,
+    #[serde(default)]
+    sessions: Vec<SessionInfo>,
+}
+
+/// Строка JSONL-файла сессии
+#[derive(Serialize, Deserialize)]
+struct Record {
+    sender: String,
+    text: String,
+    /// unix, секунды
+    ts: i64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    generated: bool,
+    /// Варианты сообщения с их продолжениями
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    variants: Vec<VariantRecord>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    variant: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VariantRecord {
+    text: String,
+    ts: i64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    following: Vec<Record>,
+}
+
+fn is_zero(n: &usize) -> bool {
+    *n == 0
+}
+
+fn from_unix(ts: i64) -> DateTime<Local> {
+    Local
+        .timestamp_opt(ts, 0)
+        .single()
+        .unwrap_or_else(Local::now)
+}
+
+impl Record {
+    fn from_message(msg: &ChatMessage) -> Self {
+        Self {
+            sender: msg.sender.clone(),
+            text: msg.text.clone(),
+            ts: msg.timestamp.timestamp(),
+            generated: msg.generated,
+            variants: msg
+                .variants
+                .iter()
+                .map(|v| VariantRecord {
+                    text: v.text.clone(),
+                    ts: v.timestamp.timestamp(),
+                    following: v.following.iter().map(Record::from_message).collect(),
+                })
+                .collect(),
+            variant: msg.variant,
+        }
+    }
+
+    fn into_message(self) -> ChatMessage {
+        let variants: Vec<Variant> = self
+            .variants
+            .into_iter()
+            .map(|v| Variant {
+                text: v.text,
+                timestamp: from_unix(v.ts),
+                following: v.following.into_iter().map(Record::into_message).collect(),
+            })
+            .collect();
+        ChatMessage {
+            generated: self.generated,
+            variant: self.variant.min(variants.len().saturating_sub(1)),
+            variants,
+            timestamp: from_unix(self.ts),
+            ..ChatMessage::new(self.sender, self.text)
+        }
+    }
+}
+
+// ============================================================================
+// Индекс
+// ============================================================================
+
+fn sessions_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|p| p.join(CONFIG_APP_NAME).join("sessions"))
+}
+
+fn session_path(id: &str) -> Option<PathBuf> {
+    sessions_dir().map(|d| d.join(format!("{}.jsonl", id)))
+}
+
+/// Индекс, загружается с диска при первом обращении
+fn index() -> &'static Mutex<Index> {
+    static INDEX: OnceLock<Mutex<Index>> = OnceLock::new();
+    INDEX.get_or_init(|| Mutex::new(load_index()))
+}
+
+fn load_index() -> Index {
+    sessions_dir()
+        .and_then(|d| std::fs::read_to_string(d.join("index.json")).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(index: &Index) {
+    let Some(dir) = sessions_dir() else {
+        return;
+    };
+    let _ = std::fs::create_dir_all(&dir);
+    if let Ok(json) = serde_json::to_string_pretty(index) {
+        let _ = std::fs::write(dir.join("index.json"), json);
+    }
+}
+
+/// Изменяет индекс и сразу сохраняет его
+fn update_index<T>(f: impl FnOnce(&mut Index) -> T) -> T {
+    let mut index = index().lock().unwrap_or_else(|e| e.into_inner());
+    let result = f(&mut index);
+    save_index(&index);
+    result
+}
+
+/// Изменяет индекс и сохраняет его, только если `f` что-то поменяла
+fn update_index_if(f: impl FnOnce(&mut Index) -> bool) {
+    let mut index = index().lock().unwrap_or_else(|e| e.into_inner());
+    if f(&mut index) {
+        save_index(&index);
+    }
+}
+
+/// Сессии, последние сверху
+pub fn list() -> Vec<SessionInfo> {
+    let index = index().lock().unwrap_or_else(|e| e.into_inner());
+    let mut sessions = index.sessions.clone();
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.updated));
+    sessions
+}
+
+/// Название сессии для показа
+pub fn title(id: &str) -> String {
+    let index = index().lock().unwrap_or_else(|e| e.into_inner());
+    index
+        .sessions
+        .iter()
+        .find(|s| s.id == id)
+        .map_or(UNTITLED, |s| s.display_title())
+        .to_string()
+}
+
+/// Сессия, открытая в прошлый раз (если она ещё существует)
+pub fn last_active() -> Option<String> {
+    let index = index().lock().unwrap_or_else(|e| e.into_inner());
+    index
+        .active
+        .clone()
+        .filter(|id| index.sessions.iter().any(|s| &s.id == id))
+}
+
+/// Название сессии из первого сообщения пользователя
+fn make_title(text: &str) -> String {
+    let line = text.lines().next().unwrap_or("").trim();
+    if line.chars().count() > SESSION_TITLE_MAX {
+        let cut: String = line.chars().take(SESSION_TITLE_MAX).collect();
+        format!("{}…", cut.trim_end())
+    } else {
+        line.to_string()
+    }
+}
+
+// ============================================================================
+// Операции с сессиями
+// ============================================================================
+
+/// Создаёт пустую сессию и делает её активной
+pub fn create() -> String {
+    let now = Local::now();
+    let mut id = now.format("%Y%m%d-%H%M%S").to_string();
+    update_index(|index| {
+        // Две сессии за одну секунду — добавляем суффикс
+        let base = id.clone();
+        let mut n = 1;
+        while index.sessions.iter().any(|s| s.id == id) {
+            n += 1;
+            id = format!("{}-{}", base, n);
+        }
+        index.sessions.push(SessionInfo {
+            id: id.clone(),
+            title: String::new(),
+            updated: now.timestamp(),
+        });
+        index.active = Some(id.clone());
+    });
+    id
+}
+
+/// Делает сессию активной (откроется при следующем запуске)
+pub fn set_active(id: &str) {
+    update_index(|index| index.active = Some(id.to_string()));
+}
+
+/// Последние `limit` сообщений сессии и число более ранних.
+/// Ранние сообщения только не показываются — файл не меняется
+pub fn load(id: &str, limit: usize) -> (usize, Vec<ChatMessage>) {
+    let mut messages = messages(id);
+    let hidden = messages.len().saturating_sub(limit);
+    messages.drain(..hidden);
+    (hidden, messages)
+}
+
+/// Все сообщения из файла сессии, без обрезки
+pub fn messages(id: &str) -> Vec<ChatMessage> {
+    records(id)
+        .into_iter()
+        .filter_map(|line| serde_json::from_str::<Record>(&line).ok())
+        .map(Record::into_message)
+        .collect()
+}
+
+/// Строки файла сессии, которые разбираются как сообщения
+fn records(id: &str) -> Vec<String> {
+    let Some(content) = session_path(id).and_then(|p| std::fs::read_to_string(p).ok()) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter(|line| serde_json::from_str::<Record>(line).is_ok())
+        .map(String::from)
+        .collect()
+}
+
+/// Дописывает сообщение в файл сессии и обновляет индекс
+pub fn append(id: &str, msg: &ChatMessage) {
+    let Some(path) = session_path(id) else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let (Ok(mut file), Ok(line)) = (
+        OpenOptions::new().create(true).append(true).open(&path),
+        serde_json::to_string(&Record::from_message(msg)),
+    ) {
+        let _ = writeln!(file, "{}", line);
+    }
+    search::add_message(id, msg);
+
+    // Индекс сохраняется, только если изменились время или название
+    update_index_if(|index| {
+        let Some(info) = index.sessions.iter_mut().find(|s| s.id == id) else {
+            return false;
+        };
+        let updated = msg.timestamp.timestamp();
+        let title =
+            (info.title.is_empty() && msg.sender == USER_SENDER).then(|| make_title(&msg.text));
+        if info.updated == updated && title.is_none() {
+            return false;
+        }
+        info.updated = updated;
+        if let Some(title) = title {
+            info.title = title;
+        }
+        true
+    });
+}
+
+/// Перезаписывает файл сессии: первые `keep` сообщений остаются как есть,
+/// остальные заменяются на `messages`
+pub fn rewrite<'a>(id: &str, keep: usize, messages: impl IntoIterator<Item = &'a ChatMessage>) {
+    let Some(path) = session_path(id) else {
+        return;
+    };
+    let kept = if keep > 0 { records(id) } else { Vec::new() };
+    let content: String = kept
+        .into_iter()
+        .take(keep)
+        .chain(
+            messages
+                .into_iter()
+                .filter_map(|m| serde_json::to_string(&Record::from_message(m)).ok()),
+        )
+        .map(|line| line + "\n")
+        .collect();
+    let _ = std::fs::write(path, content);
+    search::invalidate();
+}
+
+/// Переименовывает сессию (пустое название — снова автоматическое)
+pub fn rename(id: &str, title: &str) {
+    update_index(|index| {
+        if let Some(info) = index.sessions.iter_mut().find(|s| s.id == id) {
+            info.title = make_title(title);
+        }
+    });
+}
+
+/// Удаляет сессию вместе с файлом
+pub fn delete(id: &str) {
+    if let Some(path) = session_path(id) {
+        let _ = std::fs::remove_file(path);
+    }
+    search::invalidate();
+    update_index(|index| {
+        index.sessions.retain(|s| s.id != id);
+        if index.active.as_deref() == Some(id) {
+            index.active = None;
+        }
+    });
+}
+
+/// Удаляет самые старые сессии сверх лимита (активная сохраняется)
+pub fn prune(limit: usize) {
+    let stale: Vec<String> = {
+        let index = index().lock().unwrap_or_else(|e| e.into_inner());
+        let mut sessions: Vec<&SessionInfo> = index.sessions.iter().collect();
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.updated));
+        sessions
+            .into_iter()
+            .filter(|s| index.active.as_deref() != Some(s.id.as_str()))
+            .skip(limit.saturating_sub(1))
+            .map(|s| s.id.clone())
+            .collect()
+    };
+    for id in stale {
+        delete(&id);
+    }
+}
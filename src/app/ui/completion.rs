@@ -0,0 +1,123 @@
+//! Список автодополнения над полем ввода
+
+use super::super::completion::{self, Suggestion};
+use super::super::guides::GuideRegistry;
+use super::super::i18n::tr;
+use eframe::egui;
+
+/// Состояние списка подсказок
+#[derive(Default)]
+pub struct CompletionState {
+    suggestions: Vec<Suggestion>,
+    selected: usize,
+    /// Выбор сделан стрелками — Enter подставит подсказку
+    navigated: bool,
+    /// Текст, для которого подобраны подсказки
+    input: String,
+    /// Список скрыт клавишей Esc до следующего изменения ввода
+    hidden: bool,
+    /// Поле ввода в фокусе (без него список не показывается)
+    focused: bool,
+}
+
+impl CompletionState {
+    /// Подбирает подсказки, если текст изменился
+    pub fn update(&mut self, input: &str, guides: &GuideRegistry, focused: bool) {
+        self.focused = focused;
+        if input == self.input {
+            return;
+        }
+        self.input = input.to_string();
+        self.suggestions = completion::suggest(input, guides.ids());
+        self.selected = 0;
+        self.navigated = false;
+        self.hidden = false;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.focused && !self.hidden && !self.suggestions.is_empty()
+    }
+
+    pub fn hide(&mut self) {
+        self.hidden = true;
+    }
+
+    /// Принимает текст без подсказок (например, из истории ввода)
+    pub fn dismiss(&mut self, input: &str) {
+        self.input = input.to_string();
+        self.suggestions.clear();
+    }
+
+    /// Обрабатывает Tab, стрелки и Enter до поля ввода.
+    /// Возвращает текст выбранной подсказки
+    pub fn handle_keys(&mut self, ctx: &egui::Context) -> Option<String> {
+        if !self.is_visible() {
+            return None;
+        }
+        let count = self.suggestions.len();
+        ctx.input_mut(|i| {
+            if i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown) {
+                self.selected = (self.selected + 1) % count;
+                self.navigated = true;
+            }
+            if i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp) {
+                self.selected = (self.selected + count - 1) % count;
+                self.navigated = true;
+            }
+            let enter = self.navigated && i.consume_key(egui::Modifiers::NONE, egui::Key::Enter);
+            if i.consume_key(egui::Modifiers::NONE, egui::Key::Tab) || enter {
+                return Some(self.suggestions[self.selected].text.clone());
+            }
+            None
+        })
+    }
+
+    /// Рисует список над полем ввода; возвращает подсказку, выбранную мышью
+    pub fn render(
+        &self,
+        ctx: &egui::Context,
+        input_rect: egui::Rect,
+        accent: egui::Color32,
+    ) -> Option<String> {
+        if !self.is_visible() {
+            return None;
+        }
+        let mut chosen = None;
+
+        egui::Area::new(egui::Id::new("completion_popup"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(input_rect.left_top() - egui::vec2(0.0, 4.0))
+            .pivot(egui::Align2::LEFT_BOTTOM)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_width(input_rect.width());
+                    for (i, suggestion) in self.suggestions.iter().enumerate() {
+                        let selected = i == self.selected;
+                        let response = ui
+                            .horizontal(|ui| {
+                                let label = egui::RichText::new(&suggestion.label);
+                                let label = if selected {
+                                    label.color(accent).strong()
+                                } else {
+                                    label
+                                };
+                                let clicked = ui.selectable_label(selected, label).clicked();
+                                ui.label(egui::RichText::new(&suggestion.hint).weak().small());
+                                clicked
+                            })
+                            .inner;
+                        if response {
+                            chosen = Some(suggestion.text.clone());
+                        }
+                    }
+                    ui.label(
+                        egui::RichText::new(tr("Tab — подставить, ↑↓ — выбор, Esc — скрыть"))
+                            .weak()
+                            .small(),
+                    );
+                });
+            });
+
+        chosen
+    }
+}
@@ -1,8 +1,12 @@
 //! Модальные диалоговые окна
 
-use super::super::chat::{BackgroundTask, DialogType};
+use super::super::ai::local_provider::LocalAi;
+use super::super::chat::DialogType;
+use super::super::commands::aur::{self, AurReview, SharedReview};
+use super::super::i18n::{tr, trf};
 use super::super::AssistantApp;
 use eframe::egui;
+use std::sync::Arc;
 
 /// Отрисовка модального диалога
 pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32) {
@@ -24,7 +28,7 @@ pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32
                 ui.set_max_width(500.0);
 
                 // Заголовок
-                ui.heading(&app.dialog.title);
+                ui.heading(tr(&app.dialog.title));
                 ui.separator();
 
                 ui.vertical_centered(|ui| {
@@ -32,7 +36,7 @@ pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32
 
                     // Сообщение
                     for line in app.dialog.message.lines() {
-                        ui.label(egui::RichText::new(line).size(15.0));
+                        ui.label(egui::RichText::new(tr(line)).size(15.0));
                     }
 
                     ui.add_space(15.0);
@@ -41,10 +45,10 @@ pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32
                     match app.dialog.dialog_type {
                         DialogType::PackageSearch => {
                             ui.horizontal(|ui| {
-                                ui.label("Пакет:");
+                                ui.label(tr("Пакет:"));
                                 let input = ui.add(
                                     egui::TextEdit::singleline(&mut app.dialog.input)
-                                        .hint_text("название...")
+                                        .hint_text(tr("название..."))
                                         .desired_width(200.0),
                                 );
                                 if app.dialog.visible {
@@ -65,6 +69,10 @@ pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32
                         _ => {}
                     }
 
+                    for (i, review) in app.dialog.aur_reviews.iter().enumerate() {
+                        ui.push_id(i, |ui| render_aur_review(ui, review, &app.ai));
+                    }
+
                     ui.add_space(20.0);
 
                     // Кнопки
@@ -73,7 +81,7 @@ pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32
 
                         // Отмена
                         if ui
-                            .add_sized(btn_size, egui::Button::new("Отмена"))
+                            .add_sized(btn_size, egui::Button::new(tr("Отмена")))
                             .clicked()
                         {
                             app.dialog.hide();
@@ -81,8 +89,8 @@ pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32
 
                         // Основная кнопка
                         let action_text = match app.dialog.dialog_type {
-                            DialogType::PackageSearch => "Найти",
-                            DialogType::Confirmation => "Подтвердить",
+                            DialogType::PackageSearch => tr("Найти"),
+                            DialogType::Confirmation => tr("Подтвердить"),
                             DialogType::Info => "OK",
                         };
 
@@ -103,33 +111,146 @@ pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32
 
 /// Обработка подтверждения
 fn handle_action(app: &mut AssistantApp) {
-    match app.dialog.dialog_type {
-        DialogType::PackageSearch => {
-            if !app.dialog.input.is_empty() {
-                app.tasks
-                    .execute(BackgroundTask::SearchPackages(app.dialog.input.clone()));
-            }
+    if let Some(task) = app.dialog.take_action(app.config.snapshot_before_upgrade) {
+        app.tasks.execute(task);
+    }
+
+    app.dialog.confirmed = true;
+    app.dialog.hide();
+}
+
+/// Сведения о пакете AUR в диалоге установки
+fn render_aur_review(ui: &mut egui::Ui, review: &SharedReview, ai: &Arc<LocalAi>) {
+    let Ok(state) = review.lock().map(|r| r.clone()) else {
+        return;
+    };
+
+    match state {
+        AurReview::Official => {}
+        AurReview::Loading => {
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(tr("Проверяю пакет в AUR..."));
+            });
         }
-        DialogType::Confirmation => {
-            let title = &app.dialog.title;
-            let package = &app.dialog.package;
-
-            if title.contains("Установка") && !package.is_empty() {
-                app.tasks
-                    .execute(BackgroundTask::InstallPackage(package.clone()));
-            } else if title.contains("Удаление") && !package.is_empty() {
-                app.tasks
-                    .execute(BackgroundTask::RemovePackage(package.clone()));
-            } else if title.contains("Обновление") {
-                app.tasks.execute(BackgroundTask::UpdateSystem);
-            } else if package == "__shutdown__" {
-                app.tasks.execute(BackgroundTask::ShutdownSystem);
-            } else if package == "__reboot__" {
-                app.tasks.execute(BackgroundTask::RebootSystem);
+        AurReview::Failed(e) => {
+            ui.add_space(10.0);
+            ui.label(egui::RichText::new(e).color(egui::Color32::LIGHT_RED));
+        }
+        AurReview::Ready {
+            info,
+            pkgbuild,
+            findings,
+            summary,
+        } => {
+            ui.add_space(10.0);
+            ui.label(
+                egui::RichText::new(tr("Пакет из AUR — проверьте PKGBUILD перед установкой"))
+                    .color(egui::Color32::YELLOW),
+            );
+            if let Some(description) = &info.description {
+                ui.label(egui::RichText::new(description).weak());
+            }
+            ui.add_space(5.0);
+
+            egui::Grid::new("aur_info").num_columns(2).show(ui, |ui| {
+                ui.label(tr("Пакет:"));
+                ui.label(format!("{} {}", info.name, info.version));
+                ui.end_row();
+
+                ui.label(tr("Сопровождающий:"));
+                match &info.maintainer {
+                    Some(m) => ui.label(m),
+                    None => ui.label(
+                        egui::RichText::new(tr("нет (пакет-сирота)"))
+                            .color(egui::Color32::LIGHT_RED),
+                    ),
+                };
+                ui.end_row();
+
+                ui.label(tr("Голоса:"));
+                ui.label(trf(
+                    "{} (популярность {})",
+                    &[&info.num_votes, &format!("{:.2}", info.popularity)],
+                ));
+                ui.end_row();
+
+                ui.label(tr("Обновлён:"));
+                ui.label(aur::format_timestamp(info.last_modified));
+                ui.end_row();
+
+                if let Some(ts) = info.out_of_date {
+                    ui.label(tr("Устарел:"));
+                    ui.label(
+                        egui::RichText::new(trf("помечен с {}", &[&aur::format_timestamp(ts)]))
+                            .color(egui::Color32::LIGHT_RED),
+                    );
+                    ui.end_row();
+                }
+            });
+
+            ui.add_space(5.0);
+            if findings.is_empty() {
+                ui.label(
+                    egui::RichText::new(tr("[OK] Опасных конструкций не найдено"))
+                        .color(egui::Color32::LIGHT_GREEN),
+                );
+            } else {
+                for finding in &findings {
+                    ui.label(
+                        egui::RichText::new(trf(
+                            "[!] строка {}: {}",
+                            &[&finding.line, &finding.kind.describe()],
+                        ))
+                        .color(egui::Color32::LIGHT_RED),
+                    )
+                    .on_hover_text(&finding.text);
+                }
+            }
+
+            ui.collapsing("PKGBUILD", |ui| {
+                egui::ScrollArea::vertical()
+                    .id_source("pkgbuild")
+                    .max_height(250.0)
+                    .show(ui, |ui| {
+                        for (i, line) in pkgbuild.lines().enumerate() {
+                            let risk = findings.iter().find(|f| f.line == i + 1);
+                            let color = if risk.is_some() {
+                                egui::Color32::LIGHT_RED
+                            } else {
+                                egui::Color32::GRAY
+                            };
+                            let label = ui.label(
+                                egui::RichText::new(format!("{:>3} {}", i + 1, line))
+                                    .monospace()
+                                    .small()
+                                    .color(color),
+                            );
+                            if let Some(risk) = risk {
+                                label.on_hover_text(risk.kind.describe());
+                            }
+                        }
+                    });
+            });
+
+            match summary.as_deref() {
+                Some("") => {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(tr("ИИ читает PKGBUILD..."));
+                    });
+                }
+                Some(text) => {
+                    ui.label(egui::RichText::new(tr("Мнение ИИ:")).strong());
+                    ui.label(text);
+                }
+                None => {
+                    if ui.button(tr("Описать PKGBUILD с помощью ИИ")).clicked() {
+                        aur::request_summary(review, ai.clone());
+                    }
+                }
             }
         }
-        DialogType::Info => {}
     }
-
-    app.dialog.hide();
 }
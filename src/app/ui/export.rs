@@ -0,0 +1,121 @@
+//! Окно экспорта переписки: формат и диапазон сообщений
+
+use super::super::export::{self, ExportFormat};
+use super::super::i18n::{tr, trf};
+use super::super::AssistantApp;
+use eframe::egui;
+
+/// Символов сообщения в подписи границы диапазона
+const PREVIEW_CHARS: usize = 40;
+
+/// Состояние окна экспорта
+#[derive(Default)]
+pub struct ExportState {
+    pub open: bool,
+    pub format: ExportFormat,
+    /// Весь чат или только диапазон from..=to (с единицы)
+    pub whole: bool,
+    pub from: usize,
+    pub to: usize,
+}
+
+impl ExportState {
+    /// Открывает окно с выбранным форматом для всей сессии
+    pub fn show(&mut self, format: ExportFormat) {
+        self.open = true;
+        self.format = format;
+        self.whole = true;
+        self.from = 1;
+        self.to = usize::MAX;
+    }
+}
+
+/// Окно экспорта
+pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32) {
+    let mut open = app.export.open;
+    let mut result = None;
+    let messages: Vec<_> = app.chat.messages().cloned().collect();
+    let total = messages.len();
+
+    egui::Window::new(tr("Экспорт чата"))
+        .open(&mut open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            let state = &mut app.export;
+
+            ui.horizontal(|ui| {
+                ui.label(tr("Формат:"));
+                for format in ExportFormat::ALL {
+                    ui.radio_value(&mut state.format, *format, format.label());
+                }
+            });
+            ui.add_space(5.0);
+
+            ui.radio_value(
+                &mut state.whole,
+                true,
+                trf("Весь чат ({} сообщ.)", &[&total]),
+            );
+            ui.radio_value(&mut state.whole, false, tr("Диапазон сообщений"));
+            state.to = state.to.clamp(1, total.max(1));
+            state.from = state.from.clamp(1, state.to);
+            if !state.whole {
+                ui.horizontal(|ui| {
+                    ui.label(tr("с"));
+                    ui.add(egui::DragValue::new(&mut state.from).clamp_range(1..=state.to));
+                    ui.label(tr("по"));
+                    ui.add(
+                        egui::DragValue::new(&mut state.to).clamp_range(state.from..=total.max(1)),
+                    );
+                });
+                for (label, index) in [(tr("Первое:"), state.from), (tr("Последнее:"), state.to)]
+                {
+                    if let Some(msg) = messages.get(index - 1) {
+                        let preview: String = msg.text.chars().take(PREVIEW_CHARS).collect();
+                        ui.label(
+                            egui::RichText::new(format!("{} {}: {}", label, msg.sender, preview))
+                                .weak()
+                                .small(),
+                        );
+                    }
+                }
+            }
+
+            let range = if state.whole {
+                0..total
+            } else {
+                state.from - 1..state.to.min(total)
+            };
+            let selected = &messages[range];
+            let title = app.chat.title();
+
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                if ui
+                    .button(egui::RichText::new(tr("Сохранить в файл")).color(accent))
+                    .clicked()
+                {
+                    result = Some(export::save(selected, &title, state.format));
+                }
+                if ui
+                    .button(tr("Копировать"))
+                    .on_hover_text(tr("Скопировать в буфер обмена"))
+                    .clicked()
+                {
+                    ui.output_mut(|o| {
+                        o.copied_text = export::render(selected, &title, state.format)
+                    });
+                    result = Some(trf(
+                        "[OK] Скопировано сообщений: {} ({})",
+                        &[&selected.len(), &state.format.label()],
+                    ));
+                }
+            });
+        });
+
+    app.export.open = open;
+    if let Some(text) = result {
+        app.export.open = false;
+        app.chat.add_message("Система", text);
+    }
+}
@@ -1,13 +1,29 @@
 //! Графический интерфейс
 
+pub mod completion;
 pub mod dialogs;
+pub mod export;
+pub mod monitor;
+pub mod panels;
+pub mod search;
+pub mod sessions;
 pub mod widgets;
 
 use super::chat::BackgroundTask;
-use super::constants::{messages, APP_NAME, APP_VERSION, SETTINGS_PANEL_WIDTH};
+use super::commands::gpu;
+use super::commands::mirrors::{self, DiffLine, MirrorFilter};
+use super::commands::security;
+use super::commands::system;
+use super::constants::{
+    messages, APP_NAME, APP_VERSION, SETTINGS_PANEL_WIDTH, SNAPSHOT_MANUAL_LABEL,
+};
+use super::export::ExportFormat;
+use super::i18n::{self, tr, trf, Language};
+use super::macros;
 use super::AssistantApp;
 use eframe::egui;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 /// Главная функция рендеринга
 pub fn render(ctx: &egui::Context, app: &mut AssistantApp) {
@@ -18,6 +34,10 @@ pub fn render(ctx: &egui::Context, app: &mut AssistantApp) {
 
     render_header(ctx, app, accent);
 
+    if app.show_sessions {
+        sessions::render(ctx, app, accent);
+    }
+
     if app.show_settings {
         render_settings(ctx, app, accent);
     }
@@ -25,6 +45,16 @@ pub fn render(ctx: &egui::Context, app: &mut AssistantApp) {
     render_input(ctx, app, accent);
     render_chat(ctx, app, accent);
 
+    panels::render(ctx, app, accent);
+
+    if app.search.open {
+        search::render(ctx, app, accent);
+    }
+
+    if app.export.open {
+        export::render(ctx, app, accent);
+    }
+
     // Диалог с затемнением
     if app.dialog.visible {
         dialogs::render(ctx, app, accent);
@@ -38,12 +68,38 @@ fn handle_hotkeys(ctx: &egui::Context, app: &mut AssistantApp) {
         if i.modifiers.ctrl && i.key_pressed(egui::Key::L) {
             app.clear_chat();
         }
-        // Escape — закрыть настройки/диалог
+        // Ctrl+F — поиск по истории
+        if i.modifiers.ctrl && i.key_pressed(egui::Key::F) {
+            app.search.show();
+        }
+        // Ctrl+E — экспорт чата
+        if i.modifiers.ctrl && i.key_pressed(egui::Key::E) {
+            app.export.show(ExportFormat::default());
+        }
+        // Ctrl+N — новый чат
+        if i.modifiers.ctrl && i.key_pressed(egui::Key::N) {
+            app.new_session();
+        }
+        // Escape — закрыть диалог/панель/настройки
         if i.key_pressed(egui::Key::Escape) {
-            if app.dialog.visible {
+            if app.completion.is_visible() {
+                app.completion.hide();
+            } else if app.dialog.visible {
                 app.dialog.hide();
+            } else if app.export.open {
+                app.export.open = false;
+            } else if app.search.open {
+                app.search.open = false;
+            } else if app.panels.active.is_some() {
+                app.panels.active = None;
+            } else if app.session_rename.is_some() {
+                // Поле переименования само отменит правку
+            } else if app.editing.is_some() {
+                app.cancel_edit();
             } else if app.show_settings {
                 app.show_settings = false;
+            } else if app.show_sessions {
+                app.show_sessions = false;
             }
         }
     });
@@ -56,6 +112,15 @@ fn render_header(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Colo
         ui.horizontal(|ui| {
             ui.add_space(10.0);
 
+            // Список сессий
+            if ui
+                .button(egui::RichText::new("[≡]").size(16.0))
+                .on_hover_text(tr("Чаты"))
+                .clicked()
+            {
+                app.show_sessions = !app.show_sessions;
+            }
+
             // Название
             ui.heading(
                 egui::RichText::new(app.config.assistant_name.to_uppercase())
@@ -77,11 +142,52 @@ fn render_header(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Colo
                     .size(12.0),
             )
             .on_hover_text(if ollama_online {
-                "Ollama подключена"
+                tr("Ollama подключена")
             } else {
-                "Ollama недоступна"
+                tr("Ollama недоступна")
             });
 
+            // Индикатор уязвимостей
+            if let Some(count) = security::last_audit_count().filter(|c| *c > 0) {
+                let label = ui
+                    .add(
+                        egui::Label::new(
+                            egui::RichText::new(format!("[!] {}", count))
+                                .color(egui::Color32::from_rgb(255, 140, 0))
+                                .size(12.0),
+                        )
+                        .sense(egui::Sense::click()),
+                    )
+                    .on_hover_text(trf("Уязвимых пакетов: {}. Нажмите для отчёта", &[&count]));
+                if label.clicked() {
+                    app.tasks.execute(BackgroundTask::SecurityAudit(
+                        app.config.security_feed.clone(),
+                    ));
+                }
+            }
+
+            // Обратный отсчёт таймера питания
+            if let Some(scheduled) = system::current_schedule() {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "[{} {}]",
+                        scheduled.action.label(),
+                        system::format_countdown(scheduled.remaining_secs())
+                    ))
+                    .color(accent)
+                    .size(12.0),
+                )
+                .on_hover_text(trf("Запланировано на {}", &[&scheduled.time()]));
+                if ui
+                    .small_button("x")
+                    .on_hover_text(tr("Отменить таймер"))
+                    .clicked()
+                {
+                    app.tasks.execute(BackgroundTask::CancelPowerSchedule);
+                }
+                ctx.request_repaint_after(Duration::from_secs(1));
+            }
+
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 ui.add_space(10.0);
 
@@ -90,10 +196,20 @@ fn render_header(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Colo
                     app.show_settings = !app.show_settings;
                 }
 
+                // Меню панелей
+                ui.menu_button(tr("Панели"), |ui| {
+                    for panel in panels::Panel::ALL {
+                        if ui.button(panel.title()).clicked() {
+                            app.panels.open(*panel, "");
+                            ui.close_menu();
+                        }
+                    }
+                });
+
                 // Индикатор загрузки
                 if app.tasks.is_busy() {
                     ui.label(
-                        egui::RichText::new(messages::PROCESSING).color(egui::Color32::YELLOW),
+                        egui::RichText::new(tr(messages::PROCESSING)).color(egui::Color32::YELLOW),
                     );
                 }
             });
@@ -112,26 +228,56 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                 .auto_shrink([false; 2])
                 .show(ui, |ui| {
                     ui.add_space(20.0);
-                    ui.heading("Настройки");
+                    ui.heading(tr("Настройки"));
+                    ui.separator();
+
+                    // Мониторинг системы
+                    ui.add_space(10.0);
+                    egui::CollapsingHeader::new(egui::RichText::new(tr("Мониторинг системы")).strong())
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            if let Some(data) = app.monitor.data() {
+                                monitor::render_dashboard(ui, &data, accent);
+                            }
+                        });
                     ui.separator();
 
                     let mut changed = false;
 
                     // Персонализация
                     ui.add_space(10.0);
-                    ui.label(egui::RichText::new("Персонализация").strong());
+                    ui.label(egui::RichText::new(tr("Персонализация")).strong());
                     ui.add_space(5.0);
 
-                    ui.label("Цвет темы:");
+                    ui.label(tr("Цвет темы:"));
                     changed |= ui
                         .color_edit_button_srgb(&mut app.config.accent_color)
                         .changed();
 
+                    ui.label(tr("Язык:"));
+                    egui::ComboBox::from_id_source("settings_language")
+                        .selected_text(app.config.language.name())
+                        .show_ui(ui, |ui| {
+                            for language in Language::ALL {
+                                if ui
+                                    .selectable_value(
+                                        &mut app.config.language,
+                                        *language,
+                                        language.name(),
+                                    )
+                                    .changed()
+                                {
+                                    i18n::set_language(*language);
+                                    changed = true;
+                                }
+                            }
+                        });
+
                     // ИИ (Ollama)
                     ui.add_space(20.0);
                     ui.separator();
                     ui.add_space(10.0);
-                    ui.label(egui::RichText::new("ИИ (Ollama)").strong());
+                    ui.label(egui::RichText::new(tr("ИИ (Ollama)")).strong());
                     ui.add_space(5.0);
 
                     // Статус установки
@@ -140,21 +286,21 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
 
                     if ollama_installed {
                         ui.label(
-                            egui::RichText::new("[OK] Ollama установлена")
+                            egui::RichText::new(tr("[OK] Ollama установлена"))
                                 .color(egui::Color32::LIGHT_GREEN),
                         );
                     } else {
                         ui.label(
-                            egui::RichText::new("[X] Ollama не установлена")
+                            egui::RichText::new(tr("[X] Ollama не установлена"))
                                 .color(egui::Color32::LIGHT_RED),
                         );
                         ui.add_space(3.0);
                         if ui
-                            .button(egui::RichText::new("Установить Ollama").color(accent))
+                            .button(egui::RichText::new(tr("Установить Ollama")).color(accent))
                             .clicked()
                         {
                             app.tasks.execute(BackgroundTask::InstallOllama);
-                            app.chat.add_message("Система", messages::OLLAMA_INSTALLING);
+                            app.chat.add_message("Система", tr(messages::OLLAMA_INSTALLING));
                             // Обновим статус после установки
                             let ollama_installed = app.ollama_installed.clone();
                             std::thread::spawn(move || {
@@ -171,22 +317,22 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                     ui.add_space(5.0);
                     if ollama_online {
                         ui.label(
-                            egui::RichText::new("[OK] Сервис запущен")
+                            egui::RichText::new(tr("[OK] Сервис запущен"))
                                 .color(egui::Color32::LIGHT_GREEN),
                         );
                     } else {
                         ui.label(
-                            egui::RichText::new("[X] Сервис не запущен")
+                            egui::RichText::new(tr("[X] Сервис не запущен"))
                                 .color(egui::Color32::LIGHT_RED),
                         );
                         if ollama_installed {
                             ui.add_space(3.0);
                             if ui
-                                .button(egui::RichText::new("Запустить Ollama").color(accent))
+                                .button(egui::RichText::new(tr("Запустить Ollama")).color(accent))
                                 .clicked()
                             {
                                 app.tasks.execute(BackgroundTask::StartOllama);
-                                app.chat.add_message("Система", messages::OLLAMA_STARTING);
+                                app.chat.add_message("Система", tr(messages::OLLAMA_STARTING));
                                 // Обновим статус после запуска
                                 let ollama_online = app.ollama_online.clone();
                                 tokio::spawn(async move {
@@ -200,7 +346,7 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                     }
 
                     ui.add_space(10.0);
-                    ui.label("Модель:");
+                    ui.label(tr("Модель:"));
                     let model_response = ui.add(
                         egui::TextEdit::singleline(&mut app.config.ollama_model)
                             .hint_text("llama3")
@@ -212,7 +358,7 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                     }
 
                     ui.add_space(5.0);
-                    if ui.button("Проверить соединение").clicked() {
+                    if ui.button(tr("Проверить соединение")).clicked() {
                         let ollama_online = app.ollama_online.clone();
                         let ollama_installed = app.ollama_installed.clone();
                         std::thread::spawn(move || {
@@ -231,39 +377,39 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                     ui.add_space(10.0);
                     ui.separator();
                     ui.add_space(5.0);
-                    ui.label(egui::RichText::new("Кастомная модель").strong());
+                    ui.label(egui::RichText::new(tr("Кастомная модель")).strong());
                     ui.add_space(5.0);
 
                     let model_exists = app.custom_model_exists.load(Ordering::SeqCst);
                     if model_exists {
                         ui.label(
-                            egui::RichText::new("[OK] Модель 'alfons' готова")
+                            egui::RichText::new(tr("[OK] Модель 'alfons' готова"))
                                 .color(egui::Color32::LIGHT_GREEN),
                         );
                         ui.add_space(3.0);
-                        if ui.button("Использовать alfons").clicked() {
+                        if ui.button(tr("Использовать alfons")).clicked() {
                             app.config.ollama_model = "alfons".to_string();
                             app.ai.set_model("alfons");
                             changed = true;
                         }
                     } else {
                         ui.label(
-                            egui::RichText::new("Модель 'alfons' не создана")
+                            egui::RichText::new(tr("Модель 'alfons' не создана"))
                                 .color(egui::Color32::GRAY),
                         );
                         ui.add_space(3.0);
                         ui.label(
-                            egui::RichText::new("Создаёт модель с оптимизированным промптом")
+                            egui::RichText::new(tr("Создаёт модель с оптимизированным промптом"))
                                 .weak()
                                 .small(),
                         );
                         ui.add_space(3.0);
                         if ui
-                            .button(egui::RichText::new("Создать модель alfons").color(accent))
+                            .button(egui::RichText::new(tr("Создать модель alfons")).color(accent))
                             .clicked()
                         {
                             app.tasks.execute(BackgroundTask::CreateCustomModel);
-                            app.chat.add_message("Система", messages::MODEL_CREATING);
+                            app.chat.add_message("Система", tr(messages::MODEL_CREATING));
                             // Обновим статус после создания
                             let custom_model_exists = app.custom_model_exists.clone();
                             let ai = app.ai.clone();
@@ -282,12 +428,12 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                     ui.add_space(20.0);
                     ui.separator();
                     ui.add_space(10.0);
-                    ui.label(egui::RichText::new("Чат").strong());
+                    ui.label(egui::RichText::new(tr("Чат")).strong());
                     ui.add_space(5.0);
 
                     if ui
                         .button(
-                            egui::RichText::new("X Очистить чат").color(egui::Color32::LIGHT_RED),
+                            egui::RichText::new(tr("X Очистить чат")).color(egui::Color32::LIGHT_RED),
                         )
                         .clicked()
                     {
@@ -295,30 +441,102 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                     }
                     ui.label(egui::RichText::new("Ctrl+L").weak().small());
 
+                    // Макросы
+                    ui.add_space(20.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+                    ui.label(egui::RichText::new(tr("Макросы")).strong());
+                    ui.add_space(5.0);
+
+                    let mut remove = None;
+                    for (i, m) in app.macros.iter().enumerate() {
+                        ui.horizontal(|ui| {
+                            if ui
+                                .small_button("X")
+                                .on_hover_text(tr("Удалить макрос"))
+                                .clicked()
+                            {
+                                remove = Some(i);
+                            }
+                            if ui
+                                .link(egui::RichText::new(&m.name).strong())
+                                .on_hover_text(tr("Изменить"))
+                                .clicked()
+                            {
+                                app.macro_name_input = m.name.clone();
+                                app.macro_steps_input = m.steps.join("; ");
+                            }
+                            ui.label(egui::RichText::new(m.steps.join(" → ")).weak().small());
+                        });
+                    }
+                    if let Some(i) = remove {
+                        app.macros.remove(i);
+                        if let Err(e) = macros::save(&app.macros) {
+                            app.chat.add_message("Система", e);
+                        }
+                    }
+                    if app.macros.is_empty() {
+                        ui.label(egui::RichText::new(tr("Макросов пока нет")).weak());
+                    }
+
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Имя:"));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut app.macro_name_input)
+                                .hint_text(tr("утро"))
+                                .desired_width(200.0),
+                        );
+                    });
+                    ui.label(tr("Команды (через ;):"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut app.macro_steps_input)
+                            .hint_text(tr("обновить систему; очистить кэш"))
+                            .desired_width(f32::INFINITY),
+                    );
+                    if ui.button(tr("Сохранить макрос")).clicked() {
+                        match macros::upsert(
+                            &mut app.macros,
+                            &app.macro_name_input,
+                            &app.macro_steps_input,
+                        ) {
+                            Ok(()) => {
+                                app.macro_name_input.clear();
+                                app.macro_steps_input.clear();
+                            }
+                            Err(e) => app.chat.add_message("Система", e),
+                        }
+                    }
+                    ui.label(
+                        egui::RichText::new(tr("Введите имя макроса в чат, чтобы выполнить его шаги"))
+                            .weak()
+                            .small(),
+                    );
+
                     // Пакетный менеджер
                     ui.add_space(20.0);
                     ui.separator();
                     ui.add_space(10.0);
-                    ui.label(egui::RichText::new("Пакетный менеджер").strong());
+                    ui.label(egui::RichText::new(tr("Пакетный менеджер")).strong());
                     ui.add_space(5.0);
 
                     let yay_ok = app.yay_installed.load(Ordering::SeqCst);
 
                     if yay_ok {
                         ui.label(
-                            egui::RichText::new("[OK] yay установлен")
+                            egui::RichText::new(tr("[OK] yay установлен"))
                                 .color(egui::Color32::LIGHT_GREEN),
                         );
                     } else {
                         ui.label(
-                            egui::RichText::new("[X] yay не найден")
+                            egui::RichText::new(tr("[X] yay не найден"))
                                 .color(egui::Color32::LIGHT_RED),
                         );
                     }
 
                     ui.add_space(5.0);
                     ui.horizontal(|ui| {
-                        if ui.button("Проверить").clicked() {
+                        if ui.button(tr("Проверить")).clicked() {
                             // Обновляем кэш в фоне
                             let yay_installed = app.yay_installed.clone();
                             std::thread::spawn(move || {
@@ -328,31 +546,211 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                         }
                         if !yay_ok
                             && ui
-                                .button(egui::RichText::new("Установить yay").color(accent))
+                                .button(egui::RichText::new(tr("Установить yay")).color(accent))
                                 .clicked()
                         {
                             app.tasks.execute(BackgroundTask::InstallYay);
-                            app.chat.add_message("Система", messages::YAY_INSTALLING);
+                            app.chat.add_message("Система", tr(messages::YAY_INSTALLING));
                         }
                     });
 
+                    // Видеокарта
+                    ui.add_space(20.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+                    ui.label(egui::RichText::new(tr("Видеокарта")).strong());
+                    ui.add_space(5.0);
+
+                    match gpu::last_report() {
+                        Some(report) => {
+                            for g in &report.gpus {
+                                ui.label(format!("{} {}", g.vendor.name(), g.model));
+                                ui.label(
+                                    egui::RichText::new(trf(
+                                        "драйвер: {}",
+                                        &[&g.driver.as_deref().unwrap_or(tr("не загружен"))],
+                                    ))
+                                    .small()
+                                    .weak(),
+                                );
+                            }
+                            let missing = &report.recommendation.missing;
+                            if missing.is_empty() {
+                                ui.label(
+                                    egui::RichText::new(tr("[OK] Драйверы установлены"))
+                                        .color(egui::Color32::LIGHT_GREEN),
+                                );
+                            } else if ui
+                                .button(egui::RichText::new(tr("Установить драйверы")).color(accent))
+                                .on_hover_text(missing.join(" "))
+                                .clicked()
+                            {
+                                let r = gpu::show_install_dialog(
+                                    &report,
+                                    &app.config,
+                                    &mut app.dialog,
+                                );
+                                app.chat.add_message("Система", &r);
+                            }
+                        }
+                        None => {
+                            ui.label(egui::RichText::new(tr("Определяется...")).weak());
+                        }
+                    }
+                    if ui.button(tr("Подробнее")).clicked() {
+                        app.tasks.execute(BackgroundTask::GpuInfo);
+                    }
+
+                    // Сессии чата
+                    ui.add_space(20.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+                    ui.label(egui::RichText::new(tr("Сессии чата")).strong());
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Хранить чатов:"));
+                        changed |= ui
+                            .add(
+                                egui::DragValue::new(&mut app.config.session_limit)
+                                    .clamp_range(1..=500),
+                            )
+                            .on_hover_text(tr("Более старые сессии удаляются при создании новой"))
+                            .changed();
+                    });
+
+                    // Снимки системы
+                    ui.add_space(20.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+                    ui.label(egui::RichText::new(tr("Снимки системы")).strong());
+                    ui.add_space(5.0);
+
+                    changed |= ui
+                        .checkbox(
+                            &mut app.config.snapshot_before_upgrade,
+                            tr("Снимок перед обновлением"),
+                        )
+                        .on_hover_text(tr("snapper, timeshift или btrfs; пропускается, если снимки делает snap-pac или timeshift-autosnap"))
+                        .changed();
+                    ui.horizontal(|ui| {
+                        if ui.button(tr("Список снимков")).clicked() {
+                            app.tasks.execute(BackgroundTask::ListSnapshots);
+                        }
+                        if ui.button(tr("Создать снимок")).clicked() {
+                            app.tasks.execute(BackgroundTask::CreateSnapshot(
+                                tr(SNAPSHOT_MANUAL_LABEL).into(),
+                            ));
+                        }
+                    });
+
+                    // Зеркала pacman
+                    ui.add_space(20.0);
+                    ui.separator();
+                    ui.add_space(10.0);
+                    ui.label(egui::RichText::new(tr("Зеркала pacman")).strong());
+                    ui.add_space(5.0);
+
+                    ui.label(tr("Страны (через запятую):"));
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(&mut app.mirror_countries_input)
+                                .hint_text("Germany, Russia")
+                                .desired_width(200.0),
+                        )
+                        .changed()
+                    {
+                        app.config.mirror_countries = app
+                            .mirror_countries_input
+                            .split(',')
+                            .map(|c| c.trim().to_string())
+                            .filter(|c| !c.is_empty())
+                            .collect();
+                        changed = true;
+                    }
+                    changed |= ui
+                        .checkbox(&mut app.config.mirror_https_only, tr("Только HTTPS"))
+                        .changed();
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Количество:"));
+                        changed |= ui
+                            .add(
+                                egui::DragValue::new(&mut app.config.mirror_count)
+                                    .clamp_range(1..=50),
+                            )
+                            .changed();
+                    });
+
+                    ui.add_space(5.0);
+                    if ui.button(tr("Ранжировать зеркала")).clicked() {
+                        app.tasks
+                            .execute(BackgroundTask::RankMirrors(MirrorFilter::from_config(
+                                &app.config,
+                            )));
+                        app.chat
+                            .add_message("Система", tr("Замеряю задержку зеркал..."));
+                    }
+
+                    if let Some(plan) = mirrors::pending_plan() {
+                        ui.add_space(5.0);
+                        ui.label(
+                            egui::RichText::new(trf(
+                                "Новый список: {} зеркал",
+                                &[&plan.mirrors.len()],
+                            ))
+                            .color(egui::Color32::LIGHT_GREEN),
+                        );
+                        ui.collapsing(tr("Предпросмотр изменений"), |ui| {
+                            egui::ScrollArea::vertical()
+                                .id_source("mirror_diff")
+                                .max_height(200.0)
+                                .show(ui, |ui| {
+                                    for line in &plan.diff {
+                                        let (text, color) = match line {
+                                            DiffLine::Added(l) => {
+                                                (format!("+ {}", l), egui::Color32::LIGHT_GREEN)
+                                            }
+                                            DiffLine::Removed(l) => {
+                                                (format!("- {}", l), egui::Color32::LIGHT_RED)
+                                            }
+                                            DiffLine::Same(_) => continue,
+                                        };
+                                        ui.label(
+                                            egui::RichText::new(text)
+                                                .monospace()
+                                                .small()
+                                                .color(color),
+                                        );
+                                    }
+                                });
+                        });
+                        if ui
+                            .button(egui::RichText::new(tr("Записать mirrorlist")).color(accent))
+                            .clicked()
+                        {
+                            mirrors::show_apply_dialog(&mut app.dialog, plan);
+                        }
+                    }
+
                     // Горячие клавиши
                     ui.add_space(20.0);
                     ui.separator();
                     ui.add_space(10.0);
-                    ui.label(egui::RichText::new("Горячие клавиши").strong());
+                    ui.label(egui::RichText::new(tr("Горячие клавиши")).strong());
                     ui.add_space(5.0);
-                    ui.label(egui::RichText::new("Ctrl+L — очистить чат").weak().small());
-                    ui.label(egui::RichText::new("Esc — закрыть панель").weak().small());
-                    ui.label(egui::RichText::new("↑/↓ — история команд").weak().small());
+                    ui.label(egui::RichText::new(tr("Ctrl+L — очистить чат")).weak().small());
+                    ui.label(egui::RichText::new(tr("Ctrl+N — новый чат")).weak().small());
+                    ui.label(egui::RichText::new(tr("Ctrl+F — поиск по истории")).weak().small());
+                    ui.label(egui::RichText::new(tr("Ctrl+E — экспорт чата")).weak().small());
+                    ui.label(egui::RichText::new(tr("Esc — закрыть панель")).weak().small());
+                    ui.label(egui::RichText::new(tr("↑/↓ — история команд")).weak().small());
 
                     // О программе
                     ui.add_space(20.0);
                     ui.separator();
                     ui.add_space(10.0);
-                    ui.label(egui::RichText::new("О программе").strong());
+                    ui.label(egui::RichText::new(tr("О программе")).strong());
                     ui.add_space(5.0);
-                    ui.label(format!("{} — помощник для Arch Linux", APP_NAME));
+                    ui.label(trf("{} — помощник для Arch Linux", &[&APP_NAME]));
                     ui.label(egui::RichText::new(format!("v{}", APP_VERSION)).weak());
                     ui.label(
                         egui::RichText::new(format!("DE: {}", app.desktop_env.name()))
@@ -364,13 +762,13 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                     ui.add_space(20.0);
                     ui.separator();
                     ui.add_space(10.0);
-                    ui.label(egui::RichText::new("Установка").strong());
+                    ui.label(egui::RichText::new(tr("Установка")).strong());
                     ui.add_space(5.0);
 
                     let is_installed = app.app_installed.load(Ordering::SeqCst);
                     if is_installed {
                         ui.label(
-                            egui::RichText::new("[OK] Установлено в систему")
+                            egui::RichText::new(tr("[OK] Установлено в систему"))
                                 .color(egui::Color32::LIGHT_GREEN),
                         );
                         if let Some(path) = super::installer::get_installed_path() {
@@ -383,7 +781,7 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                         ui.add_space(5.0);
                         if ui
                             .button(
-                                egui::RichText::new("Удалить из системы")
+                                egui::RichText::new(tr("Удалить из системы"))
                                     .color(egui::Color32::LIGHT_RED),
                             )
                             .clicked()
@@ -398,16 +796,16 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                             });
                         }
                     } else {
-                        ui.label(egui::RichText::new("Не установлено").color(egui::Color32::GRAY));
+                        ui.label(egui::RichText::new(tr("Не установлено")).color(egui::Color32::GRAY));
                         ui.add_space(3.0);
                         ui.label(
-                            egui::RichText::new("Добавит ярлык в меню приложений")
+                            egui::RichText::new(tr("Добавит ярлык в меню приложений"))
                                 .weak()
                                 .small(),
                         );
                         ui.add_space(5.0);
                         if ui
-                            .button(egui::RichText::new("Установить в систему").color(accent))
+                            .button(egui::RichText::new(tr("Установить в систему")).color(accent))
                             .clicked()
                         {
                             app.tasks.execute(BackgroundTask::InstallToSystem);
@@ -424,12 +822,12 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
                         if !super::installer::is_local_bin_in_path() {
                             ui.add_space(5.0);
                             ui.label(
-                                egui::RichText::new("~/.local/bin не в PATH")
+                                egui::RichText::new(tr("~/.local/bin не в PATH"))
                                     .color(egui::Color32::YELLOW)
                                     .small(),
                             );
                             ui.label(
-                                egui::RichText::new("Добавьте в .bashrc/.zshrc:")
+                                egui::RichText::new(tr("Добавьте в .bashrc/.zshrc:"))
                                     .weak()
                                     .small(),
                             );
@@ -455,6 +853,8 @@ fn render_settings(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Co
 
 /// Область чата
 fn render_chat(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32) {
+    let mut action = None;
+
     egui::CentralPanel::default().show(ctx, |ui| {
         egui::ScrollArea::vertical()
             .auto_shrink([false; 2])
@@ -462,12 +862,46 @@ fn render_chat(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color3
             .show(ui, |ui| {
                 ui.add_space(10.0);
                 for msg in app.chat.messages() {
-                    widgets::render_message(ui, msg, accent);
+                    let inner = ui.scope(|ui| widgets::render_message(ui, msg, accent));
+                    let response = inner.response;
+                    if inner.inner.is_some() {
+                        action = inner.inner;
+                    }
+
+                    // Сообщение, выбранное в поиске: прокрутка и подсветка
+                    if let Some(focus) = app.search.focus.as_mut().filter(|f| f.matches(msg)) {
+                        if focus.take_scroll() {
+                            response.scroll_to_me(Some(egui::Align::Center));
+                        }
+                        if focus.is_active() {
+                            ui.painter().rect_stroke(
+                                response.rect.expand(3.0),
+                                15.0,
+                                egui::Stroke::new(2.0, accent),
+                            );
+                            ctx.request_repaint_after(Duration::from_millis(200));
+                        }
+                    }
                     ui.add_space(8.0);
                 }
                 ui.add_space(10.0);
             });
     });
+
+    match action {
+        // Команды из чата запускаются только после подтверждения
+        Some(widgets::MessageAction::Run(code)) => app.dialog.show_confirm_task(
+            "Выполнить команды",
+            &trf("Запустить в терминале?\n\n{}", &[&code]),
+            BackgroundTask::RunSnippet(code),
+        ),
+        Some(widgets::MessageAction::Regenerate(id)) => app.regenerate(id),
+        Some(widgets::MessageAction::Edit(id)) => app.start_edit(id),
+        Some(widgets::MessageAction::SwitchVariant(id, index)) => {
+            app.chat.switch_variant(id, index)
+        }
+        None => {}
+    }
 }
 
 /// Поле ввода
@@ -480,12 +914,38 @@ fn render_input(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color
             bottom: 30.0,
         }))
         .show(ctx, |ui| {
+            if app.editing.is_some() {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        egui::RichText::new(tr(
+                            "Изменение сообщения: Enter — новый вариант, Esc — отмена",
+                        ))
+                        .color(accent)
+                        .small(),
+                    );
+                    if ui.small_button(tr("Отмена")).clicked() {
+                        app.cancel_edit();
+                    }
+                });
+                ui.add_space(4.0);
+            }
             ui.horizontal(|ui| {
+                // Автодополнение перехватывает Tab и стрелки до поля ввода
+                let input_id = egui::Id::new("chat_input");
+                let focused = ctx.memory(|m| m.has_focus(input_id));
+                app.completion.update(&app.input_text, &app.guides, focused);
+                let mut completed = app.completion.handle_keys(ctx);
+                if let Some(text) = &completed {
+                    app.input_text = text.clone();
+                }
+
                 let input = ui.add_sized(
                     [ui.available_width() - 130.0, 45.0],
                     egui::TextEdit::singleline(&mut app.input_text)
+                        .id(input_id)
+                        .lock_focus(true)
                         .margin(egui::vec2(15.0, 11.0))
-                        .hint_text("Введите команду..."),
+                        .hint_text(tr("Введите команду...")),
                 );
 
                 // Сбрасываем позицию истории при ручном вводе текста
@@ -495,23 +955,47 @@ fn render_input(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color
 
                 // История команд (стрелки)
                 if input.has_focus() {
+                    let mut recalled = false;
                     ctx.input(|i| {
                         if i.key_pressed(egui::Key::ArrowUp) {
                             if let Some(prev) = app.input_history.up(&app.input_text) {
                                 app.input_text = prev.to_string();
+                                recalled = true;
                             }
                         }
                         if i.key_pressed(egui::Key::ArrowDown) {
                             if let Some(next) = app.input_history.down() {
                                 app.input_text = next.to_string();
+                                recalled = true;
                             }
                         }
                     });
+                    // Подсказки не мешают листать историю
+                    if recalled {
+                        app.completion.dismiss(&app.input_text);
+                    }
+                }
+
+                if let Some(text) = app.completion.render(ctx, input.rect, accent) {
+                    app.input_text = text;
+                    input.request_focus();
+                    completed = Some(app.input_text.clone());
+                }
+
+                // После подстановки курсор — в конец строки
+                if completed.is_some() {
+                    if let Some(mut state) = egui::TextEdit::load_state(ctx, input_id) {
+                        let end = egui::text::CCursor::new(app.input_text.chars().count());
+                        state
+                            .cursor
+                            .set_char_range(Some(egui::text::CCursorRange::one(end)));
+                        state.store(ctx, input_id);
+                    }
                 }
 
                 ui.add_space(10.0);
 
-                let btn = egui::Button::new(egui::RichText::new("ОТПРАВИТЬ").strong())
+                let btn = egui::Button::new(egui::RichText::new(tr("ОТПРАВИТЬ")).strong())
                     .fill(accent)
                     .min_size(egui::vec2(110.0, 45.0));
 
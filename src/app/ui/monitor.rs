@@ -0,0 +1,166 @@
+//! Панель мониторинга в настройках: графики загрузки, память, диски, датчики
+
+use super::super::i18n::{tr, trf};
+use super::super::monitor::{format_bytes, format_rate, MonitorData};
+use eframe::egui;
+use std::collections::VecDeque;
+
+const SPARKLINE_HEIGHT: f32 = 28.0;
+
+/// Мини-график истории значений. `max` — верхняя граница шкалы (None — по максимуму)
+fn sparkline(ui: &mut egui::Ui, values: &VecDeque<f32>, max: Option<f32>, color: egui::Color32) {
+    let width = ui.available_width();
+    let (rect, _) =
+        ui.allocate_exact_size(egui::vec2(width, SPARKLINE_HEIGHT), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    if values.len() < 2 {
+        return;
+    }
+
+    let max = max
+        .unwrap_or_else(|| values.iter().copied().fold(0.0, f32::max))
+        .max(f32::EPSILON);
+    let step = rect.width() / (values.len() - 1) as f32;
+    let points: Vec<egui::Pos2> = values
+        .iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let y = rect.bottom() - (v / max).clamp(0.0, 1.0) * (rect.height() - 2.0) - 1.0;
+            egui::pos2(rect.left() + i as f32 * step, y)
+        })
+        .collect();
+
+    painter.add(egui::Shape::line(points, egui::Stroke::new(1.5, color)));
+}
+
+/// Полоса заполненности с подписью
+fn usage_bar(ui: &mut egui::Ui, fraction: f32, text: String) {
+    let color = if fraction > 0.9 {
+        egui::Color32::from_rgb(200, 60, 60)
+    } else if fraction > 0.75 {
+        egui::Color32::from_rgb(200, 140, 40)
+    } else {
+        ui.visuals().selection.bg_fill
+    };
+    ui.add(
+        egui::ProgressBar::new(fraction.clamp(0.0, 1.0))
+            .text(text)
+            .fill(color),
+    );
+}
+
+/// Отрисовка панели мониторинга
+pub fn render_dashboard(ui: &mut egui::Ui, monitor: &MonitorData, accent: egui::Color32) {
+    let snapshot = &monitor.snapshot;
+
+    // CPU
+    ui.label(format!("CPU: {:.0}%", snapshot.cpu));
+    sparkline(ui, &monitor.cpu_history, Some(100.0), accent);
+    if !snapshot.cores.is_empty() {
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing.x = 2.0;
+            for (i, core) in snapshot.cores.iter().enumerate() {
+                let (rect, response) =
+                    ui.allocate_exact_size(egui::vec2(8.0, 20.0), egui::Sense::hover());
+                let painter = ui.painter_at(rect);
+                painter.rect_filled(rect, 1.0, ui.visuals().extreme_bg_color);
+                let mut filled = rect;
+                filled.set_top(rect.bottom() - rect.height() * (core / 100.0).clamp(0.0, 1.0));
+                painter.rect_filled(filled, 1.0, accent);
+                response.on_hover_text(trf("Ядро {}: {}%", &[&i, &format!("{:.0}", core)]));
+            }
+        });
+    }
+
+    // Память
+    if let Some(m) = snapshot.memory {
+        ui.add_space(5.0);
+        ui.label(trf(
+            "Память: {} / {}",
+            &[&format_bytes(m.used()), &format_bytes(m.total)],
+        ));
+        sparkline(
+            ui,
+            &monitor.memory_history,
+            Some(100.0),
+            egui::Color32::LIGHT_GREEN,
+        );
+        if m.swap_total > 0 {
+            usage_bar(
+                ui,
+                m.swap_used() as f32 / m.swap_total as f32,
+                format!(
+                    "Swap: {} / {}",
+                    format_bytes(m.swap_used()),
+                    format_bytes(m.swap_total)
+                ),
+            );
+        }
+    }
+
+    // Сеть
+    ui.add_space(5.0);
+    ui.label(trf(
+        "Сеть: ↓ {}  ↑ {}",
+        &[
+            &format_rate(snapshot.rx_rate),
+            &format_rate(snapshot.tx_rate),
+        ],
+    ));
+    sparkline(ui, &monitor.rx_history, None, egui::Color32::LIGHT_BLUE);
+    sparkline(
+        ui,
+        &monitor.tx_history,
+        None,
+        egui::Color32::from_rgb(255, 170, 90),
+    );
+
+    // Диски
+    if !snapshot.disks.is_empty() {
+        ui.add_space(5.0);
+        ui.label(tr("Диски:"));
+        for disk in &snapshot.disks {
+            usage_bar(
+                ui,
+                disk.percent() / 100.0,
+                format!(
+                    "{} — {} / {}",
+                    disk.mount.path,
+                    format_bytes(disk.used()),
+                    format_bytes(disk.total)
+                ),
+            );
+        }
+    }
+
+    // Датчики
+    if !snapshot.temperatures.is_empty() {
+        ui.add_space(5.0);
+        ui.label(tr("Температура:"));
+        for t in &snapshot.temperatures {
+            let color = if t.celsius >= 85.0 {
+                egui::Color32::LIGHT_RED
+            } else if t.celsius >= 70.0 {
+                egui::Color32::from_rgb(255, 200, 80)
+            } else {
+                ui.visuals().text_color()
+            };
+            ui.label(
+                egui::RichText::new(format!("{}: {:.0}°C", t.label, t.celsius))
+                    .color(color)
+                    .small(),
+            );
+        }
+    }
+
+    for b in &snapshot.batteries {
+        ui.add_space(5.0);
+        usage_bar(
+            ui,
+            b.capacity as f32 / 100.0,
+            format!("{}: {}% ({})", b.name, b.capacity, b.status),
+        );
+    }
+}
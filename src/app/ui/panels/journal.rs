@@ -0,0 +1,259 @@
+//! Панель системного журнала
+
+use super::super::super::commands::journal::{self, JournalEntry, PRIORITY_NAMES};
+use super::super::super::i18n::{tr, trf};
+use super::super::super::AssistantApp;
+use eframe::egui;
+use std::sync::Arc;
+
+/// Варианты фильтра по загрузке
+const BOOTS: [(Option<i32>, &str); 3] = [
+    (Some(0), "Текущая загрузка"),
+    (Some(-1), "Предыдущая"),
+    (None, "Все загрузки"),
+];
+
+/// Цвет записи по важности
+fn priority_color(priority: u8) -> egui::Color32 {
+    match priority {
+        0..=3 => egui::Color32::LIGHT_RED,
+        4 => egui::Color32::from_rgb(255, 200, 80),
+        5 => egui::Color32::LIGHT_BLUE,
+        _ => egui::Color32::GRAY,
+    }
+}
+
+/// Журнал с фильтрами, выбором записей и объяснением ошибок
+pub fn render(ui: &mut egui::Ui, app: &mut AssistantApp, accent: egui::Color32) {
+    render_filters(ui, app);
+    ui.separator();
+
+    let loaded = app.panels.journal.lock().ok().and_then(|j| j.clone());
+    let entries = match loaded {
+        None => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(egui::RichText::new(tr("Читаю журнал...")).color(accent));
+            });
+            return;
+        }
+        Some(Err(e)) => {
+            ui.label(egui::RichText::new(format!("[X] {}", e)).color(egui::Color32::LIGHT_RED));
+            return;
+        }
+        Some(Ok(entries)) => entries,
+    };
+
+    // Объяснение по команде "объясни ошибки" — как только записи загружены
+    if app.panels.journal_explain_on_load {
+        app.panels.journal_explain_on_load = false;
+        if !entries.is_empty() {
+            journal::request_explanation(
+                &entries,
+                Arc::clone(&app.ai),
+                &app.panels.journal_explanation,
+            );
+        }
+    }
+
+    render_actions(ui, app, &entries);
+    render_explanation(ui, app, accent);
+    ui.separator();
+
+    if entries.is_empty() {
+        ui.label(egui::RichText::new(tr("Записей не найдено")).weak());
+        return;
+    }
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .stick_to_bottom(true)
+        .show(ui, |ui| {
+            for (i, entry) in entries.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    let mut selected = app.panels.journal_selected.contains(&i);
+                    if ui.checkbox(&mut selected, "").changed() {
+                        if selected {
+                            app.panels.journal_selected.insert(i);
+                        } else {
+                            app.panels.journal_selected.remove(&i);
+                        }
+                    }
+
+                    ui.label(egui::RichText::new(entry.time()).monospace().weak());
+                    ui.label(egui::RichText::new(&entry.unit).strong())
+                        .on_hover_text(PRIORITY_NAMES[entry.priority.min(7) as usize]);
+                    ui.add(
+                        egui::Label::new(
+                            egui::RichText::new(&entry.message)
+                                .color(priority_color(entry.priority)),
+                        )
+                        .wrap(true),
+                    );
+                });
+            }
+        });
+}
+
+/// Строка фильтров
+fn render_filters(ui: &mut egui::Ui, app: &mut AssistantApp) {
+    let query = &mut app.panels.journal_query;
+    let mut apply = false;
+
+    ui.horizontal_wrapped(|ui| {
+        ui.label(tr("Сервис:"));
+        apply |= ui
+            .add(
+                egui::TextEdit::singleline(&mut query.unit)
+                    .hint_text(tr("все"))
+                    .desired_width(120.0),
+            )
+            .lost_focus();
+
+        ui.label(tr("Важность:"));
+        egui::ComboBox::from_id_source("journal_priority")
+            .selected_text(trf("до {}", &[&PRIORITY_NAMES[query.priority as usize]]))
+            .show_ui(ui, |ui| {
+                for (p, name) in PRIORITY_NAMES.iter().enumerate() {
+                    apply |= ui
+                        .selectable_value(&mut query.priority, p as u8, *name)
+                        .clicked();
+                }
+            });
+
+        let boot_label = BOOTS
+            .iter()
+            .find(|(b, _)| *b == query.boot)
+            .map(|(_, l)| *l)
+            .unwrap_or("?");
+        egui::ComboBox::from_id_source("journal_boot")
+            .selected_text(tr(boot_label))
+            .show_ui(ui, |ui| {
+                for (boot, label) in BOOTS {
+                    apply |= ui
+                        .selectable_value(&mut query.boot, boot, tr(label))
+                        .clicked();
+                }
+            });
+    });
+
+    ui.horizontal_wrapped(|ui| {
+        ui.label(tr("С:"));
+        apply |= ui
+            .add(
+                egui::TextEdit::singleline(&mut query.since)
+                    .hint_text("-1h, today, 2024-05-01 10:00")
+                    .desired_width(150.0),
+            )
+            .lost_focus();
+        ui.label(tr("По:"));
+        apply |= ui
+            .add(
+                egui::TextEdit::singleline(&mut query.until)
+                    .hint_text(tr("сейчас"))
+                    .desired_width(150.0),
+            )
+            .lost_focus();
+
+        if ui.button(tr("Обновить")).clicked() {
+            apply = true;
+        }
+    });
+
+    if apply {
+        app.panels.refresh_journal();
+    }
+}
+
+/// Счётчики и кнопки выбора/объяснения
+fn render_actions(ui: &mut egui::Ui, app: &mut AssistantApp, entries: &[JournalEntry]) {
+    let errors: Vec<usize> = (0..entries.len())
+        .filter(|i| entries[*i].is_error())
+        .collect();
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(trf("Записей: {}", &[&entries.len()])).weak());
+        if !errors.is_empty() {
+            ui.label(
+                egui::RichText::new(trf("Ошибок: {}", &[&errors.len()]))
+                    .color(egui::Color32::LIGHT_RED),
+            );
+        }
+
+        if ui.small_button(tr("Выбрать ошибки")).clicked() {
+            app.panels.journal_selected = errors.iter().copied().collect();
+        }
+        if !app.panels.journal_selected.is_empty() && ui.small_button(tr("Снять выбор")).clicked()
+        {
+            app.panels.journal_selected.clear();
+        }
+
+        // Без выбора объясняем все ошибки из списка
+        let selected: Vec<JournalEntry> = if app.panels.journal_selected.is_empty() {
+            errors.iter().map(|i| entries[*i].clone()).collect()
+        } else {
+            app.panels
+                .journal_selected
+                .iter()
+                .filter_map(|i| entries.get(*i).cloned())
+                .collect()
+        };
+
+        let pending = app
+            .panels
+            .journal_explanation
+            .lock()
+            .map(|e| e.as_deref() == Some(""))
+            .unwrap_or(false);
+
+        let button = ui
+            .add_enabled(
+                !selected.is_empty() && !pending,
+                egui::Button::new(trf("Объясни ошибки ({})", &[&selected.len()])),
+            )
+            .on_hover_text(tr(
+                "Отправить выбранные записи ИИ (без выбора — все ошибки)",
+            ));
+        if button.clicked() {
+            journal::request_explanation(
+                &selected,
+                Arc::clone(&app.ai),
+                &app.panels.journal_explanation,
+            );
+        }
+    });
+}
+
+/// Ответ ИИ по выбранным записям
+fn render_explanation(ui: &mut egui::Ui, app: &mut AssistantApp, accent: egui::Color32) {
+    let explanation = app
+        .panels
+        .journal_explanation
+        .lock()
+        .ok()
+        .and_then(|e| e.clone());
+
+    match explanation.as_deref() {
+        None => {}
+        Some("") => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(egui::RichText::new(tr("ИИ разбирает ошибки...")).color(accent));
+            });
+        }
+        Some(text) => {
+            egui::CollapsingHeader::new(
+                egui::RichText::new(tr("Объяснение")).strong().color(accent),
+            )
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::ScrollArea::vertical()
+                    .id_source("journal_explanation")
+                    .max_height(180.0)
+                    .show(ui, |ui| {
+                        ui.label(text);
+                    });
+            });
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Дополнительные панели (открываются из шапки или командой)
+
+pub mod journal;
+pub mod network;
+pub mod processes;
+pub mod services;
+
+use super::super::commands::journal::{self as journal_log, JournalQuery};
+use super::super::commands::journal::{SharedExplanation, SharedJournal};
+use super::super::commands::network::{self as network_cmd, SharedNetwork};
+use super::super::commands::processes::{ProcessTable, SortKey};
+use super::super::commands::services::{self as services_cmd, SharedUnits};
+use super::super::i18n::tr;
+use super::super::AssistantApp;
+use eframe::egui;
+use std::collections::BTreeSet;
+
+/// Доступные панели
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Panel {
+    Services,
+    Journal,
+    Network,
+    Processes,
+}
+
+impl Panel {
+    pub const ALL: &'static [Panel] = &[
+        Panel::Services,
+        Panel::Journal,
+        Panel::Network,
+        Panel::Processes,
+    ];
+
+    /// Идентификатор для команды открытия
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Services => "services",
+            Self::Journal => "journal",
+            Self::Network => "network",
+            Self::Processes => "processes",
+        }
+    }
+
+    /// Заголовок окна
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Services => tr("Сервисы systemd"),
+            Self::Journal => tr("Системный журнал"),
+            Self::Network => tr("Сеть и WiFi"),
+            Self::Processes => tr("Процессы"),
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|p| p.id() == id)
+    }
+}
+
+/// Состояние панелей
+#[derive(Default)]
+pub struct PanelState {
+    pub active: Option<Panel>,
+
+    // Сервисы
+    pub services: SharedUnits,
+    pub service_filter: String,
+    pub services_failed_only: bool,
+
+    // Журнал
+    pub journal: SharedJournal,
+    pub journal_query: JournalQuery,
+    /// Индексы выбранных записей
+    pub journal_selected: BTreeSet<usize>,
+    pub journal_explanation: SharedExplanation,
+    /// Объяснить ошибки сразу после загрузки
+    pub journal_explain_on_load: bool,
+
+    // Сеть
+    pub network: SharedNetwork,
+    pub wifi_selected: Option<String>,
+    pub wifi_password: String,
+    /// Сеть из команды "wifi подключить" — выбирается после сканирования
+    pub wifi_target: Option<String>,
+
+    // Процессы
+    pub processes: ProcessTable,
+    pub process_search: String,
+    pub process_sort: SortKey,
+    pub process_selected: Option<u32>,
+    /// Значение nice для кнопки "Применить"
+    pub process_nice: i32,
+}
+
+impl PanelState {
+    /// Открывает панель и загружает её данные.
+    /// `arg` — параметр из команды (для журнала: "юнит" или "юнит:explain")
+    pub fn open(&mut self, panel: Panel, arg: &str) {
+        self.active = Some(panel);
+        match panel {
+            Panel::Services => services_cmd::refresh_units(&self.services),
+            Panel::Journal => {
+                if let Some(unit) = arg.strip_suffix(":explain") {
+                    self.journal_query = JournalQuery::errors(unit, self.journal_query.limit);
+                    self.journal_explain_on_load = true;
+                } else if !arg.is_empty() {
+                    self.journal_query.unit = arg.to_string();
+                }
+                self.refresh_journal();
+            }
+            Panel::Network => {
+                self.wifi_target = (!arg.is_empty()).then(|| arg.to_string());
+                network_cmd::refresh(&self.network);
+            }
+            Panel::Processes => {
+                self.process_search = arg.to_string();
+                self.processes.sample();
+            }
+        }
+    }
+
+    /// Перечитывает журнал с текущими фильтрами
+    pub fn refresh_journal(&mut self) {
+        self.journal_selected.clear();
+        if let Ok(mut e) = self.journal_explanation.lock() {
+            *e = None;
+        }
+        journal_log::refresh(&self.journal, self.journal_query.clone());
+    }
+}
+
+/// Отрисовка активной панели
+pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32) {
+    let Some(panel) = app.panels.active else {
+        return;
+    };
+
+    let mut open = true;
+    egui::Window::new(panel.title())
+        .id(egui::Id::new("panel_window"))
+        .open(&mut open)
+        .default_size([640.0, 480.0])
+        .show(ctx, |ui| match panel {
+            Panel::Services => services::render(ui, app, accent),
+            Panel::Journal => journal::render(ui, app, accent),
+            Panel::Network => network::render(ui, app, accent),
+            Panel::Processes => processes::render(ui, app, accent),
+        });
+
+    if !open {
+        app.panels.active = None;
+    }
+}
@@ -0,0 +1,199 @@
+//! Панель сети и WiFi
+
+use super::super::super::commands::network::{self, NetworkStatus, WifiNetwork};
+use super::super::super::i18n::{tr, trf};
+use super::super::super::AssistantApp;
+use eframe::egui;
+
+/// Статус подключения, режим полёта и список сетей
+pub fn render(ui: &mut egui::Ui, app: &mut AssistantApp, accent: egui::Color32) {
+    let (backend, status, networks, busy, message) = match app.panels.network.lock() {
+        Ok(v) => (
+            v.backend,
+            v.status.clone(),
+            v.networks.clone(),
+            v.busy,
+            v.message.clone(),
+        ),
+        Err(_) => return,
+    };
+
+    // Сеть, запрошенная командой "wifi подключить <сеть>" (имя в нижнем регистре)
+    if let Some(target) = app.panels.wifi_target.clone() {
+        if let Some(found) = network::find_network(&networks, &target) {
+            app.panels.wifi_selected = Some(found.ssid.clone());
+            app.panels.wifi_target = None;
+        } else if !busy && !networks.is_empty() {
+            app.panels.wifi_target = None;
+        }
+    }
+
+    match &status {
+        None => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(egui::RichText::new(tr("Получаю состояние сети...")).color(accent));
+            });
+        }
+        Some(Err(e)) => {
+            ui.label(egui::RichText::new(format!("[X] {}", e)).color(egui::Color32::LIGHT_RED));
+        }
+        Some(Ok(status)) => render_status(ui, app, status, busy),
+    }
+    if !backend.is_empty() {
+        ui.label(egui::RichText::new(trf("Управление через {}", &[&backend])).weak());
+    }
+
+    if let Some(message) = message {
+        let color = if message.starts_with("[X]") {
+            egui::Color32::LIGHT_RED
+        } else {
+            egui::Color32::LIGHT_GREEN
+        };
+        ui.label(egui::RichText::new(message).color(color));
+    }
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(tr("Доступные сети")).strong());
+        if ui
+            .add_enabled(!busy, egui::Button::new(tr("Сканировать")))
+            .clicked()
+        {
+            network::refresh(&app.panels.network);
+        }
+        if busy {
+            ui.spinner();
+        }
+    });
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            for net in &networks {
+                render_network(ui, app, net, busy);
+            }
+            if networks.is_empty() && !busy {
+                ui.label(egui::RichText::new(tr("Сети не найдены")).weak());
+            }
+        });
+}
+
+fn render_status(ui: &mut egui::Ui, app: &mut AssistantApp, status: &NetworkStatus, busy: bool) {
+    egui::Grid::new("network_status")
+        .num_columns(2)
+        .spacing([20.0, 4.0])
+        .show(ui, |ui| {
+            ui.label(tr("Подключение:"));
+            match &status.connection {
+                Some(c) => ui.label(
+                    egui::RichText::new(format!("{} ({})", c, status.device))
+                        .color(egui::Color32::LIGHT_GREEN),
+                ),
+                None => ui.label(egui::RichText::new(tr("нет")).color(egui::Color32::GRAY)),
+            };
+            ui.end_row();
+
+            if !status.addresses.is_empty() {
+                ui.label("IP:");
+                ui.label(status.addresses.join(", "));
+                ui.end_row();
+            }
+            if let Some(gw) = &status.gateway {
+                ui.label(tr("Шлюз:"));
+                ui.label(gw);
+                ui.end_row();
+            }
+            if !status.dns.is_empty() {
+                ui.label("DNS:");
+                ui.label(status.dns.join(", "));
+                ui.end_row();
+            }
+        });
+
+    ui.horizontal(|ui| {
+        let mut airplane = status.airplane;
+        if ui
+            .add_enabled(
+                !busy,
+                egui::Checkbox::new(&mut airplane, tr("Режим полёта")),
+            )
+            .changed()
+        {
+            network::set_airplane(&app.panels.network, airplane);
+        }
+
+        if status.connection.is_some()
+            && ui
+                .add_enabled(!busy, egui::Button::new(tr("Отключиться")))
+                .clicked()
+        {
+            network::disconnect(&app.panels.network);
+        }
+    });
+}
+
+fn render_network(ui: &mut egui::Ui, app: &mut AssistantApp, net: &WifiNetwork, busy: bool) {
+    let selected = app.panels.wifi_selected.as_deref() == Some(net.ssid.as_str());
+
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::ProgressBar::new(net.signal as f32 / 100.0)
+                .desired_width(50.0)
+                .text(format!("{}%", net.signal)),
+        );
+
+        let mut name = egui::RichText::new(&net.ssid);
+        if net.in_use {
+            name = name.strong().color(egui::Color32::LIGHT_GREEN);
+        }
+        if ui.selectable_label(selected, name).clicked() {
+            app.panels.wifi_selected = if selected {
+                None
+            } else {
+                Some(net.ssid.clone())
+            };
+            app.panels.wifi_password.clear();
+        }
+
+        ui.label(
+            egui::RichText::new(if net.is_open() {
+                tr("открытая")
+            } else {
+                &net.security
+            })
+            .weak(),
+        );
+    });
+
+    if !selected || net.in_use {
+        return;
+    }
+
+    ui.indent(("wifi_connect", &net.ssid), |ui| {
+        ui.horizontal(|ui| {
+            let mut submit = false;
+            if !net.is_open() {
+                ui.label(tr("Пароль:"));
+                submit |= ui
+                    .add(
+                        egui::TextEdit::singleline(&mut app.panels.wifi_password)
+                            .password(true)
+                            .desired_width(180.0),
+                    )
+                    .lost_focus()
+                    && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            }
+            submit |= ui
+                .add_enabled(!busy, egui::Button::new(tr("Подключиться")))
+                .clicked();
+
+            if submit && !busy {
+                let password = (!net.is_open())
+                    .then(|| std::mem::take(&mut app.panels.wifi_password))
+                    .filter(|p| !p.is_empty());
+                network::connect(&app.panels.network, net.ssid.clone(), password);
+            }
+        });
+    });
+}
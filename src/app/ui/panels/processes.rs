@@ -0,0 +1,125 @@
+//! Панель процессов
+
+use super::super::super::commands::processes::{self, ProcessAction, ProcessInfo, SortKey};
+use super::super::super::constants::PROCESS_REFRESH_MS;
+use super::super::super::i18n::{tr, trf};
+use super::super::super::monitor::format_bytes;
+use super::super::super::AssistantApp;
+use eframe::egui;
+use std::time::Duration;
+
+/// Таблица процессов с поиском, сортировкой и действиями
+pub fn render(ui: &mut egui::Ui, app: &mut AssistantApp, accent: egui::Color32) {
+    app.panels.processes.sample_if_due();
+    ui.ctx()
+        .request_repaint_after(Duration::from_millis(PROCESS_REFRESH_MS));
+
+    ui.horizontal(|ui| {
+        ui.label(tr("Поиск:"));
+        ui.add(
+            egui::TextEdit::singleline(&mut app.panels.process_search)
+                .hint_text(tr("имя, PID, пользователь..."))
+                .desired_width(180.0),
+        );
+        ui.label(tr("Сортировка:"));
+        for key in SortKey::ALL {
+            ui.selectable_value(&mut app.panels.process_sort, key, key.label());
+        }
+    });
+
+    let rows: Vec<ProcessInfo> = app
+        .panels
+        .processes
+        .filtered(&app.panels.process_search, app.panels.process_sort)
+        .into_iter()
+        .cloned()
+        .collect();
+
+    ui.label(
+        egui::RichText::new(trf(
+            "Показано: {} из {}",
+            &[&rows.len(), &app.panels.processes.processes.len()],
+        ))
+        .weak(),
+    );
+
+    render_actions(ui, app, &rows);
+    ui.separator();
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            egui::Grid::new("processes_grid")
+                .num_columns(6)
+                .striped(true)
+                .show(ui, |ui| {
+                    for title in [
+                        "PID",
+                        tr("Имя"),
+                        tr("Пользователь"),
+                        "CPU",
+                        tr("Память"),
+                        tr("Команда"),
+                    ] {
+                        ui.label(egui::RichText::new(title).strong());
+                    }
+                    ui.end_row();
+
+                    for p in &rows {
+                        let selected = app.panels.process_selected == Some(p.pid);
+                        if ui.selectable_label(selected, p.pid.to_string()).clicked() {
+                            app.panels.process_selected = (!selected).then_some(p.pid);
+                            app.panels.process_nice = p.nice;
+                        }
+                        ui.label(&p.name);
+                        ui.label(egui::RichText::new(&p.user).weak());
+                        let cpu = egui::RichText::new(format!("{:.1}%", p.cpu));
+                        ui.label(if p.cpu >= 50.0 {
+                            cpu.color(accent)
+                        } else {
+                            cpu
+                        });
+                        ui.label(format_bytes(p.rss));
+                        ui.add(
+                            egui::Label::new(egui::RichText::new(&p.cmdline).small())
+                                .truncate(true),
+                        )
+                        .on_hover_text(&p.cmdline);
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+/// Кнопки действий для выбранного процесса
+fn render_actions(ui: &mut egui::Ui, app: &mut AssistantApp, rows: &[ProcessInfo]) {
+    let Some(p) = app
+        .panels
+        .process_selected
+        .and_then(|pid| rows.iter().find(|p| p.pid == pid))
+    else {
+        ui.label(egui::RichText::new(tr("Выберите процесс по PID для действий")).weak());
+        return;
+    };
+
+    let mut requested = None;
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(format!("{} ({}):", p.name, p.pid)).strong());
+        if ui.button(tr("Завершить")).clicked() {
+            requested = Some(ProcessAction::Terminate);
+        }
+        if ui.button(tr("Убить")).clicked() {
+            requested = Some(ProcessAction::Kill);
+        }
+        ui.separator();
+        ui.label("nice:");
+        ui.add(egui::DragValue::new(&mut app.panels.process_nice).clamp_range(-20..=19));
+        if ui.button(tr("Применить")).clicked() {
+            requested = Some(ProcessAction::Renice(app.panels.process_nice));
+        }
+    });
+
+    if let Some(action) = requested {
+        processes::show_action_dialog(&mut app.dialog, action, &[(p.pid, p.name.clone())]);
+    }
+}
@@ -0,0 +1,122 @@
+//! Панель сервисов systemd
+
+use super::super::super::commands::services::{self, ServiceAction};
+use super::super::super::i18n::{tr, trf};
+use super::super::super::AssistantApp;
+use eframe::egui;
+
+/// Таблица сервисов с фильтром и действиями
+pub fn render(ui: &mut egui::Ui, app: &mut AssistantApp, accent: egui::Color32) {
+    ui.horizontal(|ui| {
+        ui.label(tr("Фильтр:"));
+        ui.add(
+            egui::TextEdit::singleline(&mut app.panels.service_filter)
+                .hint_text("sshd, bluetooth...")
+                .desired_width(180.0),
+        );
+        ui.checkbox(
+            &mut app.panels.services_failed_only,
+            tr("Только с ошибками"),
+        );
+        if ui.button(tr("Обновить")).clicked() {
+            services::refresh_units(&app.panels.services);
+        }
+    });
+
+    let units = app
+        .panels
+        .services
+        .lock()
+        .map(|u| u.clone())
+        .unwrap_or_default();
+    let failed = units.iter().filter(|u| u.is_failed()).count();
+
+    ui.horizontal(|ui| {
+        ui.label(egui::RichText::new(trf("Всего: {}", &[&units.len()])).weak());
+        if failed > 0 {
+            ui.label(
+                egui::RichText::new(trf("С ошибками: {}", &[&failed]))
+                    .color(egui::Color32::LIGHT_RED)
+                    .strong(),
+            );
+        }
+    });
+    ui.separator();
+
+    let filter = app.panels.service_filter.to_lowercase();
+    let mut requested: Option<(ServiceAction, String)> = None;
+
+    egui::ScrollArea::vertical()
+        .auto_shrink([false; 2])
+        .show(ui, |ui| {
+            egui::Grid::new("services_grid")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new(tr("Сервис")).strong());
+                    ui.label(egui::RichText::new(tr("Состояние")).strong());
+                    ui.label(egui::RichText::new(tr("Автозапуск")).strong());
+                    ui.label(egui::RichText::new(tr("Действия")).strong());
+                    ui.end_row();
+
+                    for unit in units.iter().filter(|u| {
+                        (!app.panels.services_failed_only || u.is_failed())
+                            && (filter.is_empty()
+                                || u.name.to_lowercase().contains(&filter)
+                                || u.description.to_lowercase().contains(&filter))
+                    }) {
+                        let state_color = if unit.is_failed() {
+                            egui::Color32::LIGHT_RED
+                        } else if unit.is_active() {
+                            egui::Color32::LIGHT_GREEN
+                        } else {
+                            egui::Color32::GRAY
+                        };
+
+                        let mut name = egui::RichText::new(&unit.name);
+                        if unit.is_failed() {
+                            name = name.color(egui::Color32::LIGHT_RED).strong();
+                        }
+                        ui.label(name)
+                            .on_hover_text(format!("{}\nload: {}", unit.description, unit.load));
+                        ui.label(
+                            egui::RichText::new(format!("{}/{}", unit.active, unit.sub))
+                                .color(state_color),
+                        );
+                        ui.label(&unit.enabled);
+
+                        ui.horizontal(|ui| {
+                            let mut action = |ui: &mut egui::Ui, label: &str, a: ServiceAction| {
+                                if ui.small_button(label).on_hover_text(a.title()).clicked() {
+                                    requested = Some((a, unit.name.clone()));
+                                }
+                            };
+
+                            if unit.is_active() {
+                                action(ui, "■", ServiceAction::Stop);
+                                action(ui, "↻", ServiceAction::Restart);
+                            } else {
+                                action(ui, "▶", ServiceAction::Start);
+                            }
+                            match unit.enabled.as_str() {
+                                "enabled" => action(ui, tr("автозапуск −"), ServiceAction::Disable),
+                                "disabled" => action(ui, tr("автозапуск +"), ServiceAction::Enable),
+                                _ => {}
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some((action, unit)) = requested {
+        services::show_action_dialog(&mut app.dialog, action, &unit);
+    }
+
+    if units.is_empty() {
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.label(egui::RichText::new(tr("Загрузка списка...")).color(accent));
+        });
+    }
+}
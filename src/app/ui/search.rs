@@ -0,0 +1,230 @@
+//! Окно поиска по истории чатов (Ctrl+F)
+
+use super::super::chat::ChatMessage;
+use super::super::i18n::{tr, trf};
+use super::super::search::{self, Period, SearchHit, SenderFilter};
+use super::super::AssistantApp;
+use eframe::egui;
+use std::time::{Duration, Instant};
+
+/// Сколько подсвечивается сообщение, к которому перешли из поиска
+const FOCUS_HIGHLIGHT: Duration = Duration::from_secs(3);
+
+/// Как часто проверять, готов ли индекс
+const INDEXING_POLL: Duration = Duration::from_millis(100);
+
+/// Состояние окна поиска
+#[derive(Default)]
+pub struct SearchState {
+    pub open: bool,
+    pub query: String,
+    pub sender: SenderFilter,
+    pub period: Period,
+    results: Vec<SearchHit>,
+    /// Запрос изменился — результаты нужно обновить
+    dirty: bool,
+    /// Индекс ещё строится — поиск повторяется на следующих кадрах
+    indexing: bool,
+    /// Сообщение, к которому нужно прокрутить чат
+    pub focus: Option<ChatFocus>,
+}
+
+impl SearchState {
+    /// Открывает окно и ставит фокус в поле запроса
+    pub fn show(&mut self) {
+        self.open = true;
+        self.dirty = true;
+    }
+}
+
+/// Сообщение, выбранное в результатах поиска
+pub struct ChatFocus {
+    timestamp: i64,
+    text: String,
+    since: Instant,
+    scrolled: bool,
+}
+
+impl ChatFocus {
+    /// Это сообщение выбрано в поиске
+    pub fn matches(&self, msg: &ChatMessage) -> bool {
+        msg.timestamp.timestamp() == self.timestamp && msg.text == self.text
+    }
+
+    /// Прокрутить к сообщению нужно только один раз
+    pub fn take_scroll(&mut self) -> bool {
+        !std::mem::replace(&mut self.scrolled, true)
+    }
+
+    /// Подсветка ещё не погасла
+    pub fn is_active(&self) -> bool {
+        self.since.elapsed() < FOCUS_HIGHLIGHT
+    }
+}
+
+/// Окно поиска
+pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32) {
+    let mut open = app.search.open;
+    let mut selected = None;
+
+    egui::Window::new(tr("Поиск по истории"))
+        .open(&mut open)
+        .default_width(480.0)
+        .default_height(420.0)
+        .show(ctx, |ui| {
+            let state = &mut app.search;
+
+            let input = ui.add(
+                egui::TextEdit::singleline(&mut state.query)
+                    .hint_text(tr("Что найти? Например: bluetooth"))
+                    .desired_width(f32::INFINITY),
+            );
+            if state.dirty && !input.has_focus() {
+                input.request_focus();
+            }
+
+            let mut changed = input.changed();
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_source("search_sender")
+                    .selected_text(tr(state.sender.label()))
+                    .show_ui(ui, |ui| {
+                        for filter in SenderFilter::ALL {
+                            changed |= ui
+                                .selectable_value(&mut state.sender, *filter, tr(filter.label()))
+                                .changed();
+                        }
+                    });
+                egui::ComboBox::from_id_source("search_period")
+                    .selected_text(tr(state.period.label()))
+                    .show_ui(ui, |ui| {
+                        for period in Period::ALL {
+                            changed |= ui
+                                .selectable_value(&mut state.period, *period, tr(period.label()))
+                                .changed();
+                        }
+                    });
+            });
+
+            if changed || state.dirty || state.indexing {
+                match search::search(&state.query, state.sender, state.period) {
+                    Some(results) => {
+                        state.results = results;
+                        state.indexing = false;
+                    }
+                    None => {
+                        state.indexing = true;
+                        ui.ctx().request_repaint_after(INDEXING_POLL);
+                    }
+                }
+                state.dirty = false;
+            }
+
+            ui.separator();
+            if state.query.trim().is_empty() {
+                ui.label(egui::RichText::new(tr("Введите запрос")).weak());
+                return;
+            }
+            if state.indexing {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(egui::RichText::new(tr("Индексация истории…")).weak());
+                });
+                return;
+            }
+            if state.results.is_empty() {
+                ui.label(egui::RichText::new(tr("Ничего не найдено")).weak());
+                return;
+            }
+            ui.label(
+                egui::RichText::new(trf("Найдено: {}", &[&state.results.len()]))
+                    .weak()
+                    .small(),
+            );
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    for hit in &state.results {
+                        if render_hit(ui, hit, &state.query, accent) {
+                            selected = Some(hit.clone());
+                        }
+                        ui.separator();
+                    }
+                });
+        });
+
+    app.search.open = open;
+
+    // Переход к сообщению в его сессии
+    if let Some(hit) = selected {
+        app.switch_session(&hit.session);
+        app.search.focus = Some(ChatFocus {
+            timestamp: hit.timestamp.timestamp(),
+            text: hit.text,
+            since: Instant::now(),
+            scrolled: false,
+        });
+    }
+}
+
+/// Один результат; возвращает true при клике
+fn render_hit(ui: &mut egui::Ui, hit: &SearchHit, query: &str, accent: egui::Color32) -> bool {
+    let response = ui
+        .vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new(&hit.session_title).strong());
+                ui.label(
+                    egui::RichText::new(format!(
+                        "{} · {}",
+                        tr(&hit.sender),
+                        hit.timestamp.format("%d.%m.%Y %H:%M")
+                    ))
+                    .weak()
+                    .small(),
+                );
+            });
+            ui.add(
+                egui::Label::new(highlighted(
+                    ui,
+                    &search::snippet(&hit.text, query),
+                    query,
+                    accent,
+                ))
+                .wrap(true),
+            );
+        })
+        .response
+        .interact(egui::Sense::click())
+        .on_hover_cursor(egui::CursorIcon::PointingHand)
+        .on_hover_text(tr("Открыть в чате"));
+    response.clicked()
+}
+
+/// Текст с подсветкой совпадений
+fn highlighted(
+    ui: &egui::Ui,
+    text: &str,
+    query: &str,
+    accent: egui::Color32,
+) -> egui::text::LayoutJob {
+    let normal = egui::TextFormat {
+        font_id: egui::TextStyle::Body.resolve(ui.style()),
+        color: ui.visuals().text_color(),
+        ..Default::default()
+    };
+    let marked = egui::TextFormat {
+        background: accent.gamma_multiply(0.4),
+        color: egui::Color32::WHITE,
+        ..normal.clone()
+    };
+
+    let mut job = egui::text::LayoutJob::default();
+    let mut pos = 0;
+    for range in search::highlight_ranges(text, query) {
+        job.append(&text[pos..range.start], 0.0, normal.clone());
+        job.append(&text[range.clone()], 0.0, marked.clone());
+        pos = range.end;
+    }
+    job.append(&text[pos..], 0.0, normal);
+    job
+}
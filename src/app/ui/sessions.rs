@@ -0,0 +1,143 @@
+//! Боковая панель сессий чата: список, создание, переименование, удаление
+
+use super::super::constants::SESSIONS_PANEL_WIDTH;
+use super::super::export::ExportFormat;
+use super::super::i18n::tr;
+use super::super::sessions::{self, SessionInfo};
+use super::super::AssistantApp;
+use eframe::egui;
+
+/// Действие над сессией, выбранное в списке (выполняется после отрисовки)
+enum SessionAction {
+    Open(String),
+    StartRename(String),
+    Rename(String, String),
+    Export(String),
+    Delete(String),
+}
+
+/// Левая панель со списком сессий
+pub fn render(ctx: &egui::Context, app: &mut AssistantApp, accent: egui::Color32) {
+    egui::SidePanel::left("sessions")
+        .default_width(SESSIONS_PANEL_WIDTH)
+        .show(ctx, |ui| {
+            ui.add_space(20.0);
+            ui.horizontal(|ui| {
+                ui.heading(tr("Чаты"));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .button(egui::RichText::new(tr("+ Новый")).color(accent))
+                        .on_hover_text(tr("Новый чат (Ctrl+N)"))
+                        .clicked()
+                    {
+                        app.new_session();
+                    }
+                });
+            });
+            ui.separator();
+
+            let current = app.chat.session_id().map(str::to_string);
+            let mut action = None;
+
+            egui::ScrollArea::vertical()
+                .auto_shrink([false; 2])
+                .show(ui, |ui| {
+                    for info in sessions::list() {
+                        let editing = app
+                            .session_rename
+                            .as_ref()
+                            .is_some_and(|(id, _)| *id == info.id);
+                        let item_action = if editing {
+                            render_rename(ui, app)
+                        } else {
+                            render_item(ui, &info, current.as_deref(), accent)
+                        };
+                        if item_action.is_some() {
+                            action = item_action;
+                        }
+                    }
+                });
+
+            match action {
+                Some(SessionAction::Open(id)) => app.switch_session(&id),
+                Some(SessionAction::StartRename(id)) => {
+                    // Редактирование начинается с текущего названия
+                    let title = sessions::list()
+                        .into_iter()
+                        .find(|s| s.id == id)
+                        .map(|s| s.title)
+                        .unwrap_or_default();
+                    app.session_rename = Some((id, title));
+                }
+                Some(SessionAction::Rename(id, title)) => sessions::rename(&id, &title),
+                Some(SessionAction::Export(id)) => {
+                    app.switch_session(&id);
+                    app.export.show(ExportFormat::default());
+                }
+                Some(SessionAction::Delete(id)) => app.delete_session(&id),
+                None => {}
+            }
+        });
+}
+
+/// Строка списка: название и дата; меню по правой кнопке
+fn render_item(
+    ui: &mut egui::Ui,
+    info: &SessionInfo,
+    current: Option<&str>,
+    accent: egui::Color32,
+) -> Option<SessionAction> {
+    let selected = current == Some(info.id.as_str());
+    let title = egui::RichText::new(tr(info.display_title()));
+    let title = if selected { title.color(accent) } else { title };
+    let response = ui
+        .add(egui::SelectableLabel::new(selected, title))
+        .on_hover_text(tr("Двойной щелчок — переименовать, ПКМ — меню"));
+    ui.label(egui::RichText::new(info.updated_label()).weak().small());
+    ui.add_space(4.0);
+
+    let mut action = None;
+    if response.double_clicked() {
+        action = Some(SessionAction::StartRename(info.id.clone()));
+    } else if response.clicked() {
+        action = Some(SessionAction::Open(info.id.clone()));
+    }
+    response.context_menu(|ui| {
+        if ui.button(tr("Переименовать")).clicked() {
+            action = Some(SessionAction::StartRename(info.id.clone()));
+            ui.close_menu();
+        }
+        if ui.button(tr("Экспорт...")).clicked() {
+            action = Some(SessionAction::Export(info.id.clone()));
+            ui.close_menu();
+        }
+        if ui.button(tr("Удалить")).clicked() {
+            action = Some(SessionAction::Delete(info.id.clone()));
+            ui.close_menu();
+        }
+    });
+    action
+}
+
+/// Поле переименования: Enter сохраняет, Esc отменяет
+fn render_rename(ui: &mut egui::Ui, app: &mut AssistantApp) -> Option<SessionAction> {
+    let (id, title) = app.session_rename.as_mut()?;
+    let response = ui.add(
+        egui::TextEdit::singleline(title)
+            .hint_text(tr(sessions::UNTITLED))
+            .desired_width(f32::INFINITY),
+    );
+    if !response.has_focus() && !response.lost_focus() {
+        response.request_focus();
+    }
+    ui.add_space(4.0);
+
+    if !response.lost_focus() {
+        return None;
+    }
+    let action = ui
+        .input(|i| !i.key_pressed(egui::Key::Escape))
+        .then(|| SessionAction::Rename(id.clone(), title.clone()));
+    app.session_rename = None;
+    action
+}
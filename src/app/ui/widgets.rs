@@ -1,11 +1,34 @@
 //! Кастомные виджеты
 
 use super::super::chat::ChatMessage;
+use super::super::constants::USER_SENDER;
+use super::super::i18n::tr;
+use super::super::markup::{self, Block, Line, SpanStyle};
 use eframe::egui;
 
+const TEXT_SIZE: f32 = 15.0;
+const TEXT_COLOR: egui::Color32 = egui::Color32::from_gray(230);
+
+/// Действие, выбранное в пузыре сообщения
+pub enum MessageAction {
+    /// Выполнить блок команд (после подтверждения)
+    Run(String),
+    /// Новый вариант ответа ИИ
+    Regenerate(u64),
+    /// Изменить сообщение пользователя
+    Edit(u64),
+    /// Показать другой вариант сообщения
+    SwitchVariant(u64, usize),
+}
+
 /// Пузырь сообщения в чате
-pub fn render_message(ui: &mut egui::Ui, msg: &ChatMessage, accent: egui::Color32) {
-    let is_user = msg.sender == "Вы";
+pub fn render_message(
+    ui: &mut egui::Ui,
+    msg: &ChatMessage,
+    accent: egui::Color32,
+) -> Option<MessageAction> {
+    let is_user = msg.sender == USER_SENDER;
+    let mut action = None;
 
     // Цвета
     let (bg, border, name_color) = if is_user {
@@ -48,45 +71,217 @@ pub fn render_message(ui: &mut egui::Ui, msg: &ChatMessage, accent: egui::Color3
             .inner_margin(12.0)
             .show(ui, |ui| {
                 ui.set_max_width(max_bubble_width);
+                ui.with_layout(egui::Layout::top_down(egui::Align::LEFT), |ui| {
+                    // Заголовок: имя + время
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(tr(&msg.sender))
+                                .strong()
+                                .color(name_color)
+                                .size(12.0),
+                        );
+                        ui.label(egui::RichText::new(" · ").weak().size(10.0));
+                        ui.label(
+                            egui::RichText::new(msg.timestamp.format("%H:%M").to_string())
+                                .color(egui::Color32::GRAY)
+                                .size(10.0),
+                        );
+                        if let Some(a) = render_controls(ui, msg, is_user) {
+                            action = Some(a);
+                        }
+                    });
 
-                // Заголовок: имя + время
-                ui.horizontal(|ui| {
-                    ui.label(
-                        egui::RichText::new(&msg.sender)
-                            .strong()
-                            .color(name_color)
-                            .size(12.0),
-                    );
-                    ui.label(
-                        egui::RichText::new(" · ")
-                            .weak()
-                            .size(10.0),
-                    );
+                    ui.add_space(2.0);
+
+                    // Сообщения пользователя показываем как есть
+                    if is_user {
+                        render_text_line(ui, msg, &msg.text, TEXT_SIZE);
+                        return;
+                    }
+
+                    for block in markup::blocks(&msg.text) {
+                        match block {
+                            Block::Text(text) => render_text(ui, msg, &text),
+                            Block::Code { .. } => {
+                                if let Some(a) = render_code(ui, &block) {
+                                    action = Some(a);
+                                }
+                            }
+                        }
+                    }
+                });
+            });
+    });
+
+    action
+}
+
+/// Кнопки «изменить»/«заново» и переключатель вариантов ‹ 1/3 ›
+fn render_controls(ui: &mut egui::Ui, msg: &ChatMessage, is_user: bool) -> Option<MessageAction> {
+    let mut action = None;
+    let count = msg.variants.len();
+
+    if count > 1 {
+        ui.add_space(6.0);
+        if ui
+            .add_enabled(msg.variant > 0, egui::Button::new("‹").small())
+            .clicked()
+        {
+            action = Some(MessageAction::SwitchVariant(msg.id, msg.variant - 1));
+        }
+        ui.label(
+            egui::RichText::new(format!("{}/{}", msg.variant + 1, count))
+                .color(egui::Color32::GRAY)
+                .size(10.0),
+        );
+        if ui
+            .add_enabled(msg.variant + 1 < count, egui::Button::new("›").small())
+            .clicked()
+        {
+            action = Some(MessageAction::SwitchVariant(msg.id, msg.variant + 1));
+        }
+    }
+
+    ui.add_space(6.0);
+    if is_user {
+        if ui
+            .small_button(tr("изменить"))
+            .on_hover_text(tr("Изменить вопрос и получить новый ответ"))
+            .clicked()
+        {
+            action = Some(MessageAction::Edit(msg.id));
+        }
+    } else if msg.generated
+        && ui
+            .small_button(tr("заново"))
+            .on_hover_text(tr("Сгенерировать другой вариант ответа"))
+            .clicked()
+    {
+        action = Some(MessageAction::Regenerate(msg.id));
+    }
+
+    action
+}
+
+/// Текстовый блок: заголовки, списки и абзацы
+fn render_text(ui: &mut egui::Ui, msg: &ChatMessage, text: &str) {
+    for line in text.lines() {
+        match markup::line(line) {
+            Line::Heading(level, text) => {
+                ui.add_space(4.0);
+                let size = match level {
+                    1 => 20.0,
+                    2 => 18.0,
+                    _ => 16.0,
+                };
+                render_text_line(ui, msg, text, size);
+            }
+            Line::Bullet(text) => {
+                ui.horizontal_top(|ui| {
+                    ui.label(egui::RichText::new(" •").color(TEXT_COLOR).size(TEXT_SIZE));
+                    render_text_line(ui, msg, text, TEXT_SIZE);
+                });
+            }
+            Line::Numbered(number, text) => {
+                ui.horizontal_top(|ui| {
                     ui.label(
-                        egui::RichText::new(msg.timestamp.format("%H:%M").to_string())
-                            .color(egui::Color32::GRAY)
-                            .size(10.0),
+                        egui::RichText::new(number)
+                            .color(TEXT_COLOR)
+                            .size(TEXT_SIZE),
                     );
+                    render_text_line(ui, msg, text, TEXT_SIZE);
                 });
+            }
+            Line::Plain(text) => render_text_line(ui, msg, text, TEXT_SIZE),
+            Line::Blank => ui.add_space(6.0),
+        }
+    }
+}
 
-                ui.add_space(2.0);
-
-                // Текст сообщения с переносом
-                let text_response = ui.add(
-                    egui::Label::new(
-                        egui::RichText::new(&msg.text)
-                            .color(egui::Color32::WHITE)
-                            .size(15.0),
-                    )
-                    .wrap(true)
-                    .sense(egui::Sense::click()),
-                );
+/// Строка с жирным текстом и `кодом`; клик копирует всё сообщение
+fn render_text_line(ui: &mut egui::Ui, msg: &ChatMessage, text: &str, size: f32) {
+    let bold = size > TEXT_SIZE;
+    let mut job = egui::text::LayoutJob::default();
+    for (span, style) in markup::spans(text) {
+        let format = match style {
+            SpanStyle::Normal => egui::TextFormat {
+                font_id: egui::FontId::proportional(size),
+                color: if bold {
+                    egui::Color32::WHITE
+                } else {
+                    TEXT_COLOR
+                },
+                ..Default::default()
+            },
+            // Жирного начертания в шрифте нет — выделяем цветом
+            SpanStyle::Bold => egui::TextFormat {
+                font_id: egui::FontId::proportional(size),
+                color: egui::Color32::WHITE,
+                ..Default::default()
+            },
+            SpanStyle::Code => egui::TextFormat {
+                font_id: egui::FontId::monospace(size - 1.0),
+                color: egui::Color32::from_rgb(255, 200, 120),
+                background: egui::Color32::from_gray(25),
+                ..Default::default()
+            },
+        };
+        job.append(span, 0.0, format);
+    }
+
+    let response = ui.add(egui::Label::new(job).wrap(true).sense(egui::Sense::click()));
 
-                // Копирование по клику
-                if text_response.clicked() {
-                    ui.output_mut(|o| o.copied_text = msg.text.clone());
-                }
-                text_response.on_hover_text("Нажмите чтобы скопировать");
+    // Копирование по клику
+    if response.clicked() {
+        ui.output_mut(|o| o.copied_text = msg.text.clone());
+    }
+    response.on_hover_text(tr("Нажмите чтобы скопировать"));
+}
+
+/// Блок кода с кнопками «Копировать» и «Выполнить»
+fn render_code(ui: &mut egui::Ui, block: &Block) -> Option<MessageAction> {
+    let Block::Code { lang, code } = block else {
+        return None;
+    };
+    let mut action = None;
+
+    ui.add_space(4.0);
+    egui::Frame::none()
+        .fill(egui::Color32::from_gray(22))
+        .rounding(6.0)
+        .inner_margin(8.0)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(
+                    egui::RichText::new(if lang.is_empty() { tr("код") } else { lang })
+                        .weak()
+                        .size(10.0),
+                );
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if block.is_shell()
+                        && ui
+                            .small_button(tr("Выполнить"))
+                            .on_hover_text(tr("Запустить в терминале после подтверждения"))
+                            .clicked()
+                    {
+                        action = Some(MessageAction::Run(code.clone()));
+                    }
+                    if ui.small_button(tr("Копировать")).clicked() {
+                        ui.output_mut(|o| o.copied_text = code.clone());
+                    }
+                });
             });
-    });
+            ui.add(
+                egui::Label::new(
+                    egui::RichText::new(code)
+                        .monospace()
+                        .color(egui::Color32::from_rgb(200, 230, 200))
+                        .size(13.0),
+                )
+                .wrap(true),
+            );
+        });
+    ui.add_space(4.0);
+
+    action
 }
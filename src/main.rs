@@ -1,5 +1,6 @@
 mod app; // Подключение корневого модуля приложения
 
+use app::cli::Launch;
 use app::AssistantApp;
 use eframe::egui;
 
@@ -7,6 +8,14 @@ use eframe::egui;
 #[tokio::main]
 async fn main() -> Result<(), eframe::Error> {
     // <-- Делаем функцию асинхронной
+    // Аргументы разбирает режим командной строки; окно открывается без них,
+    // если оно ещё не открыто (иначе поднимается уже запущенное)
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let input = match app::cli::run(&args).await {
+        Launch::Window(input) => input,
+        Launch::Exit(code) => std::process::exit(code),
+    };
+
     // Настройка параметров графического окна
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
@@ -20,6 +29,6 @@ async fn main() -> Result<(), eframe::Error> {
         "Alfons AI", // Уникальный ID приложения
         options,
         // Передача контекста eframe в конструктор ассистента
-        Box::new(|cc| Box::new(AssistantApp::new(cc))),
+        Box::new(move |cc| Box::new(AssistantApp::new(cc, input))),
     )
 }